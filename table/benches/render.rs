@@ -0,0 +1,69 @@
+use std::convert::Infallible;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion_table::builder::TableBuilder;
+use criterion_table::formatter::GFMFormatter;
+use criterion_table::{CriterionTableData, TablesConfig};
+
+const ROWS: usize = 10;
+const COLUMNS: usize = 4;
+
+// A `benchmark-complete` message for `id` with every estimate `time` nanoseconds
+fn message(id: &str, time: f64) -> String {
+    let estimate = format!(
+        r#"{{"estimate": {time}, "lower_bound": {time}, "upper_bound": {time}, "unit": "ns"}}"#
+    );
+
+    format!(
+        r#"{{"reason": "benchmark-complete", "id": "{id}", "report_directory": "", "iteration_count": [1], "measured_values": [{time}], "unit": "ns", "throughput": [], "typical": {estimate}, "mean": {estimate}, "median": {estimate}, "median_abs_dev": {estimate}, "slope": null, "change": null}}"#
+    )
+}
+
+// A suite of `tables` tables, each with `ROWS` rows of `COLUMNS` columns
+fn suite(tables: usize) -> CriterionTableData {
+    let mut input = String::new();
+
+    for table in 0..tables {
+        for column in 0..COLUMNS {
+            for row in 0..ROWS {
+                let id = format!("Table {table}/Column {column}/{row}");
+                let time = ((table + 1) * (column + 1) * (row + 1)) as f64;
+                input.push_str(&message(&id, time));
+                input.push('\n');
+            }
+        }
+    }
+
+    TableBuilder::new()
+        .add_reader(input.as_bytes())
+        .build_data()
+        .expect("valid benchmark messages")
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Split Tables");
+    let config = TablesConfig::default();
+
+    for tables in [10, 100, 500] {
+        let data = suite(tables);
+
+        let id = BenchmarkId::new("Serial", tables);
+        group.bench_with_input(id, &data, |b, data| {
+            b.iter(|| {
+                data.make_tables_split(|| Ok::<_, Infallible>(GFMFormatter::default()), &config)
+            })
+        });
+
+        let id = BenchmarkId::new("Parallel", tables);
+        group.bench_with_input(id, &data, |b, data| {
+            b.iter(|| {
+                data.par_make_tables_split(|| Ok::<_, Infallible>(GFMFormatter::default()), &config)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
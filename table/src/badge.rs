@@ -0,0 +1,153 @@
+use flexstr::{a_flex_fmt, AFlexStr};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{Comparison, CriterionTableData, Error};
+
+// Approximate width in pixels of a char of the badge font (11px Verdana) and the padding around
+// each half of the badge
+const CHAR_WIDTH: f64 = 7.0;
+const PADDING: f64 = 10.0;
+
+// *** Badge Spec ***
+
+/// Selects the cell of a table a badge is made for (ex: `[[badges]]` in the config)
+#[derive(Clone, Debug, Deserialize)]
+pub struct BadgeSpec {
+    /// The name of the table
+    pub table: AFlexStr,
+    /// The name of the column
+    pub column: AFlexStr,
+    /// The name of the row (blank for benchmarks with only a table and column)
+    #[serde(default)]
+    pub row: AFlexStr,
+    /// The label on the left side of the badge (default: the table name)
+    pub label: Option<AFlexStr>,
+    /// The file name of the badge, without extension (default: `table-column-row`, lowercase with
+    /// spaces replaced by dashes)
+    pub name: Option<AFlexStr>,
+}
+
+impl BadgeSpec {
+    /// The file name of the badge, without extension
+    pub fn file_name(&self) -> AFlexStr {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let parts: Vec<_> = [&self.table, &self.column, &self.row]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .map(|part| part.replace(['/', ' '], "-").to_lowercase())
+                    .collect();
+                parts.join("-").into()
+            }
+        }
+    }
+}
+
+// *** Badge ***
+
+/// A badge showing a headline number (ex: `parse: 1.30 us (2.10x faster)`)
+#[derive(Clone, Debug)]
+pub struct Badge {
+    /// The text on the left side of the badge
+    pub label: AFlexStr,
+    /// The text on the right side of the badge: the time and comparison of the cell
+    pub message: AFlexStr,
+    /// The color of the right side of the badge (a shields.io color name)
+    pub color: &'static str,
+}
+
+impl Badge {
+    /// Make the badge selected by `spec` from the processed table data
+    pub fn try_new(data: &CriterionTableData, spec: &BadgeSpec) -> Result<Self, Error> {
+        let col = data
+            .tables
+            .get(&spec.table)
+            .and_then(|table| {
+                let col = table.rows.get(&spec.row)?.column_data.get(&spec.column)?;
+                Some((col, table.time_format, table.comparison_style))
+            })
+            .ok_or_else(|| Error::NoBadgeBenchmark {
+                table: spec.table.to_string(),
+                column: spec.column.to_string(),
+                row: spec.row.to_string(),
+            })?;
+
+        let (col, time_format, comparison_style) = col;
+
+        Ok(Self {
+            label: spec.label.clone().unwrap_or_else(|| spec.table.clone()),
+            message: a_flex_fmt!(
+                "{} ({})",
+                col.measurement.format(&time_format),
+                col.pct.format(comparison_style, &time_format)
+            ),
+            color: Self::color(&col.pct),
+        })
+    }
+
+    // Same thresholds as the GFM formatter
+    fn color(compare: &Comparison) -> &'static str {
+        if *compare >= 1.8 {
+            "brightgreen"
+        } else if *compare > 0.9 {
+            "green"
+        } else if *compare < 0.9 {
+            "red"
+        } else {
+            "lightgrey"
+        }
+    }
+
+    /// Returns the badge as shields.io endpoint JSON (see https://shields.io/badges/endpoint-badge)
+    pub fn to_endpoint_json(&self) -> String {
+        let endpoint = json!({
+            "schemaVersion": 1,
+            "label": self.label,
+            "message": self.message,
+            "color": self.color,
+        });
+
+        // Serializing a JSON value can't fail
+        serde_json::to_string_pretty(&endpoint).unwrap_or_default()
+    }
+
+    /// Returns the badge as a self-contained SVG image, in the flat shields.io style
+    pub fn to_svg(&self) -> String {
+        let text_width = |s: &str| s.chars().count() as f64 * CHAR_WIDTH + PADDING;
+        let label_width = text_width(&self.label);
+        let message_width = text_width(&self.message);
+        let width = label_width + message_width;
+        let fill = match self.color {
+            "brightgreen" => "#4c1",
+            "green" => "#97ca00",
+            "red" => "#e05d44",
+            _ => "#9f9f9f",
+        };
+        let (label, message) = (escape(&self.label), escape(&self.message));
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"20\" \
+             role=\"img\" aria-label=\"{label}: {message}\">\n\
+             <title>{label}: {message}</title>\n\
+             <rect width=\"{label_width:.0}\" height=\"20\" fill=\"#555\"/>\n\
+             <rect x=\"{label_width:.0}\" width=\"{message_width:.0}\" height=\"20\" \
+             fill=\"{fill}\"/>\n\
+             <g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,sans-serif\" \
+             font-size=\"11\">\n\
+             <text x=\"{:.1}\" y=\"14\">{label}</text>\n\
+             <text x=\"{:.1}\" y=\"14\">{message}</text>\n\
+             </g>\n</svg>\n",
+            label_width / 2.0,
+            label_width + message_width / 2.0,
+        )
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
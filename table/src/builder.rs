@@ -0,0 +1,264 @@
+use std::io::Read;
+
+use flexstr::AFlexStr;
+
+use crate::formatter::{FormatterRegistry, GFMFormatter};
+use crate::{
+    AggregateRow, ConfigWarning, CriterionTableData, DuplicatePolicy, Error, Formatter, IdParser,
+    IdPattern, IdScheme, Metric, RawCriterionData, RowSort, TablesConfig, ThroughputDisplay,
+};
+
+// *** Table Builder ***
+
+/// Builds tables from raw `cargo-criterion` JSON data in one chain of calls, exposing the
+/// processing options of `TablesConfig` as methods:
+///
+/// ```
+/// use criterion_table::builder::TableBuilder;
+/// use criterion_table::Metric;
+///
+/// let json = r#"{"reason":"benchmark-complete","id":"Fib/std/10","report_directory":"",
+///     "iteration_count":[],"measured_values":[],"unit":"ns","throughput":[],
+///     "typical":{"estimate":2.0,"lower_bound":1.9,"upper_bound":2.1,"unit":"ns"},
+///     "mean":{"estimate":2.0,"lower_bound":1.9,"upper_bound":2.1,"unit":"ns"},
+///     "median":{"estimate":2.0,"lower_bound":1.9,"upper_bound":2.1,"unit":"ns"},
+///     "median_abs_dev":{"estimate":0.1,"lower_bound":0.1,"upper_bound":0.1,"unit":"ns"},
+///     "slope":null,"change":null}"#;
+///
+/// let tables = TableBuilder::new()
+///     .add_reader(json.as_bytes())
+///     .metric(Metric::Median)
+///     .baseline("std")
+///     .build()
+///     .unwrap();
+/// assert!(tables.contains("## Benchmark Results"));
+/// ```
+///
+/// When given more than one reader, the data of each is merged via the `duplicates` policy
+pub struct TableBuilder<'a> {
+    inputs: Vec<serde_json::Result<Vec<RawCriterionData>>>,
+    formatter: Option<Box<dyn Formatter + 'a>>,
+    registry: Option<&'a FormatterRegistry>,
+    id_parser: Option<Box<dyn IdParser + 'a>>,
+    config: TablesConfig,
+}
+
+impl Default for TableBuilder<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> TableBuilder<'a> {
+    /// Create a new `TableBuilder` with a blank config and no input
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            inputs: Vec::new(),
+            formatter: None,
+            registry: None,
+            id_parser: None,
+            config: Default::default(),
+        }
+    }
+
+    /// Add raw `cargo-criterion` JSON data from a reader. Any error reading it is reported by
+    /// `build`
+    pub fn add_reader(mut self, r: impl Read) -> Self {
+        self.inputs.push(RawCriterionData::from_reader(r));
+        self
+    }
+
+    /// Add already loaded raw data (ex: from `criterion_dir::load`)
+    pub fn add_raw(mut self, raw_data: Vec<RawCriterionData>) -> Self {
+        self.inputs.push(Ok(raw_data));
+        self
+    }
+
+    /// The formatter the tables are rendered with (default: the one named by `format` in the
+    /// config, otherwise `GFMFormatter` with the `gfm` options of the config)
+    pub fn formatter(mut self, f: impl Formatter + 'a) -> Self {
+        self.formatter = Some(Box::new(f));
+        self
+    }
+
+    /// The registry the `format` of the config is looked up in (default: the built-in formats)
+    pub fn registry(mut self, registry: &'a FormatterRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Replace all the options with those of `config`, so this should be called before any other
+    /// option method (ex: with the result of `load_config`)
+    pub fn config(mut self, config: TablesConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The estimate of each benchmark that is tabulated
+    pub fn metric(mut self, metric: Metric) -> Self {
+        self.config.metric = metric;
+        self
+    }
+
+    /// The column each row is compared to (moved before all other columns)
+    pub fn baseline(mut self, column: impl Into<AFlexStr>) -> Self {
+        self.config.column_order = vec![column.into()];
+        self
+    }
+
+    /// How benchmark ids are split into table, column, and row names
+    pub fn id_scheme(mut self, id_scheme: IdScheme) -> Self {
+        self.config.id_scheme = id_scheme;
+        self
+    }
+
+    /// A custom benchmark id parser, used instead of the id scheme
+    pub fn id_parser(mut self, id_parser: impl IdParser + 'a) -> Self {
+        self.id_parser = Some(Box::new(id_parser));
+        self
+    }
+
+    /// What to do with duplicate benchmarks when merging multiple inputs
+    pub fn duplicates(mut self, policy: DuplicatePolicy) -> Self {
+        self.config.duplicates = policy;
+        self
+    }
+
+    /// Strip prefixes and suffixes shared by all column names (or all row names) of a table
+    pub fn strip_affixes(mut self, strip: bool) -> Self {
+        self.config.strip_affixes = strip;
+        self
+    }
+
+    /// Only keep benchmarks slower than the baseline of their row by more than this fraction
+    pub fn regressions_only(mut self, threshold: f64) -> Self {
+        self.config.regressions_only = Some(threshold);
+        self
+    }
+
+    /// Display the throughput of benchmarks that report it next to, or instead of, the time
+    pub fn throughput(mut self, display: ThroughputDisplay) -> Self {
+        self.config.throughput = display;
+        self
+    }
+
+    /// Display criterion's own verdict on the change of each benchmark since its previous run
+    pub fn change_column(mut self, show: bool) -> Self {
+        self.config.change_column = show;
+        self
+    }
+
+    /// Remove these columns from every table
+    pub fn hide_columns(mut self, columns: impl IntoIterator<Item = impl Into<AFlexStr>>) -> Self {
+        self.config.hide_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Remove benchmarks with any of these tags
+    pub fn exclude_tags(mut self, tags: impl IntoIterator<Item = impl Into<AFlexStr>>) -> Self {
+        self.config.exclude_tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only keep benchmarks whose id matches any of these patterns
+    pub fn include(mut self, patterns: impl IntoIterator<Item = IdPattern>) -> Self {
+        self.config.include = patterns.into_iter().collect();
+        self
+    }
+
+    /// Remove benchmarks whose id matches any of these patterns
+    pub fn exclude(mut self, patterns: impl IntoIterator<Item = IdPattern>) -> Self {
+        self.config.exclude = patterns.into_iter().collect();
+        self
+    }
+
+    /// Add a summary row to each table with the geometric mean of the comparisons of each column
+    pub fn geomean_row(mut self, geomean_row: bool) -> Self {
+        self.config.geomean_row = geomean_row;
+        self
+    }
+
+    /// Append rows summarizing all rows to each table, in the given order
+    pub fn aggregate_rows(
+        mut self,
+        aggregate_rows: impl IntoIterator<Item = AggregateRow>,
+    ) -> Self {
+        self.config.aggregate_rows = aggregate_rows.into_iter().collect();
+        self
+    }
+
+    /// Sort the rows of each table, in descending order if `descending` is set
+    pub fn sort_rows(mut self, sort: RowSort, descending: bool) -> Self {
+        self.config.sort_rows = Some(sort);
+        self.config.sort_descending = descending;
+        self
+    }
+
+    /// Process the input into table data, with all the options applied
+    #[inline]
+    pub fn build_data(mut self) -> Result<CriterionTableData, Error> {
+        Ok(self.process()?.0)
+    }
+
+    /// Process the input and render the tables with the formatter
+    #[inline]
+    pub fn build(self) -> Result<String, Error> {
+        Ok(self.build_with_warnings()?.0)
+    }
+
+    /// Like `build`, but also returns the config entries that matched no table (ex: a misspelled
+    /// key of `table_comments`)
+    pub fn build_with_warnings(mut self) -> Result<(String, Vec<ConfigWarning>), Error> {
+        let (data, warnings) = self.process()?;
+
+        let tables = match (self.formatter, &self.config.format) {
+            (Some(f), _) => data.make_tables(f, &self.config),
+            (None, Some(format)) => {
+                let f = match self.registry {
+                    Some(registry) => registry.make(format, &self.config)?,
+                    None => FormatterRegistry::new().make(format, &self.config)?,
+                };
+                data.make_tables(f, &self.config)
+            }
+            (None, None) => {
+                data.make_tables(GFMFormatter::new(self.config.gfm.clone()), &self.config)
+            }
+        };
+        Ok((tables, warnings))
+    }
+
+    fn process(&mut self) -> Result<(CriterionTableData, Vec<ConfigWarning>), Error> {
+        if self.inputs.is_empty() {
+            return Err(Error::NoInput);
+        }
+
+        let id_parser: &dyn IdParser = match &self.id_parser {
+            Some(id_parser) => id_parser.as_ref(),
+            None => &self.config.id_scheme,
+        };
+
+        let mut all_data = Vec::with_capacity(self.inputs.len());
+        for (idx, input) in self.inputs.drain(..).enumerate() {
+            let raw_data = input.map_err(|err| Error::Input {
+                index: idx + 1,
+                source: Box::new(err.into()),
+            })?;
+            all_data.push(CriterionTableData::from_raw(
+                &raw_data,
+                self.config.metric,
+                id_parser,
+            )?);
+        }
+
+        let mut data = if all_data.len() == 1 {
+            all_data.remove(0)
+        } else {
+            CriterionTableData::merge(all_data, self.config.duplicates)?
+        };
+        let warnings = data.check_config(&self.config)?;
+        data.apply_config(&self.config)?;
+        Ok((data, warnings))
+    }
+}
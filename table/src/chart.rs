@@ -0,0 +1,152 @@
+use flexstr::{a_flex_fmt, AFlexStr};
+use serde::Deserialize;
+
+use crate::Table;
+
+// Layout of a chart, in pixels
+const CHART_WIDTH: usize = 720;
+const LABEL_WIDTH: usize = 180;
+const VALUE_WIDTH: usize = 100;
+const BAR_HEIGHT: usize = 16;
+const BAR_GAP: usize = 4;
+const GROUP_GAP: usize = 12;
+const LEGEND_HEIGHT: usize = 20;
+const MARGIN: usize = 8;
+
+// The fill color of each column's bars (repeated when there are more columns)
+const PALETTE: &[&str] = &[
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+];
+
+// *** Chart Options ***
+
+/// Options used to customize the SVG bar charts
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ChartOptions {
+    /// Scale the bars logarithmically instead of linearly, so timings that differ by orders of
+    /// magnitude are all visible
+    pub log_scale: bool,
+}
+
+// *** Bar Chart ***
+
+/// Returns the file name of the chart of the table `name` (ex: `fibonacci.svg`)
+pub fn file_name(name: &str) -> AFlexStr {
+    a_flex_fmt!("{}.svg", name.replace(' ', "-").to_lowercase())
+}
+
+// Returns an SVG horizontal bar chart of the timings of `table`: one group of bars per row, with
+// one bar per column (in the same colors as the legend above the chart)
+pub(crate) fn bar_chart(table: &Table, options: &ChartOptions) -> String {
+    // Skip the first column - it only holds the row names
+    let columns = &table.columns.0[1..];
+    let times = || {
+        table
+            .rows
+            .values()
+            .flat_map(|row| row.column_data.values())
+            .map(|col| col.measurement.base_value())
+    };
+    let max_time = times().fold(0.0, f64::max);
+    // Log scaled bars start a decade below the fastest time, so even it has a visible bar
+    let log_floor = times().fold(f64::INFINITY, f64::min) / 10.0;
+
+    let group_height = |row_cols: usize| row_cols * (BAR_HEIGHT + BAR_GAP) + GROUP_GAP;
+    let bars_height: usize = table
+        .rows
+        .values()
+        .map(|row| group_height(row.column_data.len()))
+        .sum();
+    let height = MARGIN * 2 + LEGEND_HEIGHT * columns.len() + bars_height;
+    let bar_area = CHART_WIDTH - LABEL_WIDTH - VALUE_WIDTH - MARGIN * 2;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{height}\" \
+         font-family=\"sans-serif\" font-size=\"12\">\n"
+    );
+    svg.push_str(&format!("<title>{}</title>\n", escape(&table.name)));
+    if options.log_scale {
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">log scale</text>\n",
+            CHART_WIDTH - MARGIN,
+            MARGIN + 10
+        ));
+    }
+
+    // Legend
+    let mut y = MARGIN;
+    for (idx, column) in columns.iter().enumerate() {
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{y}\" width=\"12\" height=\"12\" fill=\"{}\"/>\n",
+            MARGIN + LABEL_WIDTH,
+            color(idx)
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\">{}</text>\n",
+            MARGIN + LABEL_WIDTH + 18,
+            y + 10,
+            escape(&column.name)
+        ));
+        y += LEGEND_HEIGHT;
+    }
+
+    // One group of bars per row
+    for row in table.rows.values() {
+        let group_y = y;
+        svg.push_str(&format!(
+            "<text x=\"{MARGIN}\" y=\"{}\">{}</text>\n",
+            group_y + BAR_HEIGHT - 4,
+            escape(&row.name)
+        ));
+
+        for (idx, column) in columns.iter().enumerate() {
+            if let Some(col) = row.column_data.get(&column.name) {
+                let time = col.measurement.base_value();
+                let scale = if options.log_scale {
+                    (time / log_floor).ln() / (max_time / log_floor).ln()
+                } else {
+                    time / max_time
+                };
+                // Always show at least a sliver, so tiny times are still visible
+                let width = if scale.is_finite() {
+                    (scale * bar_area as f64).max(1.0)
+                } else {
+                    1.0
+                };
+                let time_str = col.measurement.format(&table.time_format);
+
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{y}\" width=\"{width:.1}\" height=\"{BAR_HEIGHT}\" \
+                     fill=\"{}\"><title>{}: {time_str}</title></rect>\n",
+                    MARGIN + LABEL_WIDTH,
+                    color(idx),
+                    escape(&column.name),
+                ));
+                svg.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{}\">{time_str}</text>\n",
+                    (MARGIN + LABEL_WIDTH) as f64 + width + 4.0,
+                    y + BAR_HEIGHT - 4,
+                ));
+                y += BAR_HEIGHT + BAR_GAP;
+            }
+        }
+
+        y = group_y + group_height(row.column_data.len());
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[inline]
+fn color(idx: usize) -> &'static str {
+    PALETTE[idx % PALETTE.len()]
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,499 @@
+use std::cmp::Ordering;
+
+use flexstr::{a_flex_fmt, AFlexStr, ToAFlexStr};
+use serde::{Deserialize, Serialize};
+
+use crate::{display_width, ChangeType, Measurement, TimeFormat, SIGNIFICANT_CHANGE};
+
+// *** Speedup Tiers ***
+
+/// How many times faster (or slower) than the baseline a comparison must at least be to be in a
+/// tier
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TierBound {
+    /// At least this many times faster (ex: `2.0`)
+    Faster(f64),
+    /// At least this many times slower (ex: `2.0`)
+    Slower(f64),
+}
+
+/// A tier of comparisons by their magnitude (ex: `faster = 10.0` for at least 10x faster), marked
+/// by every formatter that marks comparisons. A comparison is only in the most extreme tier it
+/// qualifies for, and comparisons in no tier aren't marked
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpeedupTier {
+    /// The bound comparisons must reach to be in the tier
+    #[serde(flatten)]
+    pub bound: TierBound,
+    /// The marker of comparisons in the tier (ex: `🚀`)
+    pub marker: AFlexStr,
+}
+
+/// The marker of a comparison from the speedup tiers of the config
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TierMarker<'a> {
+    /// No tiers are configured, so formatters use their own markers
+    Untiered,
+    /// The comparison is in no tier, so it isn't marked
+    Unmarked,
+    /// The marker of the tier of the comparison
+    Marked(&'a AFlexStr),
+}
+
+impl<'a> TierMarker<'a> {
+    /// The marker of the tier (blank if in none), or `default` (the formatter's own marker) when
+    /// no tiers are configured
+    #[inline]
+    pub fn or<'b>(self, default: &'b str) -> &'b str
+    where
+        'a: 'b,
+    {
+        match self {
+            TierMarker::Untiered => default,
+            TierMarker::Unmarked => "",
+            TierMarker::Marked(marker) => marker,
+        }
+    }
+}
+
+// The marker of the most extreme tier `compare` is in, if any
+pub(crate) fn tier_marker<'a>(
+    tiers: &'a [SpeedupTier],
+    compare: &Comparison,
+) -> Option<&'a AFlexStr> {
+    tiers
+        .iter()
+        .filter_map(|tier| {
+            let (speedup, bound) = match tier.bound {
+                TierBound::Faster(bound) => (compare.ratio(), bound),
+                TierBound::Slower(bound) => (1.0 / compare.ratio(), bound),
+            };
+            (speedup >= bound).then_some((bound, &tier.marker))
+        })
+        .max_by(|(bound1, _), (bound2, _)| bound1.total_cmp(bound2))
+        .map(|(_, marker)| marker)
+}
+
+// *** Comparison ***
+
+/// How comparisons to the baseline are displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum ComparisonStyle {
+    /// The ratio of the times (ex: `1.43x faster`)
+    #[default]
+    Ratio,
+    /// The relative change of the time (ex: `-30.07%`, where negative is faster)
+    Percent,
+    /// The difference of the times (ex: `-1.20 us`, where negative is faster)
+    Delta,
+}
+
+/// A comparison time of a benchmark to its baseline
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(from = "ComparisonData")]
+pub struct Comparison {
+    ratio: f64,
+    // The baseline time and the time of the benchmark (not known for summaries, ex: geomeans)
+    times: Option<(Measurement, Measurement)>,
+    #[serde(default)]
+    wording: Wording,
+}
+
+// How a comparison is put into words: less time is faster, but less of anything else (ex: bytes)
+// is just lower
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+enum Wording {
+    #[default]
+    Speed,
+    Amount,
+}
+
+impl Wording {
+    #[inline]
+    fn of(measurement: &Measurement) -> Self {
+        match measurement {
+            Measurement::Time(_) => Wording::Speed,
+            _ => Wording::Amount,
+        }
+    }
+
+    // The words for a ratio above 1.0 and below it
+    #[inline]
+    fn words(self) -> (&'static str, &'static str) {
+        match self {
+            Wording::Speed => ("faster", "slower"),
+            Wording::Amount => ("lower", "higher"),
+        }
+    }
+}
+
+// Comparisons were saved (ex: in snapshots) as just the ratio
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ComparisonData {
+    Ratio(f64),
+    Full {
+        ratio: f64,
+        times: Option<(Measurement, Measurement)>,
+        #[serde(default)]
+        wording: Wording,
+    },
+}
+
+impl From<ComparisonData> for Comparison {
+    fn from(data: ComparisonData) -> Self {
+        match data {
+            ComparisonData::Ratio(ratio) => Self::from_ratio(ratio),
+            ComparisonData::Full {
+                ratio,
+                times,
+                wording,
+            } => Self {
+                ratio,
+                times,
+                wording,
+            },
+        }
+    }
+}
+
+impl Comparison {
+    /// Create a comparison of `time` to the `baseline` time
+    #[inline]
+    pub fn new(baseline: &Measurement, time: &Measurement) -> Self {
+        Self::with_times(baseline / time, baseline, time)
+    }
+
+    /// Create a comparison of the given `ratio` (ex: of throughput) between the `baseline` time and
+    /// `time`
+    #[inline]
+    pub fn with_times(ratio: f64, baseline: &Measurement, time: &Measurement) -> Self {
+        Self {
+            ratio,
+            times: Some((baseline.clone(), time.clone())),
+            wording: Wording::of(baseline),
+        }
+    }
+
+    /// Create a comparison with only a ratio (ex: a summary of other comparisons)
+    #[inline]
+    pub fn from_ratio(ratio: f64) -> Self {
+        Self {
+            ratio,
+            times: None,
+            wording: Wording::Speed,
+        }
+    }
+
+    // Returns a summary comparison with `ratio`, worded like this comparison
+    #[inline]
+    pub(crate) fn summary(&self, ratio: f64) -> Self {
+        Self {
+            ratio,
+            times: None,
+            wording: self.wording,
+        }
+    }
+
+    /// The ratio of the baseline time to this time (greater than 1.0 means faster)
+    #[inline]
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// The baseline time and the time of the benchmark, if known
+    #[inline]
+    pub fn times(&self) -> Option<&(Measurement, Measurement)> {
+        self.times.as_ref()
+    }
+
+    /// True if the difference is at most `threshold` (faster or slower, ex: 0.05 for 5%). An
+    /// exact match (ex: the baseline itself) is never noise
+    #[inline]
+    pub fn is_noise(&self, threshold: f64) -> bool {
+        self.ratio != 1.0 && f64::max(self.ratio, 1.0 / self.ratio) <= 1.0 + threshold
+    }
+
+    /// True if the difference is more than 10% (faster or slower)
+    #[inline]
+    pub fn is_significant(&self) -> bool {
+        self.ratio > 1.0 + SIGNIFICANT_CHANGE || 1.0 / self.ratio > 1.0 + SIGNIFICANT_CHANGE
+    }
+
+    /// The display width in columns of this comparison data
+    #[inline]
+    pub fn width(&self) -> usize {
+        display_width(&self.to_a_flex_str())
+    }
+
+    /// Returns this comparison as it should be displayed in `style`. Deltas are formatted like
+    /// times (with `time_format`) and need both times, so a ratio is displayed without them
+    pub fn format(&self, style: ComparisonStyle, time_format: &TimeFormat) -> AFlexStr {
+        match (style, &self.times) {
+            (ComparisonStyle::Percent, _) => {
+                a_flex_fmt!("{:+.2}%", (1.0 / self.ratio - 1.0) * 100.0)
+            }
+            (ComparisonStyle::Delta, Some((baseline, time))) => {
+                let sign = if time.base_value() < baseline.base_value() {
+                    '-'
+                } else {
+                    '+'
+                };
+                a_flex_fmt!("{sign}{}", time.abs_diff(baseline).format(time_format))
+            }
+            (ComparisonStyle::Ratio | ComparisonStyle::Delta, _) => self.to_a_flex_str(),
+        }
+    }
+}
+
+impl ToAFlexStr for Comparison {
+    fn to_a_flex_str(&self) -> AFlexStr {
+        let (lower, higher) = self.wording.words();
+        if self.ratio > 1.0 {
+            a_flex_fmt!("{:.2}x {lower}", self.ratio)
+        } else if self.ratio < 1.0 {
+            a_flex_fmt!("{:.2}x {higher}", 1.0 / self.ratio)
+        } else {
+            a_flex_fmt!("{:.2}x", self.ratio)
+        }
+    }
+}
+
+// Comparisons are equal and ordered by ratio only
+
+impl PartialEq for Comparison {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        f64::eq(&self.ratio, &other.ratio)
+    }
+}
+
+impl PartialOrd for Comparison {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        f64::partial_cmp(&self.ratio, &other.ratio)
+    }
+}
+
+impl PartialEq<f64> for Comparison {
+    #[inline]
+    fn eq(&self, other: &f64) -> bool {
+        f64::eq(&self.ratio, other)
+    }
+}
+
+impl PartialOrd<f64> for Comparison {
+    #[inline]
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        f64::partial_cmp(&self.ratio, other)
+    }
+}
+
+// *** Change ***
+
+/// Criterion's change of a benchmark since its previous run
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    /// Criterion's verdict on the change
+    pub verdict: ChangeType,
+    /// The relative change of the estimate (ex: 0.05 is 5% slower)
+    pub relative: f64,
+}
+
+impl Change {
+    /// The display width in columns of this change data
+    #[inline]
+    pub fn width(self) -> usize {
+        display_width(&self.to_a_flex_str())
+    }
+}
+
+impl ToAFlexStr for Change {
+    #[inline]
+    fn to_a_flex_str(&self) -> AFlexStr {
+        a_flex_fmt!("{:+.2}%", self.relative * 100.0)
+    }
+}
+
+// *** Regression ***
+
+/// A benchmark that is slower than its baseline (see `CriterionTableData::regressions`)
+#[derive(Clone, Debug)]
+pub struct Regression {
+    /// The name of the table
+    pub table: AFlexStr,
+    /// The name of the column (the run when comparing runs)
+    pub column: AFlexStr,
+    /// The name of the row
+    pub row: AFlexStr,
+    /// The relative change in time (ex: 0.05 is 5% slower)
+    pub change: f64,
+}
+
+// *** Delta ***
+
+/// The change in time of a benchmark between two runs (see `CriterionTableData::deltas`)
+#[derive(Clone, Debug)]
+pub struct Delta {
+    /// The name of the table
+    pub table: AFlexStr,
+    /// The name of the column
+    pub column: AFlexStr,
+    /// The name of the row
+    pub row: AFlexStr,
+    /// The time of the baseline run
+    pub old: Measurement,
+    /// The time of the latest run
+    pub new: Measurement,
+    /// The relative change in time (ex: 0.05 is 5% slower, -0.05 is 5% faster)
+    pub change: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::GFMFormatter;
+    use crate::{CriterionTableData, TablesConfig};
+
+    // A comparison of `time` to `baseline` (in nanoseconds)
+    fn compare(baseline: f64, time: f64) -> Comparison {
+        Comparison::new(
+            &Measurement::new(baseline, "ns"),
+            &Measurement::new(time, "ns"),
+        )
+    }
+
+    fn format(compare: &Comparison, style: ComparisonStyle) -> String {
+        compare.format(style, &TimeFormat::default()).to_string()
+    }
+
+    #[test]
+    fn comparison_styles() {
+        let faster = compare(2000.0, 1400.0);
+        assert_eq!(format(&faster, ComparisonStyle::Ratio), "1.43x faster");
+        assert_eq!(format(&faster, ComparisonStyle::Percent), "-30.00%");
+        assert_eq!(format(&faster, ComparisonStyle::Delta), "-600.00 ns");
+
+        let slower = compare(1000.0, 1500.0);
+        assert_eq!(format(&slower, ComparisonStyle::Ratio), "1.50x slower");
+        assert_eq!(format(&slower, ComparisonStyle::Percent), "+50.00%");
+        assert_eq!(format(&slower, ComparisonStyle::Delta), "+500.00 ns");
+
+        let same = compare(1000.0, 1000.0);
+        assert_eq!(format(&same, ComparisonStyle::Ratio), "1.00x");
+        assert_eq!(format(&same, ComparisonStyle::Percent), "+0.00%");
+    }
+
+    #[test]
+    fn style_per_table() {
+        let config = TablesConfig::try_load_config(
+            "comparison_style = \"percent\"\n[table.Sort]\ncomparison_style = \"delta\"".as_bytes(),
+        )
+        .expect("valid config");
+        let mut data = CriterionTableData::from_times(&[
+            ("Fib/std/10", 2000.0),
+            ("Fib/fast/10", 1400.0),
+            ("Sort/std/10", 2000.0),
+            ("Sort/fast/10", 1400.0),
+        ]);
+        data.apply_config(&config).expect("applied");
+
+        let tables = data.make_tables(GFMFormatter::default(), &config);
+        let (fib, sort) = tables.split_once("### Sort").expect("both tables");
+        assert!(fib.contains("-30.00%") && !fib.contains("-600.00 ns"));
+        assert!(sort.contains("-600.00 ns") && !sort.contains("-30.00%"));
+    }
+
+    #[test]
+    fn noise() {
+        assert!(compare(1000.0, 980.0).is_noise(0.05));
+        assert!(compare(1000.0, 1040.0).is_noise(0.05));
+        assert!(!compare(1000.0, 1100.0).is_noise(0.05));
+        assert!(!compare(1000.0, 900.0).is_noise(0.05));
+        // The baseline itself is never noise
+        assert!(!compare(1000.0, 1000.0).is_noise(0.05));
+    }
+
+    #[test]
+    fn noise_not_emphasized() {
+        let config = TablesConfig::try_load_config("noise_threshold = 0.05".as_bytes())
+            .expect("valid config");
+        let mut data = CriterionTableData::from_times(&[
+            ("Fib/std/10", 100.0),
+            ("Fib/fast/10", 98.0),
+            ("Fib/slow/10", 150.0),
+        ]);
+        data.apply_config(&config).expect("applied");
+
+        let tables = data.make_tables(GFMFormatter::default(), &config);
+        assert!(tables.contains("`98.00 ns` (~equal)"));
+        assert!(tables.contains("`150.00 ns` (❌ *1.50x slower*)"));
+    }
+
+    #[test]
+    fn most_extreme_tier() {
+        let tier = |bound, marker: &str| SpeedupTier {
+            bound,
+            marker: marker.into(),
+        };
+        let tiers = [
+            tier(TierBound::Faster(2.0), "fast"),
+            tier(TierBound::Faster(10.0), "fastest"),
+            tier(TierBound::Slower(1.5), "slow"),
+        ];
+        let marker = |time| tier_marker(&tiers, &compare(1000.0, time)).map(AFlexStr::as_str);
+
+        assert_eq!(marker(50.0), Some("fastest"));
+        assert_eq!(marker(500.0), Some("fast"));
+        assert_eq!(marker(800.0), None);
+        assert_eq!(marker(1000.0), None);
+        assert_eq!(marker(1500.0), Some("slow"));
+    }
+
+    #[test]
+    fn tier_markers() {
+        let config = TablesConfig::try_load_config(
+            r#"
+            [[speedup_tiers]]
+            faster = 2.0
+            marker = "🚀"
+
+            [[speedup_tiers]]
+            faster = 10.0
+            marker = "🔥"
+
+            [[speedup_tiers]]
+            slower = 1.5
+            marker = "🐢"
+            "#
+            .as_bytes(),
+        )
+        .expect("valid config");
+        let mut data = CriterionTableData::from_times(&[
+            ("Fib/std/10", 1200.0),
+            ("Fib/a/10", 100.0),
+            ("Fib/b/10", 480.0),
+            ("Fib/c/10", 1000.0),
+            ("Fib/d/10", 2400.0),
+        ]);
+        data.apply_config(&config).expect("applied");
+
+        let tables = data.make_tables(GFMFormatter::default(), &config);
+        assert!(tables.contains("`1.20 us` (**1.00x**)"));
+        assert!(tables.contains("`100.00 ns` (🔥 **12.00x faster**)"));
+        assert!(tables.contains("`480.00 ns` (🚀 **2.50x faster**)"));
+        assert!(tables.contains("`1000.00 ns` (**1.20x faster**)"));
+        assert!(tables.contains("`2.40 us` (🐢 *2.00x slower*)"));
+    }
+
+    #[test]
+    fn summary_delta_as_ratio() {
+        // A summary has no times to subtract
+        let summary = Comparison::from_ratio(2.0);
+        assert_eq!(format(&summary, ComparisonStyle::Delta), "2.00x faster");
+        assert_eq!(format(&summary, ComparisonStyle::Percent), "-50.00%");
+    }
+}
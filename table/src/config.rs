@@ -0,0 +1,741 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+
+use flexstr::{a_flex_fmt, AFlexStr, ToAFlexStr, ToFlex};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::badge::BadgeSpec;
+use crate::chart::ChartOptions;
+use crate::environment::EnvironmentOptions;
+use crate::footer::FooterOptions;
+use crate::formatter::{GFMOptions, HtmlOptions};
+use crate::hyperfine::HyperfineOptions;
+use crate::{
+    Alignment, ComparisonStyle, CriterionTableData, DuplicatePolicy, EmbedOptions, Error,
+    ExtraStatOptions, Highlight, IdPattern, IdScheme, Metric, RowSort, SpeedupTier,
+    ThroughputDisplay, TimeFormat, TocOptions, UnitPolicy, CRITERION_DIR,
+};
+
+// *** Config Warnings ***
+
+// How similar (via Jaro, from 0 to 1) a table name must be to an unknown one to be suggested
+const SUGGESTION_SIMILARITY: f64 = 0.7;
+
+/// A config entry (ex: a key of `table_comments`) that names no table of the data, so it has no
+/// effect. These are usually typos
+#[derive(Clone, Debug)]
+pub struct ConfigWarning {
+    /// The option the entry is in (ex: `table_comments`)
+    pub option: AFlexStr,
+    /// The key of the entry
+    pub key: AFlexStr,
+    /// The most similar table name (in the form the option uses), if any is close
+    pub suggestion: Option<AFlexStr>,
+}
+
+impl ConfigWarning {
+    pub(crate) fn new<'a>(
+        option: &str,
+        key: &AFlexStr,
+        names: impl IntoIterator<Item = &'a AFlexStr>,
+    ) -> Self {
+        let suggestion = names
+            .into_iter()
+            .map(|name| (strsim::jaro(key, name), name))
+            .filter(|(similarity, _)| *similarity >= SUGGESTION_SIMILARITY)
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, name)| name.clone());
+
+        Self {
+            option: option.into(),
+            key: key.clone(),
+            suggestion,
+        }
+    }
+}
+
+impl ToAFlexStr for ConfigWarning {
+    fn to_a_flex_str(&self) -> AFlexStr {
+        match &self.suggestion {
+            Some(suggestion) => a_flex_fmt!(
+                "`{}` entry `{}` matches no table (did you mean `{suggestion}`?)",
+                self.option,
+                self.key
+            ),
+            None => a_flex_fmt!("`{}` entry `{}` matches no table", self.option, self.key),
+        }
+    }
+}
+
+// *** Footnotes ***
+
+/// Footnotes attached to columns and rows (name -> footnote text), listed below the table with a
+/// marker (ex: `[1]`) after the name of each column and row they are attached to
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FootnoteOptions {
+    /// Column footnotes
+    pub columns: HashMap<AFlexStr, AFlexStr>,
+    /// Row footnotes
+    pub rows: HashMap<AFlexStr, AFlexStr>,
+}
+
+impl FootnoteOptions {
+    // The footnote of column or row `name`: from `table` if it has one, otherwise from `all`
+    pub(crate) fn find<'a>(
+        table: Option<&'a Self>,
+        all: &'a Self,
+        get: impl Fn(&'a Self) -> &'a HashMap<AFlexStr, AFlexStr>,
+        name: &AFlexStr,
+    ) -> Option<AFlexStr> {
+        table
+            .and_then(|table| get(table).get(name))
+            .or_else(|| get(all).get(name))
+            .cloned()
+    }
+}
+
+/// A footnote of a table, passed to `Formatter::footnotes`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Footnote {
+    /// The marker after the name of each column and row the footnote is attached to (ex: `[1]`)
+    pub marker: AFlexStr,
+    /// The text of the footnote
+    pub text: AFlexStr,
+}
+
+// Returns `name` with its footnote marker, if it has one
+pub(crate) fn marked_name(name: &AFlexStr, markers: &HashMap<AFlexStr, AFlexStr>) -> AFlexStr {
+    match markers.get(name) {
+        Some(marker) => a_flex_fmt!("{name} {marker}"),
+        None => name.clone(),
+    }
+}
+
+// *** Rename Options ***
+
+/// Display names used instead of the raw names of tables, columns and rows (raw name -> display
+/// name), so terse benchmark ids (ex: `fib/rec/20`) can have readable headers
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RenameOptions {
+    /// Table display names
+    pub tables: HashMap<AFlexStr, AFlexStr>,
+    /// Column display names (in every table)
+    pub columns: HashMap<AFlexStr, AFlexStr>,
+    /// Row display names (in every table)
+    pub rows: HashMap<AFlexStr, AFlexStr>,
+}
+
+// *** Column Groups ***
+
+/// Groups of related columns (ex: `serde_json (borrowed)` and `serde_json (owned)`). Formatters
+/// that support it display each group as a header spanning its columns, with the columns named
+/// without their group
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ColumnGroupOptions {
+    /// Split column names on this separator (ex: `::`), so `serde_json::owned` is column `owned`
+    /// of group `serde_json`
+    pub separator: Option<AFlexStr>,
+    /// The group of each column (column -> group), used instead of splitting its name
+    pub columns: HashMap<AFlexStr, AFlexStr>,
+}
+
+impl ColumnGroupOptions {
+    // The group of column `name` and the name it is displayed as within its group, if it has one
+    pub(crate) fn find(&self, name: &AFlexStr) -> Option<(AFlexStr, AFlexStr)> {
+        if let Some(group) = self.columns.get(name) {
+            return Some((group.clone(), name.clone()));
+        }
+
+        let (group, short_name) = name.split_once(self.separator.as_deref()?)?;
+        (!group.is_empty() && !short_name.is_empty())
+            .then(|| (group.to_flex(), short_name.to_flex()))
+    }
+}
+
+// *** Table Sections ***
+
+/// The options of one table, from its `[table."Fibonacci"]` section (keyed by its display name).
+/// These are used instead of the options for every table and of `table_comments`. Unknown keys
+/// are an error
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableOptions {
+    /// The comment written above the table
+    pub comment: Option<AFlexStr>,
+    /// The column each row is compared to (moved before all other columns)
+    pub baseline: Option<AFlexStr>,
+    /// Columns moved (in this order) before all others, after the baseline
+    pub column_order: Option<Vec<AFlexStr>>,
+    /// Columns removed, in addition to `hide_columns`
+    pub hide_columns: Vec<AFlexStr>,
+    /// Sort the rows (otherwise they are in the order criterion ran them)
+    pub sort_rows: Option<RowSort>,
+    /// Sort rows in descending instead of ascending order
+    pub sort_descending: Option<bool>,
+    /// How comparisons to the baseline are displayed
+    pub comparison_style: Option<ComparisonStyle>,
+    /// The unit every time is displayed in (ex: `table-max` for the unit of the slowest time),
+    /// used instead of the unit of `time_format`
+    pub time_unit: Option<UnitPolicy>,
+    /// Swap rows and columns
+    pub transpose: Option<bool>,
+    /// Compare every column to every other column, used instead of `matrix`
+    pub matrix: Option<MatrixOptions>,
+    /// Footnotes of columns and rows
+    pub footnotes: Option<FootnoteOptions>,
+    /// Display names of the columns and rows of this table, used before those of `rename`
+    pub rename: TableRenameOptions,
+}
+
+/// Display names of the columns and rows of one table (raw name -> display name)
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableRenameOptions {
+    /// Column display names
+    pub columns: HashMap<AFlexStr, AFlexStr>,
+    /// Row display names
+    pub rows: HashMap<AFlexStr, AFlexStr>,
+}
+
+// A config as it is read: the per table maps of older configs are merged into its table sections,
+// and any keys that aren't options are captured, so they can be reported (with `version = 2`)
+#[derive(Deserialize)]
+struct RawTablesConfig {
+    #[serde(flatten)]
+    config: TablesConfig,
+    #[serde(flatten)]
+    maps: TableMaps,
+    #[serde(flatten)]
+    unknown: IndexMap<String, toml::Value>,
+}
+
+// The per table maps (table -> option) of older configs, deprecated by the table sections
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct TableMaps {
+    table_footnotes: IndexMap<AFlexStr, FootnoteOptions>,
+    table_comparison_style: IndexMap<AFlexStr, ComparisonStyle>,
+    table_column_order: IndexMap<AFlexStr, Vec<AFlexStr>>,
+    table_hide_columns: IndexMap<AFlexStr, Vec<AFlexStr>>,
+    table_transpose: IndexMap<AFlexStr, bool>,
+}
+
+impl TableMaps {
+    // Merge each entry into the section of its table, unless the section sets that option itself.
+    // Hidden columns are added to those of the section
+    fn merge_into(self, sections: &mut IndexMap<AFlexStr, TableOptions>) {
+        for (name, footnotes) in self.table_footnotes {
+            let options = sections.entry(name).or_default();
+            options.footnotes.get_or_insert(footnotes);
+        }
+        for (name, style) in self.table_comparison_style {
+            let options = sections.entry(name).or_default();
+            options.comparison_style.get_or_insert(style);
+        }
+        for (name, order) in self.table_column_order {
+            let options = sections.entry(name).or_default();
+            options.column_order.get_or_insert(order);
+        }
+        for (name, hidden) in self.table_hide_columns {
+            let options = sections.entry(name).or_default();
+            options.hide_columns.extend(hidden);
+        }
+        for (name, transpose) in self.table_transpose {
+            let options = sections.entry(name).or_default();
+            options.transpose.get_or_insert(transpose);
+        }
+    }
+}
+
+// *** Aggregate Rows ***
+
+/// A row summarizing all rows of a table, appended after them (ex: to tables with a row per
+/// input size)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AggregateRow {
+    /// The geometric mean of the times of each column, compared as the geometric mean of its
+    /// comparisons (`Geomean time`)
+    GeomeanTime,
+    /// The arithmetic mean of the speedups of each column (`Mean speedup`)
+    MeanSpeedup,
+    /// The largest speedup of each column (`Best case`)
+    Best,
+    /// The smallest speedup of each column (`Worst case`)
+    Worst,
+}
+
+impl AggregateRow {
+    /// The name of the row
+    pub fn name(self) -> &'static str {
+        match self {
+            AggregateRow::GeomeanTime => "Geomean time",
+            AggregateRow::MeanSpeedup => "Mean speedup",
+            AggregateRow::Best => "Best case",
+            AggregateRow::Worst => "Worst case",
+        }
+    }
+}
+
+// *** Speedup Matrix ***
+
+/// Replaces the rows of a table with one per column, so every column is compared to every other
+/// column (an N×N matrix of pairwise speedups) instead of only to the first
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MatrixOptions {
+    /// The row whose benchmarks are compared. If not given, the benchmarks of all rows are
+    /// aggregated via geometric means (of the rows both columns of a pair have)
+    pub row: Option<AFlexStr>,
+}
+
+// *** Report Links ***
+
+/// What links to criterion's HTML report of each benchmark
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkTarget {
+    /// Nothing is linked
+    #[default]
+    None,
+    /// The time of each cell links to the report of its benchmark
+    Cell,
+    /// Each row name links to the report of the first benchmark of its row
+    Row,
+}
+
+/// Links from the tables to criterion's HTML reports (ex: `[report_links]` in the config), so
+/// readers can click through to the full distribution plots
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ReportLinkOptions {
+    /// What links to the reports
+    pub link: LinkTarget,
+    /// Prepended to each report path (ex: `https://example.com/criterion/` where the
+    /// `target/criterion` directory is published). With a prefix, the path of each report is
+    /// taken from within `target/criterion`. Without one, links are the report directories as
+    /// criterion gave them (ex: `target/criterion/reports/Fibonacci_Recursive Fib_10`)
+    pub url_prefix: Option<AFlexStr>,
+}
+
+impl ReportLinkOptions {
+    // The URL of the report in `report_directory`, if known
+    pub(crate) fn url(&self, report_directory: &str) -> Option<AFlexStr> {
+        if report_directory.is_empty() {
+            return None;
+        }
+
+        let dir = report_directory.replace('\\', "/");
+        let dir = dir.trim_end_matches('/');
+        let (prefix, dir) = match &self.url_prefix {
+            Some(prefix) => {
+                let dir = dir
+                    .find(CRITERION_DIR)
+                    .map(|idx| dir[idx + CRITERION_DIR.len()..].trim_start_matches('/'))
+                    .unwrap_or(dir);
+                (prefix.as_str(), dir)
+            }
+            None => ("", dir),
+        };
+
+        Some(a_flex_fmt!("{prefix}{}/index.html", percent_encode(dir)))
+    }
+}
+
+// Encodes all but the unreserved characters of a URL path (and its `/` separators)
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            byte => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+// *** Alignment ***
+
+/// How the cells of each column are aligned, by formatters that support it (ex: the GFM
+/// delimiter row). Row names are left aligned and the times of data columns right aligned by
+/// default
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AlignmentOptions {
+    /// The alignment of the row names (the first column)
+    pub row_names: Alignment,
+    /// The alignment of every data column
+    pub data: Alignment,
+    /// Per column alignment (column -> alignment), used instead of `data`
+    pub columns: HashMap<AFlexStr, Alignment>,
+}
+
+impl Default for AlignmentOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            row_names: Alignment::Left,
+            data: Alignment::Right,
+            columns: HashMap::new(),
+        }
+    }
+}
+
+impl AlignmentOptions {
+    // The alignment of the column at `idx` named `name` (the first column holds the row names)
+    pub(crate) fn find(&self, idx: usize, name: &AFlexStr) -> Alignment {
+        if idx == 0 {
+            self.row_names
+        } else {
+            self.columns.get(name).copied().unwrap_or(self.data)
+        }
+    }
+}
+
+// *** Tables Config ***
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+/// Configuration file format: the options of the tables (every table, or per table via its
+/// section) and of how they are formatted
+pub struct TablesConfig {
+    /// The version of the config format. With `2`, unknown keys are an error instead of ignored
+    pub version: Option<u32>,
+    /// The name of the output format (ex: `html`), looked up in a `FormatterRegistry`. Used when no
+    /// formatter or format is given otherwise (ex: via `--format`)
+    pub format: Option<AFlexStr>,
+    /// Top level comments
+    pub top_comments: IndexMap<AFlexStr, AFlexStr>,
+    /// The title of the document and its table of contents
+    pub toc: TocOptions,
+    /// Write only the tables, so the output can be placed in a larger document as is
+    pub embed: Option<EmbedOptions>,
+    /// An `Environment` section before the top level comments recording the benchmarking
+    /// environment (ex: the CPU model and rustc version)
+    pub environment: EnvironmentOptions,
+    /// Per table comments (table key -> comment), keyed by the table name in lowercase with spaces
+    /// replaced by `_`. The `comment` of a table section is used instead
+    pub table_comments: HashMap<AFlexStr, AFlexStr>,
+    /// Footnotes of columns and rows of every table
+    pub footnotes: FootnoteOptions,
+    /// Options for the GFM formatter
+    pub gfm: GFMOptions,
+    /// Options for the HTML formatter
+    pub html: HtmlOptions,
+    /// The footer written at the end of the output and any provenance included in it (ex: the git
+    /// commit)
+    pub footer: FooterOptions,
+    /// Options for the SVG bar charts
+    pub chart: ChartOptions,
+    /// The estimate of each benchmark that is tabulated
+    pub metric: Metric,
+    /// How benchmark ids are split into table, column, and row names
+    pub id_scheme: IdScheme,
+    /// How hyperfine commands are split into table, column, and row names (used instead of
+    /// `id_scheme` for hyperfine input)
+    pub hyperfine: HyperfineOptions,
+    /// What to do with duplicate benchmarks when merging multiple inputs
+    pub duplicates: DuplicatePolicy,
+    /// Display names of tables, columns and rows. These are applied before all other options, so
+    /// per table options (ex: `table_comments`) and column names (ex: `column_order`) use them
+    pub rename: RenameOptions,
+    /// Groups of related columns, displayed under a spanning header by formatters that support it
+    pub column_groups: ColumnGroupOptions,
+    /// How the cells of each column are aligned (ex: `right`)
+    pub alignment: AlignmentOptions,
+    /// Strip prefixes and suffixes shared by all column names (or all row names) of a table
+    /// (ex: `bench_` or `_impl`). Only whole words (up to a `_`, `-`, `.`, `:`, `/` or space)
+    /// are stripped
+    pub strip_affixes: bool,
+    /// Only keep tables with a significant change: more than 10% compared to the previous run in
+    /// baseline and series modes, otherwise criterion's own verdict on the change since its
+    /// previous run
+    pub changed_tables_only: bool,
+    /// Only keep benchmarks slower than the baseline of their row (the first column, or the
+    /// previous run in baseline and series modes) by more than this fraction (ex: 0.05 = 5%),
+    /// sorted worst first
+    pub regressions_only: Option<f64>,
+    /// Minimum width of every data column, so small timing changes don't re-pad the whole
+    /// table (keeping diffs of version controlled output limited to changed cells)
+    pub column_width: Option<usize>,
+    /// Round the width of every column up to a multiple of this, for the same reason
+    pub column_width_multiple: Option<usize>,
+    /// Display the throughput of benchmarks that report it next to, or instead of, the time. When
+    /// displayed, throughput is compared instead of time
+    pub throughput: ThroughputDisplay,
+    /// How times are displayed: significant digits, unit and a minimum time
+    pub time_format: TimeFormat,
+    /// How comparisons to the baseline are displayed: as a ratio (`1.43x faster`), percent
+    /// (`-30.07%`) or delta (`-1.20 us`). Summary rows (ex: geomeans) are always displayed as a
+    /// ratio
+    pub comparison_style: ComparisonStyle,
+    /// Highlight the fastest (and optionally slowest) cell of each row
+    pub highlight: Highlight,
+    /// Links from cells (or row names) to criterion's HTML report of each benchmark
+    pub report_links: ReportLinkOptions,
+    /// Comparisons within this fraction of the baseline (ex: 0.05 for ±5%) are displayed as
+    /// `~equal`, without being emphasized as faster or slower. Summary rows are unaffected
+    pub noise_threshold: Option<f64>,
+    /// Extra statistics of each benchmark displayed after its time (ex: the standard deviation)
+    pub extra_stats: ExtraStatOptions,
+    /// Display criterion's own verdict on the change of each benchmark since its previous run,
+    /// with the relative change of the estimate, after its comparison (ex: `❌ +3.00%`)
+    pub change_column: bool,
+    /// Columns moved (in this order) before all others in every table. The first column of each
+    /// row is the baseline the others are compared to
+    pub column_order: Vec<AFlexStr>,
+    /// Columns removed from every table
+    pub hide_columns: Vec<AFlexStr>,
+    /// Compare every column to every other column in each table (see `MatrixOptions`)
+    pub matrix: Option<MatrixOptions>,
+    /// Add a summary row to each table with the geometric mean of the comparisons of each column
+    pub geomean_row: bool,
+    /// Rows summarizing all rows of each table, appended (in this order) after them
+    pub aggregate_rows: Vec<AggregateRow>,
+    /// Tiers of comparisons by their magnitude, each with its own marker used (by the formatters
+    /// that mark comparisons) instead of the markers of the formatter
+    pub speedup_tiers: Vec<SpeedupTier>,
+    /// Sort the rows of each table (otherwise they are in the order criterion ran them)
+    pub sort_rows: Option<RowSort>,
+    /// Sort rows in descending instead of ascending order
+    pub sort_descending: bool,
+    /// Remove benchmarks with any of these tags (from an id suffix like `table/col/row#simd,unsafe`)
+    pub exclude_tags: Vec<AFlexStr>,
+    /// Only keep benchmarks whose id matches any of these patterns (ex: `Fibonacci/*`, or
+    /// `re:^Parse`)
+    pub include: Vec<IdPattern>,
+    /// Remove benchmarks whose id matches any of these patterns (applied after `include`)
+    pub exclude: Vec<IdPattern>,
+    /// The cells badges are made for by the `badge` command
+    pub badges: Vec<BadgeSpec>,
+    /// Per table sections (table -> options, ex: `[table."Fibonacci"]`), used instead of the
+    /// options for every table. Each must name a table. The per table maps of older configs (ex:
+    /// `table_transpose`) are still read, and merged into these when loaded
+    pub table: IndexMap<AFlexStr, TableOptions>,
+}
+
+impl TablesConfig {
+    /// Try to load the config from the given reader
+    pub fn try_load_config(r: impl Read) -> Result<Self, Error> {
+        let mut reader = BufReader::new(r);
+        let mut buffer = String::with_capacity(16384);
+        reader.read_to_string(&mut buffer)?;
+
+        let value: toml::Value = toml::from_str(&buffer)?;
+        let strict = value
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .is_some_and(|version| version >= 2);
+
+        let raw: RawTablesConfig = toml::from_str(&buffer)?;
+        if strict && !raw.unknown.is_empty() {
+            let keys: Vec<_> = raw.unknown.keys().map(String::as_str).collect();
+            return Err(Error::UnknownKeys {
+                keys: keys.join(", "),
+            });
+        }
+
+        let mut config = raw.config;
+        raw.maps.merge_into(&mut config.table);
+        config.metric.validate()?;
+        Ok(config)
+    }
+
+    /// True if unknown keys and config entries for unknown tables are errors (`version = 2`)
+    #[inline]
+    pub fn is_strict(&self) -> bool {
+        self.version.is_some_and(|version| version >= 2)
+    }
+
+    // The section of table `name`, if any
+    #[inline]
+    pub(crate) fn table_options(&self, name: &AFlexStr) -> Option<&TableOptions> {
+        self.table.get(name)
+    }
+
+    // The comment of table `name`
+    pub(crate) fn table_comment(&self, name: &AFlexStr) -> Option<&AFlexStr> {
+        self.table_options(name)
+            .and_then(|options| options.comment.as_ref())
+            .or_else(|| {
+                self.table_comments
+                    .get(&CriterionTableData::encode_key(name))
+            })
+    }
+
+    // The columns of table `name` moved before all others, starting with its baseline
+    pub(crate) fn table_column_order(&self, name: &AFlexStr) -> Vec<AFlexStr> {
+        let options = self.table_options(name);
+        let order = options
+            .and_then(|options| options.column_order.as_ref())
+            .unwrap_or(&self.column_order);
+
+        match options.and_then(|options| options.baseline.as_ref()) {
+            Some(baseline) => std::iter::once(baseline)
+                .chain(order.iter().filter(|column| *column != baseline))
+                .cloned()
+                .collect(),
+            None => order.clone(),
+        }
+    }
+
+    // The columns removed from table `name`
+    pub(crate) fn table_hidden_columns<'a>(
+        &'a self,
+        name: &AFlexStr,
+    ) -> impl Iterator<Item = &'a AFlexStr> + Clone {
+        let section = self
+            .table_options(name)
+            .into_iter()
+            .flat_map(|options| &options.hide_columns);
+        self.hide_columns.iter().chain(section)
+    }
+
+    pub(crate) fn table_comparison_style(&self, name: &AFlexStr) -> ComparisonStyle {
+        self.table_options(name)
+            .and_then(|options| options.comparison_style)
+            .unwrap_or(self.comparison_style)
+    }
+
+    // The time format of table `name`, before its unit is resolved from its times
+    pub(crate) fn table_time_format(&self, name: &AFlexStr) -> TimeFormat {
+        match self
+            .table_options(name)
+            .and_then(|options| options.time_unit)
+        {
+            Some(unit) => TimeFormat {
+                unit,
+                ..self.time_format
+            },
+            None => self.time_format,
+        }
+    }
+
+    pub(crate) fn table_matrix(&self, name: &AFlexStr) -> Option<&MatrixOptions> {
+        self.table_options(name)
+            .and_then(|options| options.matrix.as_ref())
+            .or(self.matrix.as_ref())
+    }
+
+    pub(crate) fn table_transpose(&self, name: &AFlexStr) -> bool {
+        self.table_options(name)
+            .and_then(|options| options.transpose)
+            .unwrap_or_default()
+    }
+
+    // The sort of the rows of table `name` and whether it is descending, if sorted
+    pub(crate) fn table_sort_rows(&self, name: &AFlexStr) -> Option<(RowSort, bool)> {
+        let options = self.table_options(name);
+        let sort = options
+            .and_then(|options| options.sort_rows)
+            .or(self.sort_rows)?;
+        let descending = options
+            .and_then(|options| options.sort_descending)
+            .unwrap_or(self.sort_descending);
+        Some((sort, descending))
+    }
+
+    pub(crate) fn table_footnotes(&self, name: &AFlexStr) -> Option<&FootnoteOptions> {
+        self.table_options(name)
+            .and_then(|options| options.footnotes.as_ref())
+    }
+}
+
+// *** Functions ***
+
+/// Load a `TablesConfig` from the file `cfg_name`. If the file doesn't exist a blank config is
+/// returned
+pub fn load_config(cfg_name: impl AsRef<Path>) -> Result<TablesConfig, Error> {
+    match File::open(cfg_name) {
+        // If the file exists, but it can't be deserialized then report that error
+        Ok(f) => Ok(TablesConfig::try_load_config(f)?),
+        // If file just isn't there then ignore and return a blank config
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(TablesConfig::default()),
+        // Report any other I/O errors
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml: &str) -> Result<TablesConfig, Error> {
+        TablesConfig::try_load_config(toml.as_bytes())
+    }
+
+    fn data() -> CriterionTableData {
+        CriterionTableData::from_times(&[("Fibonacci/std/10", 100.0), ("Sort/std/10", 10.0)])
+    }
+
+    #[test]
+    fn unknown_keys() {
+        // Ignored unless strict
+        assert!(config("sort_row = \"name\"").is_ok());
+
+        let err = config("version = 2\nsort_row = \"name\"\nnoise = 0.1");
+        match err {
+            Err(Error::UnknownKeys { keys }) => assert_eq!(keys, "sort_row, noise"),
+            _ => panic!("expected unknown keys"),
+        }
+        assert!(config("version = 2\nsort_rows = \"name\"").is_ok());
+    }
+
+    #[test]
+    fn table_maps_merged_into_sections() {
+        let config = config(
+            r#"
+            table_transpose = { Fibonacci = true, Sort = true }
+            table_hide_columns = { Sort = ["simd"] }
+
+            [table.Sort]
+            transpose = false
+            hide_columns = ["fast"]
+            "#,
+        )
+        .expect("valid config");
+
+        let section = |name: &str| config.table_options(&name.into()).expect("a section");
+        assert_eq!(section("Fibonacci").transpose, Some(true));
+        // The section's own option wins, but hidden columns are added
+        let sort = section("Sort");
+        assert_eq!(sort.transpose, Some(false));
+        assert_eq!(sort.hide_columns, ["fast", "simd"]);
+    }
+
+    #[test]
+    fn sections_for_unknown_tables() {
+        let config = config("[table.Fibonaci]\ntranspose = true").expect("valid config");
+        assert!(matches!(
+            data().check_config(&config),
+            Err(Error::UnknownTables { tables }) if tables == "Fibonaci"
+        ));
+    }
+
+    #[test]
+    fn entries_for_unknown_tables() {
+        let warnings = data()
+            .check_config(&config("[table_comments]\nfibonaci = \"Slow\"").expect("valid config"))
+            .expect("only warnings");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].to_a_flex_str(),
+            "`table_comments` entry `fibonaci` matches no table (did you mean `fibonacci`?)"
+        );
+
+        let strict =
+            config("version = 2\n[table_comments]\nfibonaci = \"Slow\"").expect("valid config");
+        assert!(matches!(
+            data().check_config(&strict),
+            Err(Error::UnknownConfigTables { .. })
+        ));
+    }
+}
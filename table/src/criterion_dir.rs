@@ -0,0 +1,234 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use flexstr::{AFlexStr, ToFlex};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    BenchmarkComplete, ChangeDetails, ChangeType, ConfidenceInterval, Error, RawCriterionData,
+    Throughput,
+};
+
+/// The baseline criterion saves the latest results of each benchmark in
+pub const LATEST_BASELINE: &str = "new";
+
+// Criterion always records estimates in nanoseconds
+const ESTIMATE_UNIT: &str = "ns";
+// Changes are recorded as a fraction of the previous result
+const CHANGE_UNIT: &str = "%";
+// Criterion's default noise threshold: smaller changes are not considered significant
+const NOISE_THRESHOLD: f64 = 0.01;
+
+// *** Criterion Directory Data Structs ***
+
+// NOTE: Only the fields we need from criterion's `benchmark.json` and `estimates.json`
+
+#[derive(Deserialize)]
+struct BenchmarkId {
+    full_id: AFlexStr,
+    // Ex: `{"Bytes": 1024}` (newer versions of criterion have more variants)
+    throughput: Option<IndexMap<AFlexStr, Value>>,
+}
+
+impl BenchmarkId {
+    fn throughput(&self) -> Vec<Throughput> {
+        let throughput = self.throughput.iter().flatten();
+
+        throughput
+            .filter_map(|(kind, per_iteration)| {
+                let unit = match kind.as_str() {
+                    "Bytes" | "BytesDecimal" => "bytes",
+                    "Elements" => "elements",
+                    _ => return None,
+                };
+
+                per_iteration.as_u64().map(|per_iteration| Throughput {
+                    per_iteration,
+                    unit: unit.into(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct DirConfidenceInterval {
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+    confidence_interval: DirConfidenceInterval,
+    point_estimate: f64,
+}
+
+impl From<&Estimate> for ConfidenceInterval {
+    fn from(estimate: &Estimate) -> Self {
+        Self {
+            estimate: estimate.point_estimate,
+            lower_bound: estimate.confidence_interval.lower_bound,
+            upper_bound: estimate.confidence_interval.upper_bound,
+            unit: ESTIMATE_UNIT.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+    median: Estimate,
+    median_abs_dev: Estimate,
+    slope: Option<Estimate>,
+}
+
+#[derive(Deserialize)]
+struct ChangeEstimates {
+    mean: Estimate,
+    median: Estimate,
+}
+
+impl ChangeEstimates {
+    fn change_details(&self) -> ChangeDetails {
+        let to_change = |estimate: &Estimate| ConfidenceInterval {
+            unit: CHANGE_UNIT.into(),
+            ..estimate.into()
+        };
+        let mean = &self.mean.confidence_interval;
+
+        // Criterion also runs a significance test, but only the estimates are saved. This is
+        // the same noise check done after it
+        let change = if mean.lower_bound > NOISE_THRESHOLD {
+            ChangeType::Regressed
+        } else if mean.upper_bound < -NOISE_THRESHOLD {
+            ChangeType::Improved
+        } else {
+            ChangeType::NoChange
+        };
+
+        ChangeDetails {
+            mean: to_change(&self.mean),
+            median: to_change(&self.median),
+            change,
+        }
+    }
+}
+
+// Older versions of criterion saved the sample as a tuple of iterations and times
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Sample {
+    Saved { iters: Vec<f64>, times: Vec<f64> },
+    Tuple(Vec<f64>, Vec<f64>),
+}
+
+impl Sample {
+    fn into_parts(self) -> (Vec<f64>, Vec<f64>) {
+        match self {
+            Sample::Saved { iters, times } | Sample::Tuple(iters, times) => (iters, times),
+        }
+    }
+}
+
+// *** Functions ***
+
+/// Load the latest raw benchmark data (the `LATEST_BASELINE`) from a criterion output directory
+/// (typically `target/criterion`), allowing tables to be built after a plain `cargo bench`. Since
+/// the execution order isn't saved, results are sorted by benchmark directory
+pub fn load(dir: impl AsRef<Path>) -> Result<Vec<RawCriterionData>, Error> {
+    load_baseline(dir, LATEST_BASELINE)
+}
+
+/// Load raw benchmark data for the named `baseline` from a criterion output directory (typically
+/// `target/criterion`). Criterion saves the latest results in `new` (`LATEST_BASELINE`), the
+/// previous results in `base`, and results saved via `--save-baseline <name>` in `<name>`.
+/// Benchmarks without the baseline are skipped and results are sorted by benchmark directory. The
+/// change from the previous run is only available for the latest results
+pub fn load_baseline(
+    dir: impl AsRef<Path>,
+    baseline: &str,
+) -> Result<Vec<RawCriterionData>, Error> {
+    let mut baseline_dirs = Vec::new();
+    find_baseline_dirs(dir.as_ref(), baseline, &mut baseline_dirs)?;
+    baseline_dirs.sort();
+
+    baseline_dirs
+        .iter()
+        .map(|path| {
+            let bm =
+                load_benchmark(path, baseline == LATEST_BASELINE).map_err(|err| Error::File {
+                    path: path.clone(),
+                    source: Box::new(err),
+                })?;
+            Ok(RawCriterionData::Benchmark(Box::new(bm)))
+        })
+        .collect()
+}
+
+fn find_baseline_dirs(dir: &Path, baseline: &str, found: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            if path.ends_with(baseline) && path.join("benchmark.json").is_file() {
+                found.push(path);
+            // The HTML report directories never hold benchmark data
+            } else if !path.ends_with("report") {
+                find_baseline_dirs(&path, baseline, found)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn load_benchmark(path: &Path, latest: bool) -> Result<BenchmarkComplete, Error> {
+    let id: BenchmarkId = load_json(&path.join("benchmark.json"))?;
+    let estimates: Estimates = load_json(&path.join("estimates.json"))?;
+
+    // Criterion only writes the sample starting with version 0.3
+    let (iters, times) = match load_optional_json::<Sample>(&path.join("sample.json"))? {
+        Some(sample) => sample.into_parts(),
+        None => Default::default(),
+    };
+
+    // The change is saved next to the latest baseline, when there was a previous run
+    let change = if latest {
+        let change_path = path.with_file_name("change").join("estimates.json");
+        load_optional_json::<ChangeEstimates>(&change_path)?.map(|change| change.change_details())
+    } else {
+        None
+    };
+
+    Ok(BenchmarkComplete {
+        throughput: id.throughput(),
+        id: id.full_id,
+        report_directory: path.with_file_name("report").to_string_lossy().to_flex(),
+        iteration_count: iters.iter().map(|&iters| iters as u64).collect(),
+        measured_values: times,
+        unit: ESTIMATE_UNIT.into(),
+        // This mirrors what cargo-criterion reports as 'typical'
+        typical: estimates.slope.as_ref().unwrap_or(&estimates.mean).into(),
+        mean: (&estimates.mean).into(),
+        median: (&estimates.median).into(),
+        median_abs_dev: (&estimates.median_abs_dev).into(),
+        slope: estimates.slope.as_ref().map(Into::into),
+        change,
+    })
+}
+
+fn load_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, Error> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+fn load_optional_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Option<T>, Error> {
+    if path.is_file() {
+        load_json(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,230 @@
+use std::io::Read;
+
+use flexstr::{a_flex_fmt, AFlexStr};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{BenchmarkComplete, Error, RawCriterionData};
+
+// The table of benchmarks at the top level of the tree, outside of any module
+const ROOT_TABLE: &str = "Benchmarks";
+// Nested modules are joined into one table name (ex: `parse::ints`)
+pub(crate) const MODULE_SEPARATOR: &str = "::";
+
+// *** Divan Data Structs ***
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Node {
+    Stats(Stats),
+    Group(IndexMap<AFlexStr, Node>),
+}
+
+#[derive(Deserialize)]
+struct Stats {
+    median: Duration,
+    mean: Option<Duration>,
+    fastest: Option<Duration>,
+    slowest: Option<Duration>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Duration {
+    Nanos(f64),
+    Text(AFlexStr),
+}
+
+impl Duration {
+    // Returns the duration in nanoseconds, parsing text with a unit (ex: `1.5 µs`)
+    fn as_nanos(&self) -> Result<f64, Error> {
+        let s = match self {
+            Duration::Nanos(nanos) => return Ok(*nanos),
+            Duration::Text(s) => s.trim(),
+        };
+
+        let split = s
+            .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+        let number: f64 = number.parse().map_err(|_| Error::InvalidTime {
+            time: s.to_string(),
+        })?;
+
+        let multiplier = match unit.trim() {
+            "ps" => 0.001,
+            "ns" | "" => 1.0,
+            "µs" | "us" => 1_000.0,
+            "ms" => 1_000_000.0,
+            "s" => 1_000_000_000.0,
+            unit => {
+                return Err(Error::UnknownUnit {
+                    unit: unit.to_string(),
+                })
+            }
+        };
+        Ok(number * multiplier)
+    }
+}
+
+impl Stats {
+    fn into_benchmark(self, id: AFlexStr) -> Result<BenchmarkComplete, Error> {
+        let median = self.median.as_nanos()?;
+        let mean = match self.mean {
+            Some(mean) => mean.as_nanos()?,
+            None => median,
+        };
+        // Divan reports the range of samples instead of their deviation, so half the range is used
+        let spread = match (self.fastest, self.slowest) {
+            (Some(fastest), Some(slowest)) => (slowest.as_nanos()? - fastest.as_nanos()?) / 2.0,
+            _ => 0.0,
+        };
+
+        Ok(BenchmarkComplete::from_samples(
+            id,
+            mean,
+            median,
+            spread,
+            Vec::new(),
+        ))
+    }
+}
+
+// *** Functions ***
+
+/// Load raw benchmark data from divan's JSON output. It is a tree of objects named by module,
+/// function, type and argument (the same tree as divan's terminal output), where each leaf holds
+/// the stats of one benchmark in nanoseconds (ex: `{"fib": {"recursive": {"10": {"fastest": 95.0,
+/// "slowest": 130.0, "median": 101.0, "mean": 104.5}}}}`). Durations formatted as text (ex:
+/// `"101.2 µs"`) are also accepted.
+///
+/// The last two names of each leaf are its column and row (function and argument), and any names
+/// before them (the modules) are joined by `::` into its table. Benchmarks without arguments use
+/// the last name as the column with a blank row instead. The resulting ids (`table/column/row`)
+/// are split via the id scheme
+pub fn from_reader(r: impl Read) -> Result<Vec<RawCriterionData>, Error> {
+    let root: IndexMap<AFlexStr, Node> = serde_json::from_reader(r)?;
+    let mut raw_data = Vec::new();
+    let mut path = Vec::new();
+
+    for (name, node) in root {
+        add_node(name, node, &mut path, &mut raw_data)?;
+    }
+
+    Ok(raw_data)
+}
+
+// Whether `value` is divan's tree of benchmarks, for detecting the input format: objects nested
+// in objects, down to at least one leaf with divan's required `median` stat
+pub(crate) fn is_divan(value: &serde_json::Value) -> bool {
+    match value.as_object() {
+        Some(object) if object.contains_key("median") => true,
+        Some(object) => {
+            !object.is_empty()
+                && object.values().all(serde_json::Value::is_object)
+                && object.values().any(is_divan)
+        }
+        None => false,
+    }
+}
+
+fn add_node(
+    name: AFlexStr,
+    node: Node,
+    path: &mut Vec<AFlexStr>,
+    raw_data: &mut Vec<RawCriterionData>,
+) -> Result<(), Error> {
+    path.push(name);
+
+    match node {
+        Node::Stats(stats) => {
+            let benchmark = stats.into_benchmark(make_id(path))?;
+            raw_data.push(RawCriterionData::Benchmark(Box::new(benchmark)));
+        }
+        Node::Group(children) => {
+            for (name, child) in children {
+                add_node(name, child, path, raw_data)?;
+            }
+        }
+    }
+
+    path.pop();
+    Ok(())
+}
+
+// Maps module/function/arg onto table/column/row (also used for libtest benchmark paths)
+pub(crate) fn make_id(path: &[impl AsRef<str>]) -> AFlexStr {
+    match path {
+        [] => Default::default(),
+        [function] => a_flex_fmt!("{ROOT_TABLE}/{}", function.as_ref()),
+        [module, function] => a_flex_fmt!("{}/{}", module.as_ref(), function.as_ref()),
+        [modules @ .., function, arg] => {
+            let modules: Vec<_> = modules.iter().map(AsRef::as_ref).collect();
+            a_flex_fmt!(
+                "{}/{}/{}",
+                modules.join(MODULE_SEPARATOR),
+                function.as_ref(),
+                arg.as_ref()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(raw_data: &[RawCriterionData]) -> Vec<&str> {
+        raw_data
+            .iter()
+            .filter_map(|data| data.benchmark())
+            .map(|benchmark| benchmark.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn from_reader_hierarchy() {
+        let input = r#"{
+            "fib": {"recursive": {"10": {"fastest": 95.0, "slowest": 131.0, "median": 101.0,
+                "mean": 104.5}}},
+            "parse": {"ints": {"std": {"median": "1.5 µs"}}},
+            "alloc": {"median": 20.0}
+        }"#;
+        let raw_data = from_reader(input.as_bytes()).expect("valid output");
+        assert_eq!(
+            ids(&raw_data),
+            ["fib/recursive/10", "parse/ints/std", "Benchmarks/alloc"]
+        );
+
+        let benchmark = raw_data[0].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.median.estimate, 101.0);
+        assert_eq!(benchmark.mean.estimate, 104.5);
+        assert_eq!(benchmark.mean.upper_bound, 104.5 + 18.0);
+
+        // Text durations are parsed with their unit, and the mean falls back to the median
+        let benchmark = raw_data[1].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.median.estimate, 1_500.0);
+        assert_eq!(benchmark.mean.estimate, 1_500.0);
+    }
+
+    #[test]
+    fn invalid_durations() {
+        let read = |input: &str| from_reader(input.as_bytes());
+
+        assert!(matches!(
+            read(r#"{"fib": {"median": "1.5 ks"}}"#),
+            Err(Error::UnknownUnit { unit }) if unit == "ks"
+        ));
+        assert!(matches!(
+            read(r#"{"fib": {"median": "fast"}}"#),
+            Err(Error::InvalidTime { .. })
+        ));
+    }
+
+    #[test]
+    fn make_id_nested_modules() {
+        assert_eq!(make_id(&["a", "b", "fib", "10"]), "a::b/fib/10");
+        assert_eq!(make_id(&["mod", "fib"]), "mod/fib");
+        assert_eq!(make_id(&["fib"]), "Benchmarks/fib");
+    }
+}
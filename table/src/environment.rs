@@ -0,0 +1,76 @@
+use flexstr::AFlexStr;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{today, Formatter};
+
+/// The heading of the environment section
+pub const ENVIRONMENT_HEADING: &str = "Environment";
+
+// *** Environment Options ***
+
+/// An `Environment` section at the top of the output recording the benchmarking environment, so
+/// published comparisons carry the context needed to interpret them (ex: the CPU model, OS and
+/// rustc version). Gathering it means reading system files and running commands, which the library
+/// leaves to its caller: the binary gathers the CPU model, core count, OS, rustc and criterion
+/// versions (and the memory, with the `sysinfo` feature). The date is added by the library
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentOptions {
+    /// Add the environment section
+    pub enabled: bool,
+    /// Values used instead of those gathered (label -> value, ex: `CPU` -> `Apple M2`), or added
+    /// after them if not gathered (ex: `Power` -> `plugged in`). A blank value leaves that label
+    /// out
+    pub values: IndexMap<AFlexStr, AFlexStr>,
+    /// The environment gathered by the caller (label -> value)
+    #[serde(skip)]
+    pub gathered: IndexMap<AFlexStr, AFlexStr>,
+}
+
+impl EnvironmentOptions {
+    /// Returns the benchmarking environment (label -> value), if enabled: the gathered values and
+    /// the date, with `values` used instead or added after them
+    pub fn entries(&self) -> IndexMap<AFlexStr, AFlexStr> {
+        if !self.enabled {
+            return IndexMap::new();
+        }
+
+        let mut environment = self.gathered.clone();
+        environment.insert("Date".into(), today());
+        for (label, value) in &self.values {
+            environment.insert(label.clone(), value.clone());
+        }
+        environment.retain(|_, value| !value.is_empty());
+        environment
+    }
+
+    /// Returns `top_comments` with the environment section (formatted by `f`) before all other
+    /// comment sections, if enabled
+    pub fn top_comments(
+        &self,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        f: &impl Formatter,
+    ) -> IndexMap<AFlexStr, AFlexStr> {
+        with_environment(&self.entries(), top_comments, f)
+    }
+}
+
+// Returns `top_comments` with the already gathered `environment` (formatted by `f`) before all
+// other comment sections, if there is any
+pub(crate) fn with_environment(
+    environment: &IndexMap<AFlexStr, AFlexStr>,
+    top_comments: &IndexMap<AFlexStr, AFlexStr>,
+    f: &impl Formatter,
+) -> IndexMap<AFlexStr, AFlexStr> {
+    if environment.is_empty() {
+        return top_comments.clone();
+    }
+
+    let mut comments = IndexMap::with_capacity(top_comments.len() + 1);
+    comments.insert(ENVIRONMENT_HEADING.into(), f.environment(environment));
+    for (header, comment) in top_comments {
+        comments.insert(header.clone(), comment.clone());
+    }
+    comments
+}
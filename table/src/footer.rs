@@ -0,0 +1,86 @@
+use flexstr::AFlexStr;
+use serde::Deserialize;
+
+use crate::Metadata;
+
+// *** Footer Options ***
+
+/// The footer written at the end of the output (ex: `Made with criterion-table`) and the
+/// provenance of the results included in it. Provenance is off by default, so output is the same
+/// from run to run
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct FooterOptions {
+    /// Write the footer (default: true). If false, nothing is written at the end, including any
+    /// provenance
+    pub enabled: bool,
+    /// Text used instead of each formatter's own `Made with criterion-table` line. It is written as
+    /// is, so it can contain markup of the output format (ex: a markdown link)
+    pub text: Option<AFlexStr>,
+    /// Include when the output was generated (in UTC)
+    pub timestamp: bool,
+    /// Include the (short) git commit of the current directory, if it is in a git repository
+    pub git_commit: bool,
+    /// Include the version of `rustc` (or `$RUSTC`)
+    pub rustc_version: bool,
+    /// Include the hostname of the machine
+    pub hostname: bool,
+    /// The provenance collected by the caller (ex: the binary runs `git` for the commit). Each item
+    /// is only included if enabled above
+    #[serde(skip)]
+    pub provenance: Provenance,
+}
+
+/// The provenance of the results, given to the library since collecting it means running commands
+/// (ex: `git`) and reading system files, which the library leaves to its caller
+#[derive(Clone, Debug, Default)]
+pub struct Provenance {
+    /// The short git commit of the current directory
+    pub git_commit: Option<AFlexStr>,
+    /// The version of rustc (ex: `1.80.0 (051478957 2024-07-21)`)
+    pub rustc_version: Option<AFlexStr>,
+    /// The hostname of the machine
+    pub hostname: Option<AFlexStr>,
+}
+
+impl Default for FooterOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            text: None,
+            timestamp: false,
+            git_commit: false,
+            rustc_version: false,
+            hostname: false,
+            provenance: Default::default(),
+        }
+    }
+}
+
+// *** Metadata ***
+
+impl Metadata {
+    /// The footer and provenance enabled in `options`. Provenance that wasn't given (ex: outside a
+    /// git repository) is left blank
+    pub fn new(options: &FooterOptions) -> Self {
+        let provenance = &options.provenance;
+        Self {
+            footer: options.enabled,
+            footer_text: options.text.clone(),
+            timestamp: options.timestamp.then(crate::now),
+            git_commit: options
+                .git_commit
+                .then(|| provenance.git_commit.clone())
+                .flatten(),
+            rustc_version: options
+                .rustc_version
+                .then(|| provenance.rustc_version.clone())
+                .flatten(),
+            hostname: options
+                .hostname
+                .then(|| provenance.hostname.clone())
+                .flatten(),
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use super::slug::{SlugStrategy, Slugger};
+use crate::{
+    CellData, Change, ChangeType, ColumnInfo, Comparison, EmbedOptions, Footnote, Formatter,
+    Metadata, Toc, TocEntry,
+};
+use flexstr::{AFlexStr, IntoFlex, ToAFlexStr, ToFlex};
+use indexmap::IndexMap;
+
+const CT_URL: &str = "https://github.com/nu11ptr/criterion-table";
+
+// The headings written before the tables
+const TOC_HEADING: &str = "Table of Contents";
+const RESULTS_HEADING: &str = "Benchmark Results";
+
+const FASTER_COLOR: &str = "green";
+const SLOWER_COLOR: &str = "red";
+
+// *** Confluence Formatter ***
+
+/// This formatter outputs Confluence wiki markup (also understood by Jira), so results can be
+/// pasted into a page or pushed via the API. Each heading has an `{anchor}` the table of contents
+/// links to. Faster comparisons are bold and green, slower ones are italics and red
+#[derive(Clone, Debug)]
+pub struct ConfluenceFormatter {
+    in_section: bool,
+    // Gives each heading the same anchor its table of contents entry links to
+    slugger: Slugger<'static>,
+    // The footer options and provenance, written by `end`
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
+
+impl Default for ConfluenceFormatter {
+    fn default() -> Self {
+        Self {
+            in_section: false,
+            slugger: Slugger::new(SlugStrategy::Github, ""),
+            metadata: Default::default(),
+            embed: None,
+        }
+    }
+}
+
+impl ConfluenceFormatter {
+    /// Create a new `ConfluenceFormatter`
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn escape(s: &str) -> AFlexStr {
+        if !s.contains(['\\', '|', '{', '}', '[', ']', '*', '_']) {
+            return s.to_flex();
+        }
+
+        let mut escaped = String::with_capacity(s.len() + 8);
+
+        for ch in s.chars() {
+            if matches!(ch, '\\' | '|' | '{' | '}' | '[' | ']' | '*' | '_') {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+
+        escaped.into_flex()
+    }
+
+    fn write_plain_heading(buffer: &mut String, level: usize, name: &str) {
+        buffer.push_str(&format!("h{level}. {}\n\n", Self::escape(name)));
+    }
+
+    fn write_heading(&mut self, buffer: &mut String, level: usize, name: &str) {
+        buffer.push_str(&format!("h{level}. {{anchor:{}}}", self.slugger.slug(name)));
+        buffer.push_str(&Self::escape(name));
+        buffer.push_str("\n\n");
+    }
+
+    fn write_toc_entry(buffer: &mut String, slugger: &mut Slugger, entry: &str, depth: usize) {
+        for _ in 0..=depth {
+            buffer.push('*');
+        }
+        buffer.push_str(" [");
+        buffer.push_str(&Self::escape(entry));
+        buffer.push_str("|#");
+        buffer.push_str(&slugger.slug(entry));
+        buffer.push_str("]\n");
+    }
+
+    fn write_toc_entries(
+        buffer: &mut String,
+        slugger: &mut Slugger,
+        entries: &[TocEntry],
+        depth: usize,
+    ) {
+        for entry in entries {
+            Self::write_toc_entry(buffer, slugger, entry.name, depth);
+            Self::write_toc_entries(buffer, slugger, &entry.children, depth + 1);
+        }
+    }
+
+    fn colored(color: &str, s: &str) -> String {
+        format!("{{color:{color}}}{s}{{color}}")
+    }
+
+    // Same thresholds as the GFM formatter: faster is bold, slower is italics
+    fn compare_str(compare: &Comparison, speedup_str: AFlexStr) -> String {
+        if *compare > 0.9 {
+            Self::colored(FASTER_COLOR, &format!("*{speedup_str}*"))
+        } else if *compare < 0.9 {
+            Self::colored(SLOWER_COLOR, &format!("_{speedup_str}_"))
+        } else {
+            speedup_str.to_string()
+        }
+    }
+
+    fn change_str(change: Change) -> String {
+        let change_str = change.to_a_flex_str();
+
+        match change.verdict {
+            ChangeType::Improved => Self::colored(FASTER_COLOR, &change_str),
+            ChangeType::Regressed => Self::colored(SLOWER_COLOR, &change_str),
+            ChangeType::NoChange => change_str.to_string(),
+        }
+    }
+
+    // Empty cells would be merged with their neighbor, so they hold a single space instead
+    fn write_cell(buffer: &mut String, data: &str) {
+        buffer.push('|');
+        buffer.push_str(if data.is_empty() { " " } else { data });
+    }
+}
+
+impl Formatter for ConfluenceFormatter {
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
+    ) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        // Only the headings linked to from the ToC have anchors
+        Self::write_plain_heading(buffer, 1, toc.title);
+
+        // The anchors are made in the same order the headings are written below
+        self.slugger = Slugger::new(SlugStrategy::Github, "");
+
+        if toc.enabled {
+            Self::write_plain_heading(buffer, 2, TOC_HEADING);
+            let mut slugger = Slugger::new(SlugStrategy::Github, "");
+
+            // Write each ToC entry in comments
+            for section_entry in top_comments.keys() {
+                Self::write_toc_entry(buffer, &mut slugger, section_entry, 0);
+            }
+
+            Self::write_toc_entry(buffer, &mut slugger, RESULTS_HEADING, 0);
+            // Write each Benchmark ToC entry
+            Self::write_toc_entries(buffer, &mut slugger, &toc.entries, 1);
+            buffer.push('\n');
+        }
+
+        // Write out all the comment sections and comments (comments are written as is, so they
+        // can contain wiki markup)
+        for (header, comment) in top_comments {
+            self.write_heading(buffer, 2, header);
+            buffer.push_str(comment);
+            buffer.push('\n');
+        }
+
+        self.write_heading(buffer, 2, RESULTS_HEADING);
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        if self.embed.is_some() || !self.metadata.footer {
+            return;
+        }
+
+        buffer.push_str("----\n\n");
+        match &self.metadata.footer_text {
+            Some(text) => buffer.push_str(text),
+            None => {
+                buffer.push_str("Made with [criterion-table|");
+                buffer.push_str(CT_URL);
+                buffer.push(']');
+            }
+        }
+        buffer.push('\n');
+
+        if let Some(provenance) = self.metadata.provenance_str() {
+            buffer.push('\n');
+            buffer.push_str(&Self::escape(&provenance));
+            buffer.push('\n');
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    // A bullet list with each label in bold
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        let mut comment = String::new();
+        for (label, value) in environment {
+            comment.push_str(&format!(
+                "* *{}:* {}\n",
+                Self::escape(label),
+                Self::escape(value)
+            ));
+        }
+        comment.into_flex()
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            self.write_heading(buffer, 3, name);
+        }
+        self.in_section = true;
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.in_section = false;
+    }
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        // Tables are nested one level deeper when inside a section
+        let level = if self.in_section { 4 } else { 3 };
+        if EmbedOptions::headings(self.embed) {
+            self.write_heading(buffer, level, name);
+        }
+
+        if let Some(comments) = comment {
+            buffer.push_str(comments);
+            buffer.push('\n');
+        }
+
+        // Header cells are delimited by double bars. The first is blank (above the row names)
+        buffer.push_str("|| ");
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        for column in &columns[1..] {
+            buffer.push_str("||{{");
+            buffer.push_str(&Self::escape(&column.name));
+            buffer.push_str("}}");
+        }
+        buffer.push_str("||\n");
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        buffer.push('\n');
+    }
+
+    fn footnotes(&mut self, buffer: &mut String, footnotes: &[Footnote]) {
+        buffer.push('\n');
+        for footnote in footnotes {
+            buffer.push_str(&Self::escape(&footnote.marker));
+            buffer.push(' ');
+            buffer.push_str(&footnote.text);
+            buffer.push('\n');
+        }
+    }
+
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        if name.is_empty() {
+            Self::write_cell(buffer, "");
+        } else {
+            Self::write_cell(buffer, &format!("{{{{{}}}}}", Self::escape(name)));
+        }
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        buffer.push_str("|\n");
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, _max_width: usize) {
+        // Noise isn't emphasized as faster or slower
+        let compare_str = if cell.is_noise() {
+            cell.compare_str().to_string()
+        } else {
+            Self::compare_str(&cell.compare, cell.compare_str())
+        };
+        let mut data = format!(
+            "{}{{{{{}}}}} ({compare_str})",
+            cell.role.marker(),
+            cell.measurement()
+        );
+
+        if let Some(change) = cell.change {
+            data.push(' ');
+            data.push_str(&Self::change_str(change));
+        }
+
+        if !cell.tags.is_empty() {
+            let tags: Vec<_> = cell.tags.iter().map(AFlexStr::as_str).collect();
+            data.push_str(&format!(" \\[{}\\]", Self::escape(&tags.join(", "))));
+        }
+
+        Self::write_cell(buffer, &data);
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, _max_width: usize) {
+        Self::write_cell(buffer, "{{N/A}}");
+    }
+
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        Self::write_cell(buffer, &format!("*{}*", Self::escape(name)));
+
+        for compare in comparisons {
+            match compare {
+                Some(compare) => {
+                    let compare_str = Self::compare_str(compare, compare.to_a_flex_str());
+                    Self::write_cell(buffer, &compare_str)
+                }
+                None => self.unused_column(buffer, 0),
+            }
+        }
+
+        self.end_row(buffer);
+    }
+
+    fn anchor(&self, name: &str) -> AFlexStr {
+        SlugStrategy::Github.slug(name).into_flex()
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{CellData, ColumnInfo, Comparison, ExtraStatOptions, Formatter, Toc};
+use flexstr::{a_flex_fmt, AFlexStr};
+use indexmap::IndexMap;
+
+// *** CSV Formatter ***
+
+/// This formatter outputs one CSV (or TSV) block per table, separated by a blank line, for
+/// importing into a spreadsheet. The first header cell is the table name, followed by a time (in
+/// nanoseconds) and a speedup ratio column for each column of the table, plus a column (also in
+/// nanoseconds) per extra statistic, if any. All values are raw numbers
+#[derive(Clone, Debug)]
+pub struct CsvFormatter {
+    separator: char,
+    section: Option<AFlexStr>,
+    extra_stats: ExtraStatOptions,
+}
+
+impl CsvFormatter {
+    /// Create a new `CsvFormatter` that separates fields with `separator`
+    #[inline]
+    pub fn new(separator: char) -> Self {
+        Self {
+            separator,
+            section: None,
+            extra_stats: Default::default(),
+        }
+    }
+
+    /// Output a column per enabled extra statistic after the speedup of each column
+    #[inline]
+    pub fn with_extra_stats(mut self, extra_stats: ExtraStatOptions) -> Self {
+        self.extra_stats = extra_stats;
+        self
+    }
+
+    /// Create a new `CsvFormatter` that outputs comma separated values
+    #[inline]
+    pub fn csv() -> Self {
+        Self::new(',')
+    }
+
+    /// Create a new `CsvFormatter` that outputs tab separated values
+    #[inline]
+    pub fn tsv() -> Self {
+        Self::new('\t')
+    }
+
+    fn write_field(&self, buffer: &mut String, field: &str) {
+        // Quote any field that would otherwise be misread
+        if field.contains([self.separator, '"', '\n', '\r']) {
+            buffer.push('"');
+            buffer.push_str(&field.replace('"', "\"\""));
+            buffer.push('"');
+        } else {
+            buffer.push_str(field);
+        }
+    }
+
+    // The header suffix of each extra statistic, in display order
+    fn extra_stat_names(&self) -> impl Iterator<Item = &'static str> {
+        ["stddev", "mad", "min", "max"]
+            .into_iter()
+            .zip(self.extra_stats.enabled())
+            .filter_map(|(name, enabled)| enabled.then_some(name))
+    }
+
+    // Blank fields for the extra statistics (ex: of an unused column)
+    fn write_blank_extra_stats(&self, buffer: &mut String) {
+        for _ in self.extra_stat_names() {
+            buffer.push(self.separator);
+        }
+    }
+}
+
+impl Default for CsvFormatter {
+    #[inline]
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+impl Formatter for CsvFormatter {
+    fn start(
+        &mut self,
+        _buffer: &mut String,
+        _top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        _toc: &Toc,
+    ) {
+    }
+
+    fn end(&mut self, _buffer: &mut String) {}
+
+    fn start_section(&mut self, _buffer: &mut String, name: &AFlexStr) {
+        self.section = Some(name.clone());
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.section = None;
+    }
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        _comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        match &self.section {
+            Some(section) => self.write_field(buffer, &a_flex_fmt!("{section}/{name}")),
+            None => self.write_field(buffer, name),
+        }
+
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        for column in &columns[1..] {
+            buffer.push(self.separator);
+            self.write_field(buffer, &a_flex_fmt!("{} (ns)", column.name));
+            buffer.push(self.separator);
+            self.write_field(buffer, &a_flex_fmt!("{} (speedup)", column.name));
+            for stat in self.extra_stat_names() {
+                buffer.push(self.separator);
+                self.write_field(buffer, &a_flex_fmt!("{} ({stat} ns)", column.name));
+            }
+        }
+
+        buffer.push('\n');
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        buffer.push('\n');
+    }
+
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        self.write_field(buffer, name);
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        buffer.push('\n');
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, _max_width: usize) {
+        buffer.push(self.separator);
+        buffer.push_str(&cell.time.export_value().to_string());
+        buffer.push(self.separator);
+        buffer.push_str(&cell.compare.ratio().to_string());
+
+        // Blank if unknown (ex: the samples weren't recorded)
+        let stats = self
+            .extra_stats
+            .enabled()
+            .into_iter()
+            .zip(cell.extra_stats.all());
+        for (_, time) in stats.filter(|(enabled, _)| *enabled) {
+            buffer.push(self.separator);
+            if let Some(time) = time {
+                buffer.push_str(&time.export_value().to_string());
+            }
+        }
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, _max_width: usize) {
+        buffer.push(self.separator);
+        buffer.push(self.separator);
+        self.write_blank_extra_stats(buffer);
+    }
+
+    // The summary has no time, just a speedup ratio
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        self.write_field(buffer, name);
+
+        for compare in comparisons {
+            buffer.push(self.separator);
+            buffer.push(self.separator);
+            if let Some(compare) = compare {
+                buffer.push_str(&compare.ratio().to_string());
+            }
+            self.write_blank_extra_stats(buffer);
+        }
+
+        buffer.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CriterionTableData, TablesConfig};
+
+    fn render(f: CsvFormatter, benchmarks: &[(&str, f64)]) -> String {
+        CriterionTableData::from_times(benchmarks).make_tables(f, &TablesConfig::default())
+    }
+
+    #[test]
+    fn raw_numbers() {
+        let csv = render(
+            CsvFormatter::csv(),
+            &[
+                ("Fib/std/10", 100.0),
+                ("Fib/fast/10", 50.0),
+                ("Fib/std/20", 200.0),
+                ("Sort/std/10", 10.0),
+            ],
+        );
+        assert_eq!(
+            csv,
+            "Fib,std (ns),std (speedup),fast (ns),fast (speedup)\n\
+             10,100,1,50,2\n\
+             20,200,1,,\n\
+             \n\
+             Sort,std (ns),std (speedup)\n\
+             10,10,1\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn tab_separated() {
+        let tsv = render(
+            CsvFormatter::tsv(),
+            &[("Fib/std/10", 100.0), ("Fib/fast/10", 50.0)],
+        );
+        assert_eq!(
+            tsv,
+            "Fib\tstd (ns)\tstd (speedup)\tfast (ns)\tfast (speedup)\n10\t100\t1\t50\t2\n\n"
+        );
+    }
+
+    #[test]
+    fn fields_quoted() {
+        let csv = render(CsvFormatter::csv(), &[("Fib/std/\"a,b\"", 100.0)]);
+        assert!(csv.starts_with("Fib,std (ns),std (speedup)\n\"\"\"a,b\"\"\",100,1\n"));
+    }
+}
@@ -1,212 +1,1011 @@
-use crate::{ColumnInfo, Comparison, Formatter, TimeUnit};
-use flexstr::{flex_fmt, FlexStr, IntoFlex, ToCase, ToFlexStr};
+use super::json::TableData;
+use super::slug::{SlugStrategy, Slugger};
+use crate::chart;
+use crate::{
+    display_width, link_width, today, Alignment, CellData, Change, ChangeType, ColumnGroup,
+    ColumnInfo, Comparison, EmbedOptions, Footnote, Formatter, Metadata, TierMarker, Toc, TocEntry,
+};
+use flexstr::{a_flex_fmt, AFlexStr, IntoFlex, ToAFlexStr, ToFlex};
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 
 const CT_URL: &str = "https://github.com/nu11ptr/criterion-table";
 
-// *** NOTE: These are in _bytes_, not _chars_ - since ASCII right now this is ok ***
+// The headings written before the tables
+const TOC_HEADING: &str = "Table of Contents";
+const RESULTS_HEADING: &str = "Benchmark Results";
+
+// *** NOTE: These are display widths - each string is ASCII, so its length in bytes is the same ***
 // Width of making a single item bold
 const FIRST_COL_EXTRA_WIDTH: usize = "**``**".len();
 // Width of a single item in bold (italics is less) + one item in back ticks + one item in parens + one space
-// NOTE: Added one more "X" because we added unicode check, x, and rocket (uses only 1 per cell) that won't be 1 byte each
-const USED_EXTRA_WIDTH: usize = "() ``****XX".len();
+// NOTE: The "XXX" is the check, x, or rocket emoji (only 1 per cell, two columns wide) + one space
+const USED_EXTRA_WIDTH: usize = "() ``****".len();
+// Width of the largest possible rate in back ticks + one space (rates are always less than 1000 of their unit)
+const RATE_EXTRA_WIDTH: usize = " `999.99 Xops/s`".len();
+// Width of a typical margin of error + one space (the margin is in the same unit as the time)
+const MARGIN_EXTRA_WIDTH: usize = " X999.99".len();
+
+// *** GFM Options ***
+
+/// Markdown dialect the output is tailored for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    /// Plain Github Flavored Markdown
+    #[default]
+    Github,
+    /// Markdown that compiles as MDX (ex: Docusaurus). Generated text (table and section names)
+    /// has `{`, `}`, `<` and `>` escaped so they aren't parsed as JSX or expressions
+    Mdx,
+    /// Markdown safe for embedding in rustdoc via `#![doc = include_str!(...)]`. Generated text has
+    /// `[`, `]`, `<` and `>` escaped so it isn't parsed as intra-doc links or HTML tags
+    Rustdoc,
+}
+
+/// The syntax front matter is written in
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FrontMatterFormat {
+    /// TOML between `+++` lines, understood by Hugo and Zola
+    #[default]
+    Toml,
+    /// YAML between `---` lines, understood by Jekyll and Hugo
+    Yaml,
+}
+
+/// Front matter written at the very top of the output (as TOML by default, understood by both Hugo
+/// and Zola, or as YAML for Jekyll)
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FrontMatter {
+    /// The syntax it is written in
+    #[serde(skip_serializing)]
+    pub format: FrontMatterFormat,
+    /// The page title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<AFlexStr>,
+    /// The page date. If not specified, today's date (UTC) is used
+    pub date: Option<AFlexStr>,
+    /// The page tags
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<AFlexStr>,
+}
+
+/// Shortcode delimiters each table is wrapped in (ex: `{{% benchmark %}}` and `{{% /benchmark %}}`)
+#[derive(Clone, Debug, Deserialize)]
+pub struct Shortcode {
+    /// Written directly before each table
+    pub start: AFlexStr,
+    /// Written directly after each table
+    pub end: AFlexStr,
+}
+
+/// How a comparison is emphasized
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Emphasis {
+    /// No emphasis
+    None,
+    /// Bold (ex: `**1.43x faster**`)
+    #[default]
+    Bold,
+    /// Italic (ex: `*1.43x slower*`)
+    Italic,
+}
+
+impl Emphasis {
+    fn apply(self, s: &str) -> AFlexStr {
+        match self {
+            Emphasis::None => s.to_flex(),
+            Emphasis::Bold => a_flex_fmt!("**{s}**"),
+            Emphasis::Italic => a_flex_fmt!("*{s}*"),
+        }
+    }
+}
+
+/// The markers (ex: emoji) and emphasis of comparisons. A blank marker is left out, so all can
+/// be blanked for renderers without emoji support
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct MarkerOptions {
+    /// The marker of comparisons at least 1.8x faster
+    pub much_faster: AFlexStr,
+    /// The marker of comparisons that are faster or about even (and of criterion's improvements)
+    pub faster: AFlexStr,
+    /// The marker of comparisons that are slower (and of criterion's regressions)
+    pub slower: AFlexStr,
+    /// The emphasis of faster and about even comparisons
+    pub faster_emphasis: Emphasis,
+    /// The emphasis of slower comparisons
+    pub slower_emphasis: Emphasis,
+}
+
+impl Default for MarkerOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            much_faster: "🚀".into(),
+            faster: "✅".into(),
+            slower: "❌".into(),
+            faster_emphasis: Emphasis::Bold,
+            slower_emphasis: Emphasis::Italic,
+        }
+    }
+}
+
+impl MarkerOptions {
+    /// No markers, only emphasis
+    pub fn no_emoji() -> Self {
+        Self {
+            much_faster: Default::default(),
+            faster: Default::default(),
+            slower: Default::default(),
+            ..Default::default()
+        }
+    }
+
+    // `s` preceded by `marker` (if any)
+    fn mark(marker: &str, s: &str) -> AFlexStr {
+        if marker.is_empty() {
+            s.to_flex()
+        } else {
+            a_flex_fmt!("{marker} {s}")
+        }
+    }
+
+    // The width of the widest marker and the space after it
+    fn width(&self) -> usize {
+        [&self.much_faster, &self.faster, &self.slower]
+            .into_iter()
+            .filter(|marker| !marker.is_empty())
+            .map(|marker| display_width(marker) + 1)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Options used to customize the output of the `GFMFormatter`
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct GFMOptions {
+    /// The markdown dialect the output is tailored for
+    pub profile: Profile,
+    /// Front matter for static site generators, if any
+    pub front_matter: Option<FrontMatter>,
+    /// Shortcode delimiters to wrap each table in, if any
+    pub shortcode: Option<Shortcode>,
+    /// Display the rate (operations per second) derived from the time in each cell
+    pub rate: bool,
+    /// Display the margin of error of the time in each cell (ex: `1.23 us ±0.04`)
+    pub confidence_interval: bool,
+    /// Emit a fenced JSON block holding the structured data of each table before it
+    pub table_json: bool,
+    /// Compact output for CI (ex: a PR comment or `$GITHUB_STEP_SUMMARY`): no title, table of
+    /// contents or footer, each table collapsible in a `<details>` block, and only significant
+    /// comparisons (more than 10% faster or slower) emphasized
+    pub compact: bool,
+    /// Wrap each table in a collapsible `<details>` block named after it (always the case with
+    /// `compact`), keeping large reports short
+    pub collapsible: bool,
+    /// Tables whose `<details>` block starts expanded, when collapsible
+    pub expanded: Vec<AFlexStr>,
+    /// Reference the SVG bar chart of each table (written via `--charts`) as an image below it,
+    /// from this directory (ex: `charts`)
+    pub chart_dir: Option<AFlexStr>,
+    /// The rules heading anchors are made with, to match the renderer (ex: `gitlab`)
+    pub slug: SlugStrategy,
+    /// Prepended to every heading anchor (ex: `bench-`), for renderers or sites that prefix the
+    /// ids of headings
+    pub anchor_prefix: Option<AFlexStr>,
+    /// The markers and emphasis of comparisons
+    pub markers: MarkerOptions,
+}
 
 // *** GFM Formatter ***
 
 /// This formatter outputs Github Flavored Markdown
-pub struct GFMFormatter;
+#[derive(Clone, Debug, Default)]
+pub struct GFMFormatter {
+    options: GFMOptions,
+    in_section: bool,
+    // The buffer position the JSON block is inserted at once the table is complete
+    table_data: Option<(usize, TableData)>,
+    // The chart image of the current table, if charts are referenced
+    chart_image: Option<AFlexStr>,
+    // The column groups of the next table, written above its column names
+    column_groups: Vec<ColumnGroup>,
+    // The alignment of each column of the current table, and the column of the next cell
+    aligns: Vec<Alignment>,
+    column: usize,
+    // The footer options and provenance, written by `end`
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
 
 impl GFMFormatter {
+    /// Create a new `GFMFormatter` with the given `options`
+    #[inline]
+    pub fn new(options: GFMOptions) -> Self {
+        Self {
+            options,
+            in_section: false,
+            table_data: None,
+            chart_image: None,
+            column_groups: Vec::new(),
+            aligns: Vec::new(),
+            column: 0,
+            metadata: Default::default(),
+            embed: None,
+        }
+    }
+
+    // Escapes generated (not user supplied) text as needed by the profile
+    fn escape(&self, s: &str) -> AFlexStr {
+        let special: &[char] = match self.options.profile {
+            Profile::Github => &[],
+            Profile::Mdx => &['{', '}', '<', '>'],
+            Profile::Rustdoc => &['[', ']', '<', '>'],
+        };
+
+        if !s.contains(special) {
+            return s.to_flex();
+        }
+
+        let mut escaped = String::with_capacity(s.len() + 8);
+
+        for ch in s.chars() {
+            if special.contains(&ch) {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+
+        escaped.into_flex()
+    }
+
     fn pad(buffer: &mut String, ch: char, max_width: usize, written: usize) {
         // Pad the rest of the column (inclusive to handle trailing space)
-        let remaining = max_width - written;
+        let remaining = max_width.saturating_sub(written);
 
         for _ in 0..=remaining {
             buffer.push(ch);
         }
     }
 
+    // Writes `data` (`written` columns wide) padded to the width of its column as given by `align`
+    fn write_cell(
+        buffer: &mut String,
+        align: Alignment,
+        data: &str,
+        max_width: usize,
+        written: usize,
+    ) {
+        let remaining = max_width.saturating_sub(written);
+        let before = match align {
+            Alignment::Left => 0,
+            Alignment::Center => remaining / 2,
+            Alignment::Right => remaining,
+        };
+
+        buffer.extend(std::iter::repeat_n(' ', before));
+        buffer.push_str(data);
+        Self::pad(buffer, ' ', remaining - before, 0);
+    }
+
+    // The alignment of the next cell of the current row
+    fn next_align(&mut self) -> Alignment {
+        self.column += 1;
+        self.aligns.get(self.column).copied().unwrap_or_default()
+    }
+
+    // Each value is written as a JSON string (or array of them), which YAML accepts as is
+    fn yaml_front_matter(buffer: &mut String, front_matter: &FrontMatter) {
+        // Serializing strings can't fail
+        let fields = [
+            (
+                "title",
+                front_matter.title.as_ref().map(serde_json::to_string),
+            ),
+            (
+                "date",
+                front_matter.date.as_ref().map(serde_json::to_string),
+            ),
+            (
+                "tags",
+                (!front_matter.tags.is_empty()).then(|| serde_json::to_string(&front_matter.tags)),
+            ),
+        ];
+
+        for (key, value) in fields {
+            if let Some(Ok(value)) = value {
+                buffer.push_str(&a_flex_fmt!("{key}: {value}\n"));
+            }
+        }
+    }
+
+    // The delimiter of a column, as wide as its cells (`|:---`, `|:--:` or `|---:`)
+    fn delimiter(buffer: &mut String, align: Alignment, max_width: usize) {
+        match align {
+            Alignment::Left => {
+                buffer.push_str("|:");
+                Self::pad(buffer, '-', max_width, 0);
+            }
+            Alignment::Center => {
+                buffer.push_str("|:");
+                Self::pad(buffer, '-', max_width.saturating_sub(1), 0);
+                buffer.push(':');
+            }
+            Alignment::Right => {
+                buffer.push('|');
+                Self::pad(buffer, '-', max_width, 0);
+                buffer.push(':');
+            }
+        }
+    }
+
+    #[inline]
+    fn collapsible(&self) -> bool {
+        self.options.compact || self.options.collapsible
+    }
+
     #[inline]
-    fn encode_link(s: &str) -> FlexStr {
-        s.replace(' ', "-").into_flex().to_lower()
+    fn used_extra_width(&self) -> usize {
+        let mut width = USED_EXTRA_WIDTH + self.options.markers.width();
+        if self.options.rate {
+            width += RATE_EXTRA_WIDTH;
+        }
+        if self.options.confidence_interval {
+            width += MARGIN_EXTRA_WIDTH;
+        }
+        width
     }
 
-    fn write_toc_entry(buffer: &mut String, entry: &str, indent: bool) {
-        if indent {
+    fn start_row_name(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        link: Option<&AFlexStr>,
+        max_width: usize,
+    ) {
+        self.column = 0;
+        buffer.push_str("| ");
+
+        // Regular row name
+        let (data, written) = if !name.is_empty() {
+            let data = match link {
+                Some(link) => a_flex_fmt!("[**`{name}`**]({link})"),
+                None => a_flex_fmt!("**`{name}`**"),
+            };
+            let written = display_width(name) + FIRST_COL_EXTRA_WIDTH + link_width(link);
+            (data, written)
+            // Empty row name
+        } else {
+            (AFlexStr::default(), 0)
+        };
+
+        let align = self.aligns.first().copied().unwrap_or_default();
+        Self::write_cell(
+            buffer,
+            align,
+            &data,
+            max_width + FIRST_COL_EXTRA_WIDTH,
+            written,
+        );
+    }
+
+    fn write_column_names(&self, buffer: &mut String, columns: &[ColumnInfo]) {
+        buffer.push_str("| ");
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        Self::pad(buffer, ' ', columns[0].max_width + FIRST_COL_EXTRA_WIDTH, 0);
+
+        for column in &columns[1..] {
+            let max_width = column.max_width + self.used_extra_width();
+
+            buffer.push_str("| ");
+            Self::write_cell(
+                buffer,
+                column.align,
+                &a_flex_fmt!("`{}`", column.name),
+                max_width,
+                display_width(&column.name) + 2,
+            );
+        }
+
+        buffer.push_str(" |\n");
+    }
+
+    // Each group is named (in bold) above its first column, and the rest of its columns are blank
+    fn write_group_names(
+        &self,
+        buffer: &mut String,
+        columns: &[ColumnInfo],
+        groups: &[ColumnGroup],
+    ) {
+        buffer.push_str("| ");
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        Self::pad(buffer, ' ', columns[0].max_width + FIRST_COL_EXTRA_WIDTH, 0);
+
+        let names = groups.iter().flat_map(|group| {
+            let rest = std::iter::repeat_n(None, group.span - 1);
+            std::iter::once(Some(&group.name)).chain(rest)
+        });
+
+        for (column, name) in columns[1..].iter().zip(names) {
+            let max_width = column.max_width + self.used_extra_width();
+
+            buffer.push_str("| ");
+            let (data, written) = match name {
+                Some(name) if !name.is_empty() => {
+                    let name = self.escape(name);
+                    (a_flex_fmt!("**{name}**"), display_width(&name) + 4)
+                }
+                _ => (AFlexStr::default(), 0),
+            };
+            Self::write_cell(buffer, column.align, &data, max_width, written);
+        }
+
+        buffer.push_str(" |\n");
+    }
+
+    // Criterion's own verdict, with the same markers as the comparisons
+    fn change_str(&self, change: Change) -> AFlexStr {
+        let change_str = change.to_a_flex_str();
+        let markers = &self.options.markers;
+
+        match change.verdict {
+            ChangeType::Improved => MarkerOptions::mark(&markers.faster, &change_str),
+            ChangeType::Regressed => MarkerOptions::mark(&markers.slower, &change_str),
+            ChangeType::NoChange => change_str,
+        }
+    }
+
+    // Emphasizes `speedup_str` (the comparison in the selected style) by how much faster it is,
+    // marked by its speedup `tier` when tiers are configured
+    fn compare_str(
+        &self,
+        compare: &Comparison,
+        tier: TierMarker,
+        speedup_str: AFlexStr,
+    ) -> AFlexStr {
+        // Compact output only emphasizes significant changes
+        if self.options.compact && !compare.is_significant() {
+            speedup_str
+        // Allow 10% wiggle room to qualify
+        } else if *compare >= 1.8 {
+            let markers = &self.options.markers;
+            MarkerOptions::mark(
+                tier.or(&markers.much_faster),
+                &markers.faster_emphasis.apply(&speedup_str),
+            )
+        // Allow 10% wiggle room to qualify
+        } else if *compare > 0.9 {
+            let markers = &self.options.markers;
+            MarkerOptions::mark(
+                tier.or(&markers.faster),
+                &markers.faster_emphasis.apply(&speedup_str),
+            )
+        // Allow 10% wiggle room
+        } else if *compare < 0.9 {
+            let markers = &self.options.markers;
+            MarkerOptions::mark(
+                tier.or(&markers.slower),
+                &markers.slower_emphasis.apply(&speedup_str),
+            )
+        } else {
+            // Even = no special formatting
+            MarkerOptions::mark(tier.or(""), &speedup_str)
+        }
+    }
+
+    fn slugger(&self) -> Slugger<'_> {
+        let prefix = self.options.anchor_prefix.as_deref().unwrap_or_default();
+        Slugger::new(self.options.slug, prefix)
+    }
+
+    // The entries must be written in the same order as their headings, so duplicate headings get
+    // the same anchors the renderer gives them
+    fn write_toc_entry(
+        &self,
+        buffer: &mut String,
+        entry: &str,
+        depth: usize,
+        slugger: &mut Slugger,
+    ) {
+        for _ in 0..depth {
             buffer.push_str("    ");
         }
         buffer.push_str("- [");
-        buffer.push_str(entry);
+        buffer.push_str(&self.escape(entry));
         buffer.push_str("](#");
-        buffer.push_str(&Self::encode_link(entry));
+        buffer.push_str(&slugger.slug(entry));
         buffer.push_str(")\n");
     }
+
+    fn write_toc_entries(
+        &self,
+        buffer: &mut String,
+        entries: &[TocEntry],
+        depth: usize,
+        slugger: &mut Slugger,
+    ) {
+        for entry in entries {
+            self.write_toc_entry(buffer, entry.name, depth, slugger);
+            self.write_toc_entries(buffer, &entry.children, depth + 1, slugger);
+        }
+    }
 }
 
 impl Formatter for GFMFormatter {
     fn start(
         &mut self,
         buffer: &mut String,
-        top_comments: &IndexMap<FlexStr, FlexStr>,
-        tables: &[&FlexStr],
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
     ) {
-        buffer.push_str("# Benchmarks\n\n");
-        buffer.push_str("## Table of Contents\n\n");
-
-        // Write each ToC entry in comments
-        for section_entry in top_comments.keys() {
-            Self::write_toc_entry(buffer, section_entry, false);
+        if self.embed.is_some() {
+            return;
         }
 
-        Self::write_toc_entry(buffer, "Benchmark Results", false);
+        if let Some(front_matter) = &self.options.front_matter {
+            let mut front_matter = front_matter.clone();
+            front_matter.date.get_or_insert_with(today);
+
+            match front_matter.format {
+                FrontMatterFormat::Toml => {
+                    // Serializing a plain struct of strings can't fail
+                    let front_matter = toml::to_string(&front_matter).unwrap_or_default();
+                    buffer.push_str("+++\n");
+                    buffer.push_str(&front_matter);
+                    buffer.push_str("+++\n\n");
+                }
+                FrontMatterFormat::Yaml => {
+                    buffer.push_str("---\n");
+                    Self::yaml_front_matter(buffer, &front_matter);
+                    buffer.push_str("---\n\n");
+                }
+            }
+        }
 
-        // Write each Benchmark ToC entry
-        for &table_entry in tables {
-            Self::write_toc_entry(buffer, table_entry, true);
+        if self.options.compact {
+            // Only the comments themselves, as there are no headings to link to
+            for comment in top_comments.values() {
+                buffer.push_str(comment);
+                buffer.push('\n');
+            }
+            return;
         }
 
-        buffer.push('\n');
+        buffer.push_str("# ");
+        buffer.push_str(&self.escape(toc.title));
+        buffer.push_str("\n\n");
+
+        if toc.enabled {
+            buffer.push_str("## ");
+            buffer.push_str(TOC_HEADING);
+            buffer.push_str("\n\n");
+
+            // These headings come before any others, so they get the first use of their anchors
+            let mut slugger = self.slugger();
+            slugger.slug(toc.title);
+            slugger.slug(TOC_HEADING);
+
+            // Write each ToC entry in comments
+            for section_entry in top_comments.keys() {
+                self.write_toc_entry(buffer, section_entry, 0, &mut slugger);
+            }
+
+            self.write_toc_entry(buffer, RESULTS_HEADING, 0, &mut slugger);
+
+            // Write each Benchmark ToC entry
+            self.write_toc_entries(buffer, &toc.entries, 1, &mut slugger);
+
+            buffer.push('\n');
+        }
 
         // Write out all the comment sections and comments
         for (header, comment) in top_comments {
             buffer.push_str("## ");
-            buffer.push_str(header);
+            buffer.push_str(&self.escape(header));
             buffer.push_str("\n\n");
             buffer.push_str(comment);
             buffer.push('\n');
         }
 
-        buffer.push_str("## Benchmark Results\n\n");
+        buffer.push_str("## ");
+        buffer.push_str(RESULTS_HEADING);
+        buffer.push_str("\n\n");
     }
 
     fn end(&mut self, buffer: &mut String) {
+        if self.options.compact || self.embed.is_some() || !self.metadata.footer {
+            return;
+        }
+
         buffer.push_str("---\n");
-        buffer.push_str("Made with [criterion-table](");
-        buffer.push_str(CT_URL);
-        buffer.push_str(")\n");
+        match &self.metadata.footer_text {
+            Some(text) => buffer.push_str(text),
+            None => {
+                buffer.push_str("Made with [criterion-table](");
+                buffer.push_str(CT_URL);
+                buffer.push(')');
+            }
+        }
+        buffer.push('\n');
+
+        if let Some(provenance) = self.metadata.provenance_str() {
+            buffer.push('\n');
+            buffer.push_str(&self.escape(&provenance));
+            buffer.push('\n');
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    // A list with each label in bold
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        let mut comment = String::new();
+        for (label, value) in environment {
+            comment.push_str(&format!(
+                "- **{}:** {}\n",
+                self.escape(label),
+                self.escape(value)
+            ));
+        }
+        comment.into_flex()
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            buffer.push_str("### ");
+            buffer.push_str(&self.escape(name));
+            buffer.push_str("\n\n");
+        }
+        self.in_section = true;
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.in_section = false;
     }
 
     fn start_table(
         &mut self,
         buffer: &mut String,
-        name: &FlexStr,
-        comment: Option<&FlexStr>,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
         columns: &[ColumnInfo],
     ) {
         // *** Title ***
 
-        buffer.push_str("### ");
-        buffer.push_str(name);
-        buffer.push_str("\n\n");
+        // The heading is kept when collapsible, so the table of contents can still link to it
+        if !self.options.compact && EmbedOptions::headings(self.embed) {
+            // Tables are nested one level deeper when inside a section
+            if self.in_section {
+                buffer.push('#');
+            }
+            buffer.push_str("### ");
+            buffer.push_str(&self.escape(name));
+            buffer.push_str("\n\n");
+        }
+
+        if self.collapsible() {
+            if self.options.expanded.contains(name) {
+                buffer.push_str("<details open>\n<summary>");
+            } else {
+                buffer.push_str("<details>\n<summary>");
+            }
+            buffer.push_str(&self.escape(name));
+            buffer.push_str("</summary>\n\n");
+        }
 
         if let Some(comments) = comment {
             buffer.push_str(comments);
             buffer.push('\n');
         }
 
-        // *** Header Row ***
+        if self.options.table_json {
+            self.table_data = Some((buffer.len(), TableData::new(name, columns)));
+        }
+        self.aligns = columns.iter().map(|column| column.align).collect();
+
+        if let Some(chart_dir) = &self.options.chart_dir {
+            self.chart_image = Some(a_flex_fmt!(
+                "![{}]({}/{})",
+                self.escape(name),
+                chart_dir.trim_end_matches('/'),
+                chart::file_name(name)
+            ));
+        }
 
-        buffer.push_str("| ");
-        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
-        let first_col_max_width = columns[0].max_width + FIRST_COL_EXTRA_WIDTH;
-        Self::pad(buffer, ' ', first_col_max_width, 0);
+        if let Some(shortcode) = &self.options.shortcode {
+            buffer.push_str(&shortcode.start);
+            buffer.push('\n');
+        }
 
-        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
-        for column in &columns[1..] {
-            let max_width = column.max_width + USED_EXTRA_WIDTH;
+        // *** Header Row ***
 
-            buffer.push_str("| `");
-            buffer.push_str(&column.name);
-            buffer.push('`');
-            Self::pad(buffer, ' ', max_width, column.name.chars().count() + 2);
+        // GFM has no spanning cells and only one header row, so when the columns are grouped each
+        // group is named above its first column in the header, and the column names are the
+        // first row of the table instead
+        let groups = std::mem::take(&mut self.column_groups);
+        if groups.is_empty() {
+            self.write_column_names(buffer, columns);
+        } else {
+            self.write_group_names(buffer, columns, &groups);
         }
 
-        buffer.push_str(" |\n");
-
         // *** Deliminator Row ***
 
-        // Right now, everything is left justified
-        buffer.push_str("|:");
-        Self::pad(buffer, '-', first_col_max_width, 0);
-
         // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
-        for column in &columns[1..] {
-            let max_width = column.max_width + USED_EXTRA_WIDTH;
+        Self::delimiter(
+            buffer,
+            columns[0].align,
+            columns[0].max_width + FIRST_COL_EXTRA_WIDTH,
+        );
 
-            buffer.push_str("|:");
-            Self::pad(buffer, '-', max_width, 0);
+        for column in &columns[1..] {
+            let max_width = column.max_width + self.used_extra_width();
+            Self::delimiter(buffer, column.align, max_width);
         }
 
         buffer.push_str(" |\n");
+
+        if !groups.is_empty() {
+            self.write_column_names(buffer, columns);
+        }
     }
 
     fn end_table(&mut self, buffer: &mut String) {
+        if let Some(shortcode) = &self.options.shortcode {
+            buffer.push_str(&shortcode.end);
+            buffer.push('\n');
+        }
+
         buffer.push('\n');
-    }
 
-    fn start_row(&mut self, buffer: &mut String, name: &FlexStr, max_width: usize) {
-        // Regular row name
-        let written = if !name.is_empty() {
-            buffer.push_str("| **`");
-            buffer.push_str(name);
-            buffer.push_str("`**");
-            name.chars().count() + FIRST_COL_EXTRA_WIDTH
-            // Empty row name
-        } else {
-            buffer.push_str("| ");
-            0
-        };
+        if let Some(chart_image) = self.chart_image.take() {
+            buffer.push_str(&chart_image);
+            buffer.push_str("\n\n");
+        }
+
+        if let Some((start, table_data)) = self.table_data.take() {
+            // Serializing a JSON value can't fail
+            let json = serde_json::to_string_pretty(&table_data.to_value()).unwrap_or_default();
+            buffer.insert_str(start, &format!("```json\n{json}\n```\n\n"));
+        }
+
+        if self.collapsible() {
+            buffer.push_str("</details>\n\n");
+        }
+    }
 
-        Self::pad(buffer, ' ', max_width + FIRST_COL_EXTRA_WIDTH, written);
+    fn column_groups(&mut self, groups: &[ColumnGroup]) -> bool {
+        self.column_groups = groups.to_vec();
+        true
     }
 
-    fn end_row(&mut self, buffer: &mut String) {
-        buffer.push_str(" |\n");
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, max_width: usize) {
+        if let Some((_, table_data)) = &mut self.table_data {
+            table_data.start_row(name);
+        }
+
+        self.start_row_name(buffer, name, None, max_width);
     }
 
-    fn used_column(
+    fn start_linked_row(
         &mut self,
         buffer: &mut String,
-        time: TimeUnit,
-        compare: Comparison,
+        name: &AFlexStr,
+        link: &AFlexStr,
         max_width: usize,
     ) {
-        let (time_str, speedup_str) = (time.to_flex_str(), compare.to_flex_str());
+        if let Some((_, table_data)) = &mut self.table_data {
+            table_data.start_row(name);
+        }
 
-        // Allow 10% wiggle room to qualify
-        let data = if compare >= 1.8 {
-            // Positive = bold
-            flex_fmt!("`{time_str}` (🚀 **{speedup_str}**)")
-        // Allow 10% wiggle room to qualify
-        } else if compare > 0.9 {
-            // Positive = bold
-            flex_fmt!("`{time_str}` (✅ **{speedup_str}**)")
-        // Allow 10% wiggle room
-        } else if compare < 0.9 {
-            // Negative = italics
-            flex_fmt!("`{time_str}` (❌ *{speedup_str}*)")
+        self.start_row_name(buffer, name, Some(link), max_width);
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        buffer.push_str(" |\n");
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, max_width: usize) {
+        if let Some((_, table_data)) = &mut self.table_data {
+            table_data.add_cell(Some(cell));
+        }
+
+        let (time, compare, tags) = (&cell.time, &cell.compare, cell.tags);
+        let time_str = if self.options.confidence_interval {
+            a_flex_fmt!("{} ±{:.2}", cell.format_time(time), cell.margin())
         } else {
-            // Even = no special formatting
-            flex_fmt!("`{time_str}` ({speedup_str})")
+            cell.format_time(time)
+        };
+        let time_str = match (cell.show_time, cell.throughput) {
+            (true, Some(throughput)) => a_flex_fmt!("{time_str}, {}", throughput.to_a_flex_str()),
+            (false, Some(throughput)) => throughput.to_a_flex_str(),
+            (_, None) => time_str,
+        };
+        let time_str = cell
+            .extra_stats
+            .iter()
+            .fold(time_str, |time_str, (label, time)| {
+                a_flex_fmt!("{time_str}, {label} {}", cell.format_time(time))
+            });
+        let time_str = match time.rate().filter(|_| self.options.rate) {
+            Some(rate) => a_flex_fmt!(
+                "{}`{time_str}` `{}`",
+                cell.role.marker(),
+                rate.to_a_flex_str()
+            ),
+            None => a_flex_fmt!("{}`{time_str}`", cell.role.marker()),
+        };
+        let time_str = match cell.link {
+            Some(link) => a_flex_fmt!("[{time_str}]({link})"),
+            None => time_str,
         };
 
-        buffer.push_str("| ");
-        buffer.push_str(&data);
+        // Noise isn't emphasized as faster or slower
+        let compare_str = if cell.is_noise() {
+            cell.compare_str()
+        } else {
+            self.compare_str(compare, cell.tier, cell.compare_str())
+        };
+        let mut data = a_flex_fmt!("{time_str} ({compare_str})");
 
-        let max_width = max_width + USED_EXTRA_WIDTH;
-        Self::pad(buffer, ' ', max_width, data.chars().count());
+        if let Some(change) = cell.change {
+            data = a_flex_fmt!("{data} {}", self.change_str(change));
+        }
+
+        if !tags.is_empty() {
+            let tags: Vec<_> = tags.iter().map(AFlexStr::as_str).collect();
+            data = a_flex_fmt!("{data} [{}]", tags.join(", "));
+        }
+
+        buffer.push_str("| ");
+        let max_width = max_width + self.used_extra_width();
+        let align = self.next_align();
+        Self::write_cell(buffer, align, &data, max_width, display_width(&data));
     }
 
     fn unused_column(&mut self, buffer: &mut String, max_width: usize) {
+        if let Some((_, table_data)) = &mut self.table_data {
+            table_data.add_cell(None);
+        }
+
         buffer.push_str("| ");
         let data = "`N/A`";
-        buffer.push_str(data);
+        let max_width = max_width + self.used_extra_width();
+        let align = self.next_align();
+        Self::write_cell(buffer, align, data, max_width, display_width(data));
+    }
 
-        Self::pad(
-            buffer,
-            ' ',
-            max_width + USED_EXTRA_WIDTH,
-            data.chars().count(),
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        columns: &[ColumnInfo],
+    ) {
+        if let Some((_, table_data)) = &mut self.table_data {
+            table_data.set_summary(name, comparisons);
+        }
+
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        self.start_row_name(buffer, name, None, columns[0].max_width);
+
+        for (compare, column) in comparisons.iter().zip(&columns[1..]) {
+            let data = match compare {
+                Some(compare) => {
+                    self.compare_str(compare, TierMarker::Untiered, compare.to_a_flex_str())
+                }
+                None => "`N/A`".into(),
+            };
+
+            buffer.push_str("| ");
+            let max_width = column.max_width + self.used_extra_width();
+            Self::write_cell(buffer, column.align, &data, max_width, display_width(&data));
+        }
+
+        self.end_row(buffer);
+    }
+
+    fn footnotes(&mut self, buffer: &mut String, footnotes: &[Footnote]) {
+        // Each footnote follows a blank line, so the first isn't parsed as a row of the table
+        for footnote in footnotes {
+            buffer.push('\n');
+            buffer.push_str(&self.escape(&footnote.marker));
+            buffer.push(' ');
+            buffer.push_str(&footnote.text);
+            buffer.push('\n');
+        }
+
+        if let Some((_, table_data)) = &mut self.table_data {
+            table_data.set_footnotes(footnotes);
+        }
+    }
+
+    fn anchor(&self, name: &str) -> AFlexStr {
+        let prefix = self.options.anchor_prefix.as_deref().unwrap_or_default();
+        a_flex_fmt!("{prefix}{}", self.options.slug.slug(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CriterionTableData, TablesConfig};
+
+    fn render(config: &str) -> String {
+        let config = TablesConfig::try_load_config(config.as_bytes()).expect("valid config");
+        let mut data = CriterionTableData::from_times(&[
+            ("Fib/std/10", 100.0),
+            ("Fib/fast/10", 50.0),
+            ("Fib/std/20", 200.0),
+            ("Fib/fast/20", 400.0),
+        ]);
+        data.apply_config(&config).expect("applied");
+        data.make_tables(GFMFormatter::new(config.gfm.clone()), &config)
+    }
+
+    // The lines of the table
+    fn table_lines(doc: &str) -> Vec<&str> {
+        doc.lines().filter(|line| line.starts_with('|')).collect()
+    }
+
+    #[test]
+    fn document() {
+        let doc = render("");
+        assert!(doc.starts_with(
+            "# Benchmarks\n\n\
+             ## Table of Contents\n\n\
+             - [Benchmark Results](#benchmark-results)\n    \
+             - [Fib](#fib)\n\n\
+             ## Benchmark Results\n\n\
+             ### Fib\n\n"
+        ));
+        assert!(doc.ends_with(
+            "---\nMade with [criterion-table](https://github.com/nu11ptr/criterion-table)\n"
+        ));
+        assert_eq!(
+            table_lines(&doc),
+            [
+                "|          |                      `std` |                            `fast`  |",
+                "|:---------|---------------------------:|----------------------------------: |",
+                "| **`10`** | `100.00 ns` (✅ **1.00x**) |  `50.00 ns` (🚀 **2.00x faster**)  |",
+                "| **`20`** | `200.00 ns` (✅ **1.00x**) |   `400.00 ns` (❌ *2.00x slower*)  |",
+            ]
+        );
+    }
+
+    #[test]
+    fn alignment() {
+        let doc = render(
+            r#"
+            [alignment]
+            row_names = "right"
+            data = "left"
+
+            [alignment.columns]
+            fast = "center"
+            "#,
+        );
+        let lines = table_lines(&doc);
+        assert_eq!(
+            lines[1],
+            "|---------:|:---------------------------|:---------------------------------: |"
+        );
+        assert_eq!(
+            lines[2],
+            "| **`10`** | `100.00 ns` (✅ **1.00x**) | `50.00 ns` (🚀 **2.00x faster**)   |"
         );
     }
 }
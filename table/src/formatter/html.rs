@@ -0,0 +1,610 @@
+use crate::{
+    Alignment, CellData, ChangeType, ColumnGroup, ColumnInfo, Comparison, EmbedOptions, Footnote,
+    Formatter, Metadata, Toc, TocEntry,
+};
+use flexstr::{AFlexStr, IntoFlex, ToAFlexStr, ToFlex};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::fmt;
+
+const CT_URL: &str = "https://github.com/nu11ptr/criterion-table";
+
+// The same styling as the GFM output: faster is bold, slower is italics
+const STYLE: &str = "\
+body { font-family: sans-serif; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }
+th[colspan], .center { text-align: center; }
+.right { text-align: right; }
+td.faster, td.much-faster { font-weight: bold; }
+td.slower { font-style: italic; }
+span.tags { color: #666; }
+span.change.improved { color: #080; }
+span.change.regressed { color: #c00; }
+tr.summary { border-top: 2px solid #999; }
+p.footnote { font-size: smaller; margin: 4px 0; }
+";
+
+// *** Heatmap ***
+
+/// A CSS color in hex (ex: `#63be7b`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct HexColor(pub u8, pub u8, pub u8);
+
+impl HexColor {
+    // The color `t` (from 0.0 to 1.0) of the way from this color to `other`
+    fn blend(self, other: Self, t: f64) -> Self {
+        let mix = |c1: u8, c2: u8| (c1 as f64 + (c2 as f64 - c1 as f64) * t).round() as u8;
+        Self(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("Invalid hex color (expected `#rrggbb`): {s}");
+        let hex = s.strip_prefix('#').ok_or_else(invalid)?;
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+
+        let channel =
+            |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).map_err(|_| invalid());
+        Ok(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl fmt::Display for HexColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Options used to customize the output of the `HtmlFormatter`
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct HtmlOptions {
+    /// Shade the background of each cell by how much slower it is than the fastest cell of its row
+    pub heatmap: bool,
+    /// The colors cells are shaded with, from the fastest cell of their row to `heatmap_max` times
+    /// slower. Cells in between are a blend of the two nearest colors
+    pub heatmap_palette: Vec<HexColor>,
+    /// How many times slower than the fastest cell of their row cells are shaded with the last
+    /// color of the palette (as are all slower cells)
+    pub heatmap_max: f64,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            heatmap: true,
+            // Green, yellow, then red
+            heatmap_palette: vec![
+                HexColor(0xd9, 0xf2, 0xdd),
+                HexColor(0xff, 0xf3, 0xc4),
+                HexColor(0xf8, 0xd3, 0xcf),
+            ],
+            heatmap_max: 10.0,
+        }
+    }
+}
+
+// *** HTML Formatter ***
+
+/// This formatter outputs a standalone HTML document
+#[derive(Clone, Debug, Default)]
+pub struct HtmlFormatter {
+    options: HtmlOptions,
+    in_section: bool,
+    // The footnotes of the current table, written after it
+    footnotes: Vec<Footnote>,
+    // The column groups of the next table, written above its column names
+    column_groups: Vec<ColumnGroup>,
+    // The alignment of each column of the current table, and the index of the next cell's column
+    aligns: Vec<Alignment>,
+    column: usize,
+    // The footer options and provenance, written by `end`
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
+
+impl HtmlFormatter {
+    /// Create a new `HtmlFormatter` with the given `options`
+    #[inline]
+    pub fn new(options: HtmlOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    // The class aligning the cells of a column, if not the default (left justified)
+    fn align_class(align: Alignment) -> Option<&'static str> {
+        match align {
+            Alignment::Left => None,
+            Alignment::Center => Some("center"),
+            Alignment::Right => Some("right"),
+        }
+    }
+
+    // Opens a cell (ex: `<td class="faster right"`) with the alignment of the next column, without
+    // closing the tag so attributes can follow
+    fn open_cell(&mut self, buffer: &mut String, tag: &str, class: Option<&str>) {
+        let align = self.aligns.get(self.column).copied().unwrap_or_default();
+        self.column += 1;
+
+        let classes: Vec<_> = class.into_iter().chain(Self::align_class(align)).collect();
+        buffer.push('<');
+        buffer.push_str(tag);
+        if !classes.is_empty() {
+            buffer.push_str(" class=\"");
+            buffer.push_str(&classes.join(" "));
+            buffer.push('"');
+        }
+    }
+
+    fn start_row_name(&mut self, buffer: &mut String, name: &AFlexStr, link: Option<&AFlexStr>) {
+        buffer.push_str("<tr>\n");
+        self.column = 0;
+        self.open_cell(buffer, "th", None);
+        buffer.push('>');
+        if !name.is_empty() {
+            Self::open_link(buffer, link);
+            buffer.push_str("<code>");
+            buffer.push_str(&Self::escape(name));
+            buffer.push_str("</code>");
+            Self::close_link(buffer, link);
+        }
+        buffer.push_str("</th>\n");
+    }
+
+    fn open_link(buffer: &mut String, link: Option<&AFlexStr>) {
+        if let Some(link) = link {
+            buffer.push_str("<a href=\"");
+            buffer.push_str(&Self::escape(link));
+            buffer.push_str("\">");
+        }
+    }
+
+    fn close_link(buffer: &mut String, link: Option<&AFlexStr>) {
+        if link.is_some() {
+            buffer.push_str("</a>");
+        }
+    }
+
+    // The heatmap color of a cell `relative_time` times slower than the fastest cell of its row.
+    // The scale is logarithmic, so 2x and 4x slower are as far apart as 4x and 8x
+    fn heat_color(&self, relative_time: f64) -> Option<HexColor> {
+        let palette = &self.options.heatmap_palette;
+        let last = palette.len().checked_sub(1)?;
+
+        let scale = self.options.heatmap_max.ln();
+        let t = if scale > 0.0 {
+            (relative_time.ln() / scale).clamp(0.0, 1.0)
+        } else if relative_time > 1.0 {
+            1.0
+        } else {
+            0.0
+        };
+        // NaN (ex: from a zero time) is treated as the fastest
+        let t = if t.is_nan() { 0.0 } else { t };
+
+        let pos = t * last as f64;
+        let idx = pos.floor() as usize;
+        Some(palette[idx].blend(palette[(idx + 1).min(last)], pos - idx as f64))
+    }
+
+    pub(crate) fn escape(s: &str) -> AFlexStr {
+        if !s.contains(['&', '<', '>', '"', '\'']) {
+            return s.to_flex();
+        }
+
+        let mut escaped = String::with_capacity(s.len() + 16);
+
+        for ch in s.chars() {
+            match ch {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                _ => escaped.push(ch),
+            }
+        }
+
+        escaped.into_flex()
+    }
+
+    fn write_heading(&self, buffer: &mut String, level: usize, name: &str) {
+        buffer.push_str(&format!("<h{level} id=\""));
+        buffer.push_str(&Self::escape(&self.anchor(name)));
+        buffer.push_str("\">");
+        buffer.push_str(&Self::escape(name));
+        buffer.push_str(&format!("</h{level}>\n"));
+    }
+
+    // Same thresholds as the GFM formatter
+    fn class_and_symbol(compare: &Comparison) -> (&'static str, &'static str) {
+        if *compare >= 1.8 {
+            ("much-faster", "🚀 ")
+        } else if *compare > 0.9 {
+            ("faster", "✅ ")
+        } else if *compare < 0.9 {
+            ("slower", "❌ ")
+        } else {
+            ("even", "")
+        }
+    }
+
+    fn write_toc_entry(&self, buffer: &mut String, entry: &str) {
+        buffer.push_str("<li><a href=\"#");
+        buffer.push_str(&Self::escape(&self.anchor(entry)));
+        buffer.push_str("\">");
+        buffer.push_str(&Self::escape(entry));
+        buffer.push_str("</a>");
+    }
+
+    fn write_toc_entries(&self, buffer: &mut String, entries: &[TocEntry]) {
+        buffer.push_str("<ul>\n");
+
+        for entry in entries {
+            self.write_toc_entry(buffer, entry.name);
+            if !entry.children.is_empty() {
+                buffer.push('\n');
+                self.write_toc_entries(buffer, &entry.children);
+            }
+            buffer.push_str("</li>\n");
+        }
+
+        buffer.push_str("</ul>\n");
+    }
+}
+
+impl Formatter for HtmlFormatter {
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
+    ) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        buffer.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        buffer.push_str("<title>");
+        buffer.push_str(&Self::escape(toc.title));
+        buffer.push_str("</title>\n<style>\n");
+        buffer.push_str(STYLE);
+        buffer.push_str("</style>\n</head>\n<body>\n");
+
+        self.write_heading(buffer, 1, toc.title);
+
+        if toc.enabled {
+            self.write_heading(buffer, 2, "Table of Contents");
+
+            // Write each ToC entry in comments
+            buffer.push_str("<ul>\n");
+            for section_entry in top_comments.keys() {
+                self.write_toc_entry(buffer, section_entry);
+                buffer.push_str("</li>\n");
+            }
+
+            self.write_toc_entry(buffer, "Benchmark Results");
+            // Write each Benchmark ToC entry
+            if !toc.entries.is_empty() {
+                buffer.push('\n');
+                self.write_toc_entries(buffer, &toc.entries);
+            }
+            buffer.push_str("</li>\n</ul>\n");
+        }
+
+        // Write out all the comment sections and comments (comments are written as is, so they
+        // can contain HTML)
+        for (header, comment) in top_comments {
+            self.write_heading(buffer, 2, header);
+            buffer.push_str("<div>\n");
+            buffer.push_str(comment);
+            buffer.push_str("</div>\n");
+        }
+
+        self.write_heading(buffer, 2, "Benchmark Results");
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        if self.metadata.footer {
+            buffer.push_str("<hr>\n<p>");
+            match &self.metadata.footer_text {
+                Some(text) => buffer.push_str(text),
+                None => {
+                    buffer.push_str("Made with <a href=\"");
+                    buffer.push_str(CT_URL);
+                    buffer.push_str("\">criterion-table</a>");
+                }
+            }
+            buffer.push_str("</p>\n");
+
+            if let Some(provenance) = self.metadata.provenance_str() {
+                buffer.push_str("<p><small>");
+                buffer.push_str(&Self::escape(&provenance));
+                buffer.push_str("</small></p>\n");
+            }
+        }
+        buffer.push_str("</body>\n</html>\n");
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    // A list with each label in bold
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        let mut comment = String::new();
+        comment.push_str("<ul>\n");
+        for (label, value) in environment {
+            comment.push_str(&format!(
+                "<li><b>{}:</b> {}</li>\n",
+                Self::escape(label),
+                Self::escape(value)
+            ));
+        }
+        comment.push_str("</ul>\n");
+        comment.into_flex()
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            self.write_heading(buffer, 3, name);
+        }
+        self.in_section = true;
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.in_section = false;
+    }
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        // Tables are nested one level deeper when inside a section
+        let level = if self.in_section { 4 } else { 3 };
+        if EmbedOptions::headings(self.embed) {
+            self.write_heading(buffer, level, name);
+        }
+
+        if let Some(comments) = comment {
+            buffer.push_str("<div>\n");
+            buffer.push_str(comments);
+            buffer.push_str("</div>\n");
+        }
+
+        buffer.push_str("<table>\n<thead>\n<tr>\n");
+        self.aligns = columns.iter().map(|column| column.align).collect();
+        self.column = 0;
+
+        // The blank first header cell spans both header rows when the columns are grouped
+        let groups = std::mem::take(&mut self.column_groups);
+        self.open_cell(buffer, "th", None);
+        if groups.is_empty() {
+            buffer.push_str("></th>\n");
+        } else {
+            buffer.push_str(" rowspan=\"2\"></th>\n");
+            for group in &groups {
+                if group.span > 1 {
+                    buffer.push_str(&format!("<th colspan=\"{}\">", group.span));
+                } else {
+                    buffer.push_str("<th>");
+                }
+                buffer.push_str(&Self::escape(&group.name));
+                buffer.push_str("</th>\n");
+            }
+            buffer.push_str("</tr>\n<tr>\n");
+        }
+
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        for column in &columns[1..] {
+            self.open_cell(buffer, "th", None);
+            buffer.push_str("><code>");
+            buffer.push_str(&Self::escape(&column.name));
+            buffer.push_str("</code></th>\n");
+        }
+
+        buffer.push_str("</tr>\n</thead>\n<tbody>\n");
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        buffer.push_str("</tbody>\n</table>\n");
+
+        // Footnote text is written as-is, like comments
+        for footnote in self.footnotes.drain(..) {
+            buffer.push_str("<p class=\"footnote\">");
+            buffer.push_str(&Self::escape(&footnote.marker));
+            buffer.push(' ');
+            buffer.push_str(&footnote.text);
+            buffer.push_str("</p>\n");
+        }
+    }
+
+    fn footnotes(&mut self, _buffer: &mut String, footnotes: &[Footnote]) {
+        self.footnotes = footnotes.to_vec();
+    }
+
+    fn column_groups(&mut self, groups: &[ColumnGroup]) -> bool {
+        self.column_groups = groups.to_vec();
+        true
+    }
+
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        self.start_row_name(buffer, name, None);
+    }
+
+    fn start_linked_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        link: &AFlexStr,
+        _max_width: usize,
+    ) {
+        self.start_row_name(buffer, name, Some(link));
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        buffer.push_str("</tr>\n");
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, _max_width: usize) {
+        // Noise isn't emphasized as faster or slower
+        let (class, symbol) = if cell.is_noise() {
+            ("even", "")
+        } else {
+            Self::class_and_symbol(&cell.compare)
+        };
+
+        self.open_cell(buffer, "td", Some(class));
+        if self.options.heatmap {
+            if let Some(color) = self.heat_color(cell.relative_time) {
+                buffer.push_str(&format!(" style=\"background-color: {color}\""));
+            }
+        }
+        // The confidence interval is shown on hover
+        buffer.push_str(" title=\"");
+        buffer.push_str(&cell.format_time(&cell.lower_bound));
+        buffer.push_str(" - ");
+        buffer.push_str(&cell.format_time(&cell.upper_bound));
+        buffer.push_str("\">");
+        buffer.push_str(cell.role.marker());
+        Self::open_link(buffer, cell.link);
+        buffer.push_str("<code>");
+        buffer.push_str(&cell.measurement());
+        buffer.push_str("</code>");
+        Self::close_link(buffer, cell.link);
+        buffer.push_str(" (");
+        let symbol = cell.tier.or(symbol.trim_end());
+        if !symbol.is_empty() {
+            buffer.push_str(&Self::escape(symbol));
+            buffer.push(' ');
+        }
+        buffer.push_str(&cell.compare_str());
+        buffer.push(')');
+
+        if let Some(change) = cell.change {
+            let (class, symbol) = match change.verdict {
+                ChangeType::Improved => ("improved", "✅ "),
+                ChangeType::Regressed => ("regressed", "❌ "),
+                ChangeType::NoChange => ("no-change", ""),
+            };
+            buffer.push_str(" <span class=\"change ");
+            buffer.push_str(class);
+            buffer.push_str("\">");
+            buffer.push_str(symbol);
+            buffer.push_str(&change.to_a_flex_str());
+            buffer.push_str("</span>");
+        }
+
+        if !cell.tags.is_empty() {
+            let tags: Vec<_> = cell.tags.iter().map(AFlexStr::as_str).collect();
+            buffer.push_str(" <span class=\"tags\">[");
+            buffer.push_str(&Self::escape(&tags.join(", ")));
+            buffer.push_str("]</span>");
+        }
+
+        buffer.push_str("</td>\n");
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, _max_width: usize) {
+        self.open_cell(buffer, "td", Some("unused"));
+        buffer.push_str("><code>N/A</code></td>\n");
+    }
+
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        buffer.push_str("<tr class=\"summary\">\n");
+        self.column = 0;
+        self.open_cell(buffer, "th", None);
+        buffer.push('>');
+        buffer.push_str(&Self::escape(name));
+        buffer.push_str("</th>\n");
+
+        for compare in comparisons {
+            match compare {
+                Some(compare) => {
+                    let (class, symbol) = Self::class_and_symbol(compare);
+                    self.open_cell(buffer, "td", Some(class));
+                    buffer.push('>');
+                    buffer.push_str(symbol);
+                    buffer.push_str(&compare.to_a_flex_str());
+                    buffer.push_str("</td>\n");
+                }
+                None => self.unused_column(buffer, 0),
+            }
+        }
+
+        self.end_row(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CriterionTableData, TablesConfig};
+
+    fn render(benchmarks: &[(&str, f64)]) -> String {
+        CriterionTableData::from_times(benchmarks)
+            .make_tables(HtmlFormatter::default(), &TablesConfig::default())
+    }
+
+    #[test]
+    fn standalone_document() {
+        let html = render(&[("Fib/std/10", 100.0)]);
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>\n<head>"));
+        assert!(html.contains("<title>Benchmarks</title>"));
+        assert!(html.contains(r##"<li><a href="#fib">Fib</a></li>"##));
+        assert!(html.contains(r#"<h3 id="fib">Fib</h3>"#));
+        assert!(html.ends_with("</body>\n</html>\n"));
+    }
+
+    #[test]
+    fn cells_classed_by_comparison() {
+        let html = render(&[
+            ("Fib/std/10", 100.0),
+            ("Fib/fast/10", 50.0),
+            ("Fib/std/20", 200.0),
+            ("Fib/fast/20", 400.0),
+        ]);
+        assert!(html.contains(r#"<td class="much-faster right""#));
+        assert!(html.contains("<code>50.00 ns</code> (🚀 2.00x faster)</td>"));
+        assert!(html.contains(r#"<td class="slower right""#));
+        assert!(html.contains("<code>400.00 ns</code> (❌ 2.00x slower)</td>"));
+    }
+
+    #[test]
+    fn names_escaped() {
+        let html = render(&[("Vec<u8>/std/10", 100.0)]);
+        assert!(html.contains(r#"<h3 id="vec&lt;u8&gt;">Vec&lt;u8&gt;</h3>"#));
+        assert!(!html.contains("Vec<u8>"));
+    }
+}
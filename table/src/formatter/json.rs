@@ -0,0 +1,323 @@
+use crate::{
+    CellData, CellRole, ChangeType, ColumnInfo, Comparison, Footnote, Formatter, Metadata,
+    ThroughputUnit, Toc,
+};
+use flexstr::{AFlexStr, ToAFlexStr};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+// Structured data of the table being formatted, collected as it is visited
+#[derive(Clone, Debug)]
+pub(crate) struct TableData {
+    name: AFlexStr,
+    columns: Vec<AFlexStr>,
+    rows: Vec<Value>,
+    col_idx: usize,
+    summary: Option<Value>,
+    footnotes: Vec<Footnote>,
+    // Also hold each value as it is displayed, plus blank cells (so cells line up with columns)
+    display: bool,
+}
+
+impl TableData {
+    pub(crate) fn new(name: &AFlexStr, columns: &[ColumnInfo]) -> Self {
+        Self {
+            name: name.clone(),
+            // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+            columns: columns[1..].iter().map(|col| col.name.clone()).collect(),
+            rows: Vec::new(),
+            col_idx: 0,
+            summary: None,
+            footnotes: Vec::new(),
+            display: false,
+        }
+    }
+
+    #[cfg(feature = "template")]
+    pub(crate) fn with_display(mut self) -> Self {
+        self.display = true;
+        self
+    }
+
+    pub(crate) fn start_row(&mut self, name: &AFlexStr) {
+        self.rows.push(json!({ "name": name, "cells": [] }));
+        self.col_idx = 0;
+    }
+
+    pub(crate) fn add_cell(&mut self, cell: Option<&CellData>) {
+        let cells = self
+            .rows
+            .last_mut()
+            .and_then(|row| row["cells"].as_array_mut());
+
+        if let Some(cells) = cells {
+            if let Some(cell) = cell {
+                let mut value = json!({
+                    "column": self.columns.get(self.col_idx),
+                    "time_ps": cell.time.base_value(),
+                    "lower_bound_ps": cell.lower_bound.base_value(),
+                    "upper_bound_ps": cell.upper_bound.base_value(),
+                    "speedup": cell.compare.ratio(),
+                });
+                // The `_ps` values of other measurements are in the base unit of their kind
+                if cell.time.as_time().is_none() {
+                    value["unit"] = json!(cell.time.base_unit());
+                }
+                if let Some(throughput) = cell.throughput {
+                    value["throughput_per_second"] = json!(throughput.per_second);
+                    value["throughput_unit"] = json!(match throughput.unit {
+                        ThroughputUnit::Bytes => "bytes",
+                        ThroughputUnit::Elements => "elements",
+                    });
+                }
+                if let Some(change) = cell.change {
+                    value["change"] = json!(change.relative);
+                    value["change_verdict"] = json!(match change.verdict {
+                        ChangeType::NoChange => "no-change",
+                        ChangeType::Improved => "improved",
+                        ChangeType::Regressed => "regressed",
+                    });
+                }
+                let extra_stats = ["std_dev_ps", "median_abs_dev_ps", "min_ps", "max_ps"];
+                for (key, time) in extra_stats.into_iter().zip(cell.extra_stats.all()) {
+                    if let Some(time) = time {
+                        value[key] = json!(time.base_value());
+                    }
+                }
+                if cell.is_noise() {
+                    value["noise"] = json!(true);
+                }
+                match cell.role {
+                    CellRole::Normal => {}
+                    CellRole::Best => value["role"] = json!("best"),
+                    CellRole::Worst => value["role"] = json!("worst"),
+                }
+                if !cell.tags.is_empty() {
+                    value["tags"] = json!(cell.tags);
+                }
+                if self.display {
+                    value["time"] = json!(cell.format_time(&cell.time));
+                    value["lower_bound"] = json!(cell.format_time(&cell.lower_bound));
+                    value["upper_bound"] = json!(cell.format_time(&cell.upper_bound));
+                    value["measurement"] = json!(cell.measurement());
+                    value["comparison"] = json!(cell.compare_str());
+                    value["marker"] = json!(cell.role.marker());
+                }
+                cells.push(value);
+            } else if self.display {
+                cells.push(Value::Null);
+            }
+        }
+
+        self.col_idx += 1;
+    }
+
+    pub(crate) fn set_summary(&mut self, name: &AFlexStr, comparisons: &[Option<Comparison>]) {
+        let speedups: Vec<_> = comparisons
+            .iter()
+            .map(|compare| compare.as_ref().map(Comparison::ratio))
+            .collect();
+        let mut summary = json!({ "name": name, "speedups": speedups });
+        if self.display {
+            let comparisons: Vec<_> = comparisons
+                .iter()
+                .map(|compare| compare.as_ref().map(|compare| compare.to_a_flex_str()))
+                .collect();
+            summary["comparisons"] = json!(comparisons);
+        }
+        self.summary = Some(summary);
+    }
+
+    pub(crate) fn set_footnotes(&mut self, footnotes: &[Footnote]) {
+        self.footnotes = footnotes.to_vec();
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        let mut value = json!({ "table": self.name, "columns": self.columns, "rows": self.rows });
+        // Only set when a summary row is enabled
+        if let Some(summary) = &self.summary {
+            value["summary"] = summary.clone();
+        }
+        // Only set when the table has footnotes
+        if !self.footnotes.is_empty() {
+            value["footnotes"] = json!(self.footnotes);
+        }
+        value
+    }
+}
+
+// *** JSON Formatter ***
+
+/// This formatter outputs the processed tables as a JSON document for downstream tooling: the
+/// top level comments and each table with its name, comment, columns and rows. Each cell holds
+/// the time in picoseconds and the speedup ratio compared to the first column of the row. Any
+/// provenance enabled in the footer options is held in `metadata`
+#[derive(Clone, Debug, Default)]
+pub struct JsonFormatter {
+    top_comments: IndexMap<AFlexStr, AFlexStr>,
+    section: Option<AFlexStr>,
+    table: Option<(TableData, Option<AFlexStr>)>,
+    tables: Vec<Value>,
+    metadata: Metadata,
+}
+
+impl JsonFormatter {
+    /// Create a new `JsonFormatter`
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn start(
+        &mut self,
+        _buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        _toc: &Toc,
+    ) {
+        self.top_comments = top_comments.clone();
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        let mut data = json!({
+            "top_comments": self.top_comments,
+            "tables": self.tables,
+        });
+        // Only set when there is provenance
+        if self.metadata.provenance_str().is_some() {
+            data["metadata"] = json!(self.metadata);
+        }
+
+        // Serializing a JSON value can't fail
+        buffer.push_str(&serde_json::to_string_pretty(&data).unwrap_or_default());
+        buffer.push('\n');
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn start_section(&mut self, _buffer: &mut String, name: &AFlexStr) {
+        self.section = Some(name.clone());
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.section = None;
+    }
+
+    fn start_table(
+        &mut self,
+        _buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        self.table = Some((TableData::new(name, columns), comment.cloned()));
+    }
+
+    fn end_table(&mut self, _buffer: &mut String) {
+        if let Some((table_data, comment)) = self.table.take() {
+            let mut table = table_data.to_value();
+            table["comment"] = json!(comment);
+            // Only set when output is split into sections (ex: workspace members)
+            if let Some(section) = &self.section {
+                table["section"] = json!(section);
+            }
+            self.tables.push(table);
+        }
+    }
+
+    fn start_row(&mut self, _buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.start_row(name);
+        }
+    }
+
+    fn end_row(&mut self, _buffer: &mut String) {}
+
+    fn used_column(&mut self, _buffer: &mut String, cell: &CellData, _max_width: usize) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.add_cell(Some(cell));
+        }
+    }
+
+    fn unused_column(&mut self, _buffer: &mut String, _max_width: usize) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.add_cell(None);
+        }
+    }
+
+    fn summary_row(
+        &mut self,
+        _buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.set_summary(name, comparisons);
+        }
+    }
+
+    fn footnotes(&mut self, _buffer: &mut String, footnotes: &[Footnote]) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.set_footnotes(footnotes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CriterionTableData, TablesConfig};
+
+    fn render(benchmarks: &[(&str, f64)]) -> Value {
+        let json = CriterionTableData::from_times(benchmarks)
+            .make_tables(JsonFormatter::new(), &TablesConfig::default());
+        serde_json::from_str(&json).expect("valid JSON")
+    }
+
+    #[test]
+    fn raw_values() {
+        let json = render(&[
+            ("Fib/std/10", 100.0),
+            ("Fib/fast/10", 50.0),
+            ("Fib/std/20", 200.0),
+        ]);
+        let table = &json["tables"][0];
+        assert_eq!(table["table"], "Fib");
+        assert_eq!(table["columns"], json!(["std", "fast"]));
+
+        let row = &table["rows"][0];
+        assert_eq!(row["name"], "10");
+        assert_eq!(
+            row["cells"][1],
+            json!({
+                "column": "fast",
+                "time_ps": 50000.0,
+                "lower_bound_ps": 50000.0,
+                "upper_bound_ps": 50000.0,
+                "speedup": 2.0,
+            })
+        );
+
+        // Blank cells are left out
+        let cells = table["rows"][1]["cells"].as_array().expect("cells");
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0]["column"], "std");
+    }
+
+    #[test]
+    fn one_entry_per_table() {
+        let json = render(&[("Fib/std/10", 100.0), ("Sort/std/10", 10.0)]);
+        let tables: Vec<_> = json["tables"]
+            .as_array()
+            .expect("tables")
+            .iter()
+            .map(|table| &table["table"])
+            .collect();
+        assert_eq!(tables, ["Fib", "Sort"]);
+        assert_eq!(json["top_comments"], json!({}));
+    }
+}
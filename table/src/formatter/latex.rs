@@ -0,0 +1,332 @@
+use super::slug::SlugStrategy;
+use crate::{
+    CellData, CellRole, ChangeType, ColumnInfo, Comparison, EmbedOptions, Footnote, Formatter,
+    Metadata, Toc,
+};
+use flexstr::{AFlexStr, IntoFlex, ToAFlexStr, ToFlex};
+use indexmap::IndexMap;
+
+const CT_URL: &str = "https://github.com/nu11ptr/criterion-table";
+
+// The preamble of a standalone document
+const PREAMBLE: &str = "\\documentclass{article}\n\
+                        \\usepackage[T1]{fontenc}\n\
+                        \\usepackage{booktabs}\n\
+                        \\begin{document}\n\n";
+
+// *** LaTeX Formatter ***
+
+/// This formatter outputs LaTeX tables using the `booktabs` package. Each table is written as a
+/// `table` float with its name as the caption (labeled `tab:` + its anchor), so it can be included
+/// in a paper via `\input`. If `standalone` is set, a full document is written instead (for
+/// previewing) with a list of tables. Faster comparisons are bold and slower ones are emphasized
+#[derive(Clone, Debug, Default)]
+pub struct LatexFormatter {
+    standalone: bool,
+    // Whether the `tabular` of the current table hasn't been ended yet
+    in_tabular: bool,
+    // The footer options and provenance. Unless standalone, they are written as comments at the
+    // start (so an `\input` file is only tables)
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
+
+impl LatexFormatter {
+    /// Create a new `LatexFormatter`
+    #[inline]
+    pub fn new(standalone: bool) -> Self {
+        Self {
+            standalone,
+            in_tabular: false,
+            metadata: Default::default(),
+            embed: None,
+        }
+    }
+
+    fn escape(s: &str) -> AFlexStr {
+        if !s.contains([
+            '\\', '&', '%', '$', '#', '_', '{', '}', '~', '^', '±', 'µ', 'σ',
+        ]) {
+            return s.to_flex();
+        }
+
+        let mut escaped = String::with_capacity(s.len() + 8);
+
+        for ch in s.chars() {
+            match ch {
+                '\\' => escaped.push_str("\\textbackslash{}"),
+                '~' => escaped.push_str("\\textasciitilde{}"),
+                '^' => escaped.push_str("\\textasciicircum{}"),
+                '±' => escaped.push_str("$\\pm$"),
+                'µ' => escaped.push_str("$\\mu$"),
+                'σ' => escaped.push_str("$\\sigma$"),
+                '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                    escaped.push('\\');
+                    escaped.push(ch);
+                }
+                ch => escaped.push(ch),
+            }
+        }
+
+        escaped.into_flex()
+    }
+
+    fn write_heading(buffer: &mut String, name: &str) {
+        buffer.push_str("\\subsection*{");
+        buffer.push_str(&Self::escape(name));
+        buffer.push_str("}\n\n");
+    }
+
+    // Emoji can't be typeset by pdflatex, so math symbols are used instead
+    fn role_marker(role: CellRole) -> &'static str {
+        match role {
+            CellRole::Normal => "",
+            CellRole::Best => "$\\star$ ",
+            CellRole::Worst => "$\\dagger$ ",
+        }
+    }
+
+    // Same thresholds as the GFM formatter: faster is bold, slower is emphasized
+    fn compare_str(compare: &Comparison, speedup_str: AFlexStr) -> String {
+        let speedup_str = Self::escape(&speedup_str);
+
+        if *compare > 0.9 {
+            format!("\\textbf{{{speedup_str}}}")
+        } else if *compare < 0.9 {
+            format!("\\emph{{{speedup_str}}}")
+        } else {
+            speedup_str.to_string()
+        }
+    }
+
+    fn end_tabular(&mut self, buffer: &mut String) {
+        if self.in_tabular {
+            buffer.push_str("\\bottomrule\n\\end{tabular}\n");
+            self.in_tabular = false;
+        }
+    }
+
+    fn write_row(buffer: &mut String, cells: impl IntoIterator<Item = String>) {
+        let cells: Vec<_> = cells.into_iter().collect();
+        buffer.push_str(&cells.join(" & "));
+        buffer.push_str(" \\\\\n");
+    }
+}
+
+impl Formatter for LatexFormatter {
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
+    ) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        if self.standalone {
+            buffer.push_str(PREAMBLE);
+            buffer.push_str("\\section*{");
+            buffer.push_str(&Self::escape(toc.title));
+            buffer.push_str("}\n\n");
+            if toc.enabled {
+                buffer.push_str("\\listoftables\n\n");
+            }
+        } else {
+            if self.metadata.footer {
+                match &self.metadata.footer_text {
+                    Some(text) => buffer.push_str(&format!("% {text}\n")),
+                    None => buffer.push_str(&format!("% Made with criterion-table ({CT_URL})\n")),
+                }
+                if let Some(provenance) = self.metadata.provenance_str() {
+                    buffer.push_str(&format!("% {provenance}\n"));
+                }
+            }
+            buffer.push_str("% Requires \\usepackage{booktabs}\n\n");
+        }
+
+        // Write out all the comment sections and comments (comments are written as is, so they
+        // can contain LaTeX)
+        for (header, comment) in top_comments {
+            Self::write_heading(buffer, header);
+            buffer.push_str(comment);
+            buffer.push('\n');
+        }
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        if self.standalone && self.embed.is_none() {
+            // A standalone document has no credit by default, only any custom text and provenance
+            let lines: Vec<_> = self
+                .metadata
+                .footer_text
+                .iter()
+                .map(AFlexStr::to_string)
+                .chain(
+                    self.metadata
+                        .provenance_str()
+                        .map(|p| Self::escape(&p).to_string()),
+                )
+                .collect();
+            if self.metadata.footer && !lines.is_empty() {
+                buffer.push_str("\\bigskip\\hrule\\smallskip\n\\noindent ");
+                buffer.push_str(&lines.join("\\\\\n"));
+                buffer.push_str("\n\n");
+            }
+            buffer.push_str("\\end{document}\n");
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    // An `itemize` list with each label in bold
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        let mut comment = String::new();
+        comment.push_str("\\begin{itemize}\n");
+        for (label, value) in environment {
+            comment.push_str(&format!(
+                "\\item \\textbf{{{}:}} {}\n",
+                Self::escape(label),
+                Self::escape(value)
+            ));
+        }
+        comment.push_str("\\end{itemize}\n");
+        comment.into_flex()
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            Self::write_heading(buffer, name);
+        }
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {}
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        if let Some(comments) = comment {
+            buffer.push_str(comments);
+            buffer.push('\n');
+        }
+
+        buffer.push_str("\\begin{table}[htbp]\n\\centering\n\\caption{");
+        buffer.push_str(&Self::escape(name));
+        buffer.push_str("}\n\\label{tab:");
+        buffer.push_str(&self.anchor(name));
+        buffer.push_str("}\n");
+
+        // Row names are left aligned and each column's cells are right aligned
+        buffer.push_str("\\begin{tabular}{l");
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        for _ in &columns[1..] {
+            buffer.push('r');
+        }
+        buffer.push_str("}\n\\toprule\n");
+
+        // The first header cell is blank (it is above the row names)
+        let header = columns[1..]
+            .iter()
+            .map(|column| format!("\\texttt{{{}}}", Self::escape(&column.name)));
+        Self::write_row(buffer, std::iter::once(String::new()).chain(header));
+        buffer.push_str("\\midrule\n");
+        self.in_tabular = true;
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        self.end_tabular(buffer);
+        buffer.push_str("\\end{table}\n\n");
+    }
+
+    // Written inside the float (below the tabular), so they stay with the table. Unlike comments,
+    // the text is escaped (ex: `100%` is common in footnotes, but starts a LaTeX comment)
+    fn footnotes(&mut self, buffer: &mut String, footnotes: &[Footnote]) {
+        self.end_tabular(buffer);
+        buffer.push_str("\\par\\smallskip\n{\\footnotesize\n");
+        for footnote in footnotes {
+            buffer.push_str(&Self::escape(&footnote.marker));
+            buffer.push(' ');
+            buffer.push_str(&Self::escape(&footnote.text));
+            buffer.push_str("\\par\n");
+        }
+        buffer.push_str("}\n");
+    }
+
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        if !name.is_empty() {
+            buffer.push_str("\\texttt{");
+            buffer.push_str(&Self::escape(name));
+            buffer.push('}');
+        }
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        buffer.push_str(" \\\\\n");
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, _max_width: usize) {
+        // Noise isn't emphasized as faster or slower
+        let compare_str = if cell.is_noise() {
+            Self::escape(&cell.compare_str()).to_string()
+        } else {
+            Self::compare_str(&cell.compare, cell.compare_str())
+        };
+
+        buffer.push_str(" & ");
+        buffer.push_str(Self::role_marker(cell.role));
+        buffer.push_str(&format!(
+            "\\texttt{{{}}} ({compare_str})",
+            Self::escape(&cell.measurement())
+        ));
+
+        if let Some(change) = cell.change {
+            let change_str = Self::escape(&change.to_a_flex_str());
+            buffer.push(' ');
+            match change.verdict {
+                ChangeType::Improved => buffer.push_str(&format!("\\textbf{{{change_str}}}")),
+                ChangeType::Regressed => buffer.push_str(&format!("\\emph{{{change_str}}}")),
+                ChangeType::NoChange => buffer.push_str(&change_str),
+            }
+        }
+
+        if !cell.tags.is_empty() {
+            let tags: Vec<_> = cell.tags.iter().map(AFlexStr::as_str).collect();
+            buffer.push_str(&format!(" [{}]", Self::escape(&tags.join(", "))));
+        }
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, _max_width: usize) {
+        buffer.push_str(" & --");
+    }
+
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        buffer.push_str("\\midrule\n");
+        let cells = comparisons.iter().map(|compare| match compare {
+            Some(compare) => Self::compare_str(compare, compare.to_a_flex_str()),
+            None => "--".to_string(),
+        });
+        let name = format!("\\textbf{{{}}}", Self::escape(name));
+        Self::write_row(buffer, std::iter::once(name).chain(cells));
+    }
+
+    fn anchor(&self, name: &str) -> AFlexStr {
+        SlugStrategy::Github.slug(name).into_flex()
+    }
+}
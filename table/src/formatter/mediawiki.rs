@@ -0,0 +1,303 @@
+use crate::{
+    CellData, Change, ChangeType, ColumnInfo, Comparison, EmbedOptions, Footnote, Formatter,
+    Metadata, Toc,
+};
+use flexstr::{AFlexStr, IntoFlex, ToAFlexStr, ToFlex};
+use indexmap::IndexMap;
+
+const CT_URL: &str = "https://github.com/nu11ptr/criterion-table";
+
+// The headings written before the tables
+const RESULTS_HEADING: &str = "Benchmark Results";
+
+// *** MediaWiki Formatter ***
+
+/// This formatter outputs MediaWiki markup, so results can be pasted directly into a wiki page.
+/// Each table is a `wikitable` under its own heading and the wiki builds the table of contents
+/// itself (placed via `__TOC__`). Faster comparisons are bold and slower ones are italics
+#[derive(Clone, Debug, Default)]
+pub struct MediaWikiFormatter {
+    in_section: bool,
+    // Cells of a row are written on one line, so the first needs a different delimiter
+    first_cell: bool,
+    // The footnotes of the current table, written below it
+    footnotes: Vec<Footnote>,
+    // The footer options and provenance, written by `end`
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
+
+impl MediaWikiFormatter {
+    /// Create a new `MediaWikiFormatter`
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Chars that would start markup (links, templates, bold/italics or table syntax) are written
+    // as entities
+    fn escape(s: &str) -> AFlexStr {
+        if !s.contains(['&', '<', '>', '|', '[', ']', '{', '}', '\'']) {
+            return s.to_flex();
+        }
+
+        let mut escaped = String::with_capacity(s.len() + 16);
+
+        for ch in s.chars() {
+            match ch {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '|' => escaped.push_str("&#124;"),
+                '[' => escaped.push_str("&#91;"),
+                ']' => escaped.push_str("&#93;"),
+                '{' => escaped.push_str("&#123;"),
+                '}' => escaped.push_str("&#125;"),
+                '\'' => escaped.push_str("&#39;"),
+                ch => escaped.push(ch),
+            }
+        }
+
+        escaped.into_flex()
+    }
+
+    fn write_heading(buffer: &mut String, level: usize, name: &str) {
+        let delimiter = "=".repeat(level);
+        buffer.push_str(&delimiter);
+        buffer.push(' ');
+        buffer.push_str(&Self::escape(name));
+        buffer.push(' ');
+        buffer.push_str(&delimiter);
+        buffer.push_str("\n\n");
+    }
+
+    // Same thresholds as the GFM formatter: faster is bold, slower is italics
+    fn compare_str(compare: &Comparison, speedup_str: AFlexStr) -> String {
+        if *compare > 0.9 {
+            format!("'''{speedup_str}'''")
+        } else if *compare < 0.9 {
+            format!("''{speedup_str}''")
+        } else {
+            speedup_str.to_string()
+        }
+    }
+
+    fn change_str(change: Change) -> String {
+        let change_str = change.to_a_flex_str();
+
+        match change.verdict {
+            ChangeType::Improved => format!("'''{change_str}'''"),
+            ChangeType::Regressed => format!("''{change_str}''"),
+            ChangeType::NoChange => change_str.to_string(),
+        }
+    }
+
+    fn write_cell(&mut self, buffer: &mut String, data: &str) {
+        buffer.push_str(if self.first_cell { "| " } else { " || " });
+        buffer.push_str(data);
+        self.first_cell = false;
+    }
+}
+
+impl Formatter for MediaWikiFormatter {
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
+    ) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        Self::write_heading(buffer, 1, toc.title);
+        if toc.enabled {
+            buffer.push_str("__TOC__\n\n");
+        } else {
+            buffer.push_str("__NOTOC__\n\n");
+        }
+
+        // Write out all the comment sections and comments (comments are written as is, so they
+        // can contain wiki markup)
+        for (header, comment) in top_comments {
+            Self::write_heading(buffer, 2, header);
+            buffer.push_str(comment);
+            buffer.push('\n');
+        }
+
+        Self::write_heading(buffer, 2, RESULTS_HEADING);
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        if self.embed.is_some() || !self.metadata.footer {
+            return;
+        }
+
+        buffer.push_str("----\n\n");
+        match &self.metadata.footer_text {
+            Some(text) => buffer.push_str(text),
+            None => {
+                buffer.push_str("Made with [");
+                buffer.push_str(CT_URL);
+                buffer.push_str(" criterion-table]");
+            }
+        }
+        buffer.push('\n');
+
+        if let Some(provenance) = self.metadata.provenance_str() {
+            buffer.push('\n');
+            buffer.push_str(&Self::escape(&provenance));
+            buffer.push('\n');
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    // A bullet list with each label in bold
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        let mut comment = String::new();
+        for (label, value) in environment {
+            comment.push_str(&format!(
+                "* '''{}:''' {}\n",
+                Self::escape(label),
+                Self::escape(value)
+            ));
+        }
+        comment.into_flex()
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            Self::write_heading(buffer, 3, name);
+        }
+        self.in_section = true;
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.in_section = false;
+    }
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        // Tables are nested one level deeper when inside a section
+        let level = if self.in_section { 4 } else { 3 };
+        if EmbedOptions::headings(self.embed) {
+            Self::write_heading(buffer, level, name);
+        }
+
+        if let Some(comments) = comment {
+            buffer.push_str(comments);
+            buffer.push('\n');
+        }
+
+        buffer.push_str("{| class=\"wikitable\"\n");
+        // The first header cell is blank (it is above the row names)
+        buffer.push('!');
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        for column in &columns[1..] {
+            buffer.push_str(" !! <code>");
+            buffer.push_str(&Self::escape(&column.name));
+            buffer.push_str("</code>");
+        }
+        buffer.push('\n');
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        buffer.push_str("|}\n\n");
+
+        for footnote in self.footnotes.drain(..) {
+            buffer.push_str(&Self::escape(&footnote.marker));
+            buffer.push(' ');
+            buffer.push_str(&footnote.text);
+            // A blank line between each, otherwise they are joined into one paragraph
+            buffer.push_str("\n\n");
+        }
+    }
+
+    fn footnotes(&mut self, _buffer: &mut String, footnotes: &[Footnote]) {
+        self.footnotes = footnotes.to_vec();
+    }
+
+    // Each row name is a header cell on its own line, followed by the other cells on one line
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        buffer.push_str("|-\n!");
+        if !name.is_empty() {
+            buffer.push_str(" <code>");
+            buffer.push_str(&Self::escape(name));
+            buffer.push_str("</code>");
+        }
+        buffer.push('\n');
+        self.first_cell = true;
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        buffer.push('\n');
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, _max_width: usize) {
+        // Noise isn't emphasized as faster or slower
+        let compare_str = if cell.is_noise() {
+            cell.compare_str().to_string()
+        } else {
+            Self::compare_str(&cell.compare, cell.compare_str())
+        };
+        let mut data = format!(
+            "{}<code>{}</code> ({compare_str})",
+            cell.role.marker(),
+            Self::escape(&cell.measurement())
+        );
+
+        if let Some(change) = cell.change {
+            data.push(' ');
+            data.push_str(&Self::change_str(change));
+        }
+
+        if !cell.tags.is_empty() {
+            let tags: Vec<_> = cell.tags.iter().map(AFlexStr::as_str).collect();
+            data.push_str(&format!(" &#91;{}&#93;", Self::escape(&tags.join(", "))));
+        }
+
+        self.write_cell(buffer, &data);
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, _max_width: usize) {
+        self.write_cell(buffer, "<code>N/A</code>");
+    }
+
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        buffer.push_str("|-\n! ");
+        buffer.push_str(&Self::escape(name));
+        buffer.push('\n');
+        self.first_cell = true;
+
+        for compare in comparisons {
+            match compare {
+                Some(compare) => {
+                    let compare_str = Self::compare_str(compare, compare.to_a_flex_str());
+                    self.write_cell(buffer, &compare_str);
+                }
+                None => self.unused_column(buffer, 0),
+            }
+        }
+
+        self.end_row(buffer);
+    }
+}
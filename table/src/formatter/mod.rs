@@ -1,3 +1,29 @@
+mod confluence;
+mod csv;
 mod gfm;
+mod html;
+mod json;
+mod latex;
+mod mediawiki;
+mod org;
+mod registry;
+mod rst;
+mod slug;
+#[cfg(feature = "template")]
+mod template;
+mod term;
 
+pub use confluence::*;
+pub use csv::*;
 pub use gfm::*;
+pub use html::*;
+pub use json::JsonFormatter;
+pub use latex::*;
+pub use mediawiki::*;
+pub use org::*;
+pub use registry::*;
+pub use rst::*;
+pub use slug::SlugStrategy;
+#[cfg(feature = "template")]
+pub use template::*;
+pub use term::*;
@@ -0,0 +1,332 @@
+use crate::{
+    display_width, CellData, ChangeType, ColumnInfo, Comparison, EmbedOptions, Footnote, Formatter,
+    Metadata, Toc, TocEntry,
+};
+use flexstr::{AFlexStr, IntoFlex, ToAFlexStr, ToFlex};
+use indexmap::IndexMap;
+
+const CT_URL: &str = "https://github.com/nu11ptr/criterion-table";
+
+// The headings written before the tables
+const TOC_HEADING: &str = "Table of Contents";
+const RESULTS_HEADING: &str = "Benchmark Results";
+
+// *** NOTE: These are display widths - each string is ASCII, so its length in bytes is the same ***
+// Width of a single item in verbatim (or bold, which is the same width)
+const FIRST_COL_EXTRA_WIDTH: usize = "==".len();
+// Width of one item in verbatim + one item in bold (italics is the same) in parens + one space
+const USED_EXTRA_WIDTH: usize = "== (**)".len();
+
+// *** Org Formatter ***
+
+/// This formatter outputs Emacs [org-mode](https://orgmode.org/) tables. Each table is written
+/// under its own heading and the table of contents links to each heading. Faster comparisons are
+/// bold and slower ones are italics
+#[derive(Clone, Debug, Default)]
+pub struct OrgFormatter {
+    in_section: bool,
+    // The inner width of each column of the current table
+    widths: Vec<usize>,
+    // The footer options and provenance, written by `end`
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
+
+impl OrgFormatter {
+    /// Create a new `OrgFormatter`
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // A `|` would end the table cell, so it is written as the `\vert` entity instead
+    fn escape(s: &str) -> AFlexStr {
+        if s.contains('|') {
+            s.replace('|', "\\vert{}").into_flex()
+        } else {
+            s.to_flex()
+        }
+    }
+
+    fn write_heading(buffer: &mut String, level: usize, name: &str) {
+        for _ in 0..level {
+            buffer.push('*');
+        }
+        buffer.push(' ');
+        buffer.push_str(name);
+        buffer.push_str("\n\n");
+    }
+
+    // Org resolves `[[*Heading]]` links to the heading with that text
+    fn write_toc_entry(buffer: &mut String, entry: &str, depth: usize) {
+        for _ in 0..depth {
+            buffer.push_str("  ");
+        }
+        buffer.push_str("- [[*");
+        buffer.push_str(entry);
+        buffer.push_str("][");
+        buffer.push_str(entry);
+        buffer.push_str("]]\n");
+    }
+
+    fn write_toc_entries(buffer: &mut String, entries: &[TocEntry], depth: usize) {
+        for entry in entries {
+            Self::write_toc_entry(buffer, entry.name, depth);
+            Self::write_toc_entries(buffer, &entry.children, depth + 1);
+        }
+    }
+
+    // Same thresholds as the GFM formatter: faster is bold, slower is italics
+    fn compare_str(compare: &Comparison, speedup_str: AFlexStr) -> String {
+        if *compare > 0.9 {
+            format!("*{speedup_str}*")
+        } else if *compare < 0.9 {
+            format!("/{speedup_str}/")
+        } else {
+            speedup_str.to_string()
+        }
+    }
+
+    fn write_cell(buffer: &mut String, data: &str, width: usize) {
+        buffer.push_str("| ");
+        buffer.push_str(data);
+
+        // Pad the rest of the column (inclusive to handle trailing space)
+        let remaining = width.saturating_sub(display_width(data));
+        for _ in 0..=remaining {
+            buffer.push(' ');
+        }
+    }
+
+    // A horizontal rule spanning the table, ex: `|---+---|`
+    fn write_separator(&self, buffer: &mut String) {
+        buffer.push('|');
+        for (idx, width) in self.widths.iter().enumerate() {
+            if idx > 0 {
+                buffer.push('+');
+            }
+            // Includes the space on either side of the cell
+            for _ in 0..width + 2 {
+                buffer.push('-');
+            }
+        }
+        buffer.push_str("|\n");
+    }
+}
+
+impl Formatter for OrgFormatter {
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
+    ) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        Self::write_heading(buffer, 1, toc.title);
+
+        if toc.enabled {
+            Self::write_heading(buffer, 2, TOC_HEADING);
+
+            // Write each ToC entry in comments
+            for section_entry in top_comments.keys() {
+                Self::write_toc_entry(buffer, section_entry, 0);
+            }
+
+            Self::write_toc_entry(buffer, RESULTS_HEADING, 0);
+            // Write each Benchmark ToC entry
+            Self::write_toc_entries(buffer, &toc.entries, 1);
+            buffer.push('\n');
+        }
+
+        // Write out all the comment sections and comments (comments are written as is, so they
+        // can contain org markup)
+        for (header, comment) in top_comments {
+            Self::write_heading(buffer, 2, header);
+            buffer.push_str(comment);
+            buffer.push('\n');
+        }
+
+        Self::write_heading(buffer, 2, RESULTS_HEADING);
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        if self.embed.is_some() || !self.metadata.footer {
+            return;
+        }
+
+        buffer.push_str("-----\n\n");
+        match &self.metadata.footer_text {
+            Some(text) => buffer.push_str(text),
+            None => {
+                buffer.push_str("Made with [[");
+                buffer.push_str(CT_URL);
+                buffer.push_str("][criterion-table]]");
+            }
+        }
+        buffer.push('\n');
+
+        if let Some(provenance) = self.metadata.provenance_str() {
+            buffer.push('\n');
+            buffer.push_str(&Self::escape(&provenance));
+            buffer.push('\n');
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    // A list with each label in bold
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        let mut comment = String::new();
+        for (label, value) in environment {
+            comment.push_str(&format!(
+                "- *{}:* {}\n",
+                Self::escape(label),
+                Self::escape(value)
+            ));
+        }
+        comment.into_flex()
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            Self::write_heading(buffer, 3, name);
+        }
+        self.in_section = true;
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.in_section = false;
+    }
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        // Tables are nested one level deeper when inside a section
+        let level = if self.in_section { 4 } else { 3 };
+        if EmbedOptions::headings(self.embed) {
+            Self::write_heading(buffer, level, name);
+        }
+
+        if let Some(comments) = comment {
+            buffer.push_str(comments);
+            buffer.push('\n');
+        }
+
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        self.widths = vec![columns[0].max_width + FIRST_COL_EXTRA_WIDTH];
+        self.widths.extend(
+            columns[1..]
+                .iter()
+                .map(|column| column.max_width + USED_EXTRA_WIDTH),
+        );
+
+        // The first header cell is blank (it is above the row names)
+        Self::write_cell(buffer, "", self.widths[0]);
+        for (column, width) in columns[1..].iter().zip(&self.widths[1..]) {
+            Self::write_cell(buffer, &format!("={}=", Self::escape(&column.name)), *width);
+        }
+        self.end_row(buffer);
+        self.write_separator(buffer);
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        buffer.push('\n');
+    }
+
+    fn footnotes(&mut self, buffer: &mut String, footnotes: &[Footnote]) {
+        buffer.push('\n');
+        for footnote in footnotes {
+            buffer.push_str(&footnote.marker);
+            buffer.push(' ');
+            buffer.push_str(&footnote.text);
+            buffer.push('\n');
+        }
+    }
+
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, max_width: usize) {
+        if name.is_empty() {
+            Self::write_cell(buffer, "", max_width + FIRST_COL_EXTRA_WIDTH);
+        } else {
+            let name = format!("={}=", Self::escape(name));
+            Self::write_cell(buffer, &name, max_width + FIRST_COL_EXTRA_WIDTH);
+        }
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        buffer.push_str("|\n");
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, max_width: usize) {
+        // Noise isn't emphasized as faster or slower
+        let compare_str = if cell.is_noise() {
+            cell.compare_str().to_string()
+        } else {
+            Self::compare_str(&cell.compare, cell.compare_str())
+        };
+        let mut data = format!(
+            "{}={}= ({compare_str})",
+            cell.role.marker(),
+            cell.measurement()
+        );
+
+        if let Some(change) = cell.change {
+            let change_str = change.to_a_flex_str();
+            data.push(' ');
+            match change.verdict {
+                ChangeType::Improved => data.push_str(&format!("*{change_str}*")),
+                ChangeType::Regressed => data.push_str(&format!("/{change_str}/")),
+                ChangeType::NoChange => data.push_str(&change_str),
+            }
+        }
+
+        if !cell.tags.is_empty() {
+            let tags: Vec<_> = cell.tags.iter().map(AFlexStr::as_str).collect();
+            data.push_str(&format!(" [{}]", Self::escape(&tags.join(", "))));
+        }
+
+        Self::write_cell(buffer, &data, max_width + USED_EXTRA_WIDTH);
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, max_width: usize) {
+        Self::write_cell(buffer, "=N/A=", max_width + USED_EXTRA_WIDTH);
+    }
+
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        columns: &[ColumnInfo],
+    ) {
+        self.write_separator(buffer);
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        let name = format!("*{}*", Self::escape(name));
+        Self::write_cell(buffer, &name, columns[0].max_width + FIRST_COL_EXTRA_WIDTH);
+
+        for (compare, column) in comparisons.iter().zip(&columns[1..]) {
+            match compare {
+                Some(compare) => {
+                    let compare_str = Self::compare_str(compare, compare.to_a_flex_str());
+                    Self::write_cell(buffer, &compare_str, column.max_width + USED_EXTRA_WIDTH);
+                }
+                None => self.unused_column(buffer, column.max_width),
+            }
+        }
+
+        self.end_row(buffer);
+    }
+}
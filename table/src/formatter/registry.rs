@@ -0,0 +1,133 @@
+use super::{
+    BoxStyle, ConfluenceFormatter, CsvFormatter, GFMFormatter, HtmlFormatter, JsonFormatter,
+    LatexFormatter, MediaWikiFormatter, OrgFormatter, RstFormatter, TermFormatter,
+};
+use crate::{Error, Formatter, TablesConfig};
+use flexstr::{AFlexStr, ToFlex};
+use indexmap::IndexMap;
+
+/// The name of the format used when none is chosen
+pub const DEFAULT_FORMAT: &str = "gfm";
+
+type MakeFormatter = Box<dyn Fn(&TablesConfig) -> Box<dyn Formatter>>;
+
+// A registered format
+struct Entry {
+    extension: AFlexStr,
+    make: MakeFormatter,
+}
+
+// *** Formatter Registry ***
+
+/// Formatters registered under a name (ex: `gfm`), so they can be chosen at runtime (ex: via
+/// `format` in the config). Each is made from the config by its own function, so tools built on
+/// this crate can add their own formats alongside the built-in ones:
+///
+/// ```
+/// use criterion_table::formatter::{FormatterRegistry, RstFormatter};
+///
+/// let mut registry = FormatterRegistry::new();
+/// registry.register("sphinx", "rst", |_config| Box::new(RstFormatter::new()));
+/// assert!(registry.contains("sphinx"));
+/// assert!(registry.make("docx", &Default::default()).is_err());
+/// ```
+pub struct FormatterRegistry {
+    formats: IndexMap<AFlexStr, Entry>,
+}
+
+impl Default for FormatterRegistry {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatterRegistry {
+    /// Create a registry of the built-in formats: `gfm`, `html`, `csv`, `tsv`, `json`, `rst`,
+    /// `term` (uncolored), `org`, `confluence`, `latex` (just the tables) and `mediawiki`
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register("gfm", "md", |config| {
+            Box::new(GFMFormatter::new(config.gfm.clone()))
+        });
+        registry.register("html", "html", |config| {
+            Box::new(HtmlFormatter::new(config.html.clone()))
+        });
+        registry.register("csv", "csv", |config| {
+            Box::new(CsvFormatter::csv().with_extra_stats(config.extra_stats))
+        });
+        registry.register("tsv", "tsv", |config| {
+            Box::new(CsvFormatter::tsv().with_extra_stats(config.extra_stats))
+        });
+        registry.register("json", "json", |_| Box::new(JsonFormatter::new()));
+        registry.register("rst", "rst", |_| Box::new(RstFormatter::new()));
+        registry.register("term", "txt", |_| {
+            Box::new(TermFormatter::new(BoxStyle::Unicode, false))
+        });
+        registry.register("org", "org", |_| Box::new(OrgFormatter::new()));
+        registry.register("confluence", "wiki", |_| {
+            Box::new(ConfluenceFormatter::new())
+        });
+        registry.register("latex", "tex", |_| Box::new(LatexFormatter::new(false)));
+        registry.register("mediawiki", "wiki", |_| Box::new(MediaWikiFormatter::new()));
+        registry
+    }
+
+    /// Create a registry without any formats
+    #[inline]
+    pub fn empty() -> Self {
+        Self {
+            formats: IndexMap::new(),
+        }
+    }
+
+    /// Register a format under `name`, written to files with `extension` (ex: `md`), replacing
+    /// any already registered under that name (including a built-in one)
+    pub fn register(
+        &mut self,
+        name: impl Into<AFlexStr>,
+        extension: impl Into<AFlexStr>,
+        make: impl Fn(&TablesConfig) -> Box<dyn Formatter> + 'static,
+    ) {
+        let entry = Entry {
+            extension: extension.into(),
+            make: Box::new(make),
+        };
+        self.formats.insert(name.into(), entry);
+    }
+
+    /// True if a format is registered under `name`
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.formats.contains_key(&name.to_flex())
+    }
+
+    /// The names of the registered formats, in the order they were registered
+    pub fn names(&self) -> impl Iterator<Item = &AFlexStr> {
+        self.formats.keys()
+    }
+
+    /// The file extension of the format registered under `name`, if any
+    pub fn extension(&self, name: &str) -> Option<&AFlexStr> {
+        self.formats
+            .get(&name.to_flex())
+            .map(|entry| &entry.extension)
+    }
+
+    /// Make the formatter registered under `name` from `config`
+    pub fn make(&self, name: &str, config: &TablesConfig) -> Result<Box<dyn Formatter>, Error> {
+        match self.formats.get(&name.to_flex()) {
+            Some(entry) => Ok((entry.make)(config)),
+            None => Err(Error::UnknownFormat {
+                format: name.to_string(),
+                known: self.known(),
+            }),
+        }
+    }
+
+    // The registered names, for error messages
+    fn known(&self) -> String {
+        let names: Vec<_> = self.names().map(AFlexStr::as_str).collect();
+        names.join(", ")
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{
+    display_width, CellData, Change, ChangeType, ColumnInfo, Comparison, EmbedOptions, Footnote,
+    Formatter, Metadata, Toc, TocEntry,
+};
+use flexstr::{AFlexStr, IntoFlex, ToAFlexStr, ToFlex};
+use indexmap::IndexMap;
+
+const CT_URL: &str = "https://github.com/nu11ptr/criterion-table";
+
+// Heading underline chars by level (RST infers the level from the order they are first seen)
+const HEADING_CHARS: [char; 4] = ['=', '=', '-', '~'];
+
+// *** RST Formatter ***
+
+/// This formatter outputs reStructuredText (for Sphinx docs). Each table is written as a
+/// `list-table` directive and the table of contents links to each heading
+#[derive(Clone, Debug, Default)]
+pub struct RstFormatter {
+    in_section: bool,
+    // The footer options and provenance, written by `end`
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
+
+impl RstFormatter {
+    /// Create a new `RstFormatter`
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn escape(s: &str) -> AFlexStr {
+        if !s.contains(['\\', '`', '*', '_', '|']) {
+            return s.to_flex();
+        }
+
+        let mut escaped = String::with_capacity(s.len() + 8);
+
+        for ch in s.chars() {
+            if matches!(ch, '\\' | '`' | '*' | '_' | '|') {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+
+        escaped.into_flex()
+    }
+
+    fn write_heading(buffer: &mut String, level: usize, name: &str) {
+        let name = Self::escape(name);
+        let ch = HEADING_CHARS[level - 1];
+        let underline = ch.to_string().repeat(display_width(&name));
+
+        // The top level heading is the only one with an overline
+        if level == 1 {
+            buffer.push_str(&underline);
+            buffer.push('\n');
+        }
+        buffer.push_str(&name);
+        buffer.push('\n');
+        buffer.push_str(&underline);
+        buffer.push_str("\n\n");
+    }
+
+    // Each heading is an implicit hyperlink target named after its text
+    fn write_toc_entry(buffer: &mut String, entry: &str, depth: usize) {
+        for _ in 0..depth {
+            buffer.push_str("  ");
+        }
+        buffer.push_str("- `");
+        buffer.push_str(&Self::escape(entry));
+        buffer.push_str("`_\n");
+    }
+
+    fn write_toc_entries(buffer: &mut String, entries: &[TocEntry], depth: usize) {
+        for entry in entries {
+            Self::write_toc_entry(buffer, entry.name, depth);
+            // Nested lists must be separated from their parent item by blank lines
+            if !entry.children.is_empty() {
+                buffer.push('\n');
+                Self::write_toc_entries(buffer, &entry.children, depth + 1);
+                buffer.push('\n');
+            }
+        }
+    }
+
+    // Same thresholds and styling as the GFM formatter: faster is bold, slower is italics
+    // Same symbols as the GFM formatter
+    fn change_str(change: Change) -> String {
+        let change_str = change.to_a_flex_str();
+
+        match change.verdict {
+            ChangeType::Improved => format!("✅ {change_str}"),
+            ChangeType::Regressed => format!("❌ {change_str}"),
+            ChangeType::NoChange => change_str.to_string(),
+        }
+    }
+
+    fn compare_str(compare: &Comparison, speedup_str: AFlexStr) -> String {
+        if *compare >= 1.8 {
+            format!("🚀 **{speedup_str}**")
+        } else if *compare > 0.9 {
+            format!("✅ **{speedup_str}**")
+        } else if *compare < 0.9 {
+            format!("❌ *{speedup_str}*")
+        } else {
+            speedup_str.to_string()
+        }
+    }
+
+    fn write_item(buffer: &mut String, first: bool, item: &str) {
+        buffer.push_str(if first { "   * -" } else { "     -" });
+        if !item.is_empty() {
+            buffer.push(' ');
+            buffer.push_str(item);
+        }
+        buffer.push('\n');
+    }
+}
+
+impl Formatter for RstFormatter {
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
+    ) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        Self::write_heading(buffer, 1, toc.title);
+
+        if toc.enabled {
+            Self::write_heading(buffer, 2, "Table of Contents");
+
+            // Write each ToC entry in comments
+            for section_entry in top_comments.keys() {
+                Self::write_toc_entry(buffer, section_entry, 0);
+            }
+
+            Self::write_toc_entry(buffer, "Benchmark Results", 0);
+            // Write each Benchmark ToC entry
+            if !toc.entries.is_empty() {
+                buffer.push('\n');
+                Self::write_toc_entries(buffer, &toc.entries, 1);
+            }
+            buffer.push('\n');
+        }
+
+        // Write out all the comment sections and comments (comments are written as is, so they
+        // can contain RST)
+        for (header, comment) in top_comments {
+            Self::write_heading(buffer, 2, header);
+            buffer.push_str(comment);
+            buffer.push('\n');
+        }
+
+        Self::write_heading(buffer, 2, "Benchmark Results");
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        if self.embed.is_some() || !self.metadata.footer {
+            return;
+        }
+
+        buffer.push_str("----\n\n");
+        match &self.metadata.footer_text {
+            Some(text) => buffer.push_str(text),
+            None => {
+                buffer.push_str("Made with `criterion-table <");
+                buffer.push_str(CT_URL);
+                buffer.push_str(">`_");
+            }
+        }
+        buffer.push('\n');
+
+        if let Some(provenance) = self.metadata.provenance_str() {
+            buffer.push('\n');
+            buffer.push_str(&Self::escape(&provenance));
+            buffer.push('\n');
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    // A bullet list with each label in bold
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        let mut comment = String::new();
+        for (label, value) in environment {
+            comment.push_str(&format!(
+                "- **{}:** {}\n",
+                Self::escape(label),
+                Self::escape(value)
+            ));
+        }
+        comment.into_flex()
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            Self::write_heading(buffer, 3, name);
+        }
+        self.in_section = true;
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.in_section = false;
+    }
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        // Tables are nested one level deeper when inside a section
+        let level = if self.in_section { 4 } else { 3 };
+        if EmbedOptions::headings(self.embed) {
+            Self::write_heading(buffer, level, name);
+        }
+
+        if let Some(comments) = comment {
+            buffer.push_str(comments);
+            buffer.push('\n');
+        }
+
+        buffer.push_str(".. list-table::\n   :header-rows: 1\n   :stub-columns: 1\n\n");
+
+        // The first header cell is blank (it is above the row names)
+        Self::write_item(buffer, true, "");
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        for column in &columns[1..] {
+            Self::write_item(buffer, false, &format!("``{}``", column.name));
+        }
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        buffer.push('\n');
+    }
+
+    // A line block, so each footnote is on its own line
+    fn footnotes(&mut self, buffer: &mut String, footnotes: &[Footnote]) {
+        buffer.push('\n');
+        for footnote in footnotes {
+            buffer.push_str("| ");
+            buffer.push_str(&footnote.marker);
+            buffer.push(' ');
+            buffer.push_str(&footnote.text);
+            buffer.push('\n');
+        }
+    }
+
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        if name.is_empty() {
+            Self::write_item(buffer, true, "");
+        } else {
+            Self::write_item(buffer, true, &format!("``{name}``"));
+        }
+    }
+
+    fn end_row(&mut self, _buffer: &mut String) {}
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, _max_width: usize) {
+        // Noise isn't emphasized as faster or slower
+        let compare_str = if cell.is_noise() {
+            cell.compare_str().to_string()
+        } else {
+            Self::compare_str(&cell.compare, cell.compare_str())
+        };
+        let mut data = format!(
+            "{}``{}`` ({compare_str})",
+            cell.role.marker(),
+            cell.measurement()
+        );
+
+        if let Some(change) = cell.change {
+            data.push(' ');
+            data.push_str(&Self::change_str(change));
+        }
+
+        if !cell.tags.is_empty() {
+            let tags: Vec<_> = cell.tags.iter().map(AFlexStr::as_str).collect();
+            data.push_str(&format!(" [{}]", Self::escape(&tags.join(", "))));
+        }
+
+        Self::write_item(buffer, false, &data);
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, _max_width: usize) {
+        Self::write_item(buffer, false, "``N/A``");
+    }
+
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        Self::write_item(buffer, true, &format!("**{}**", Self::escape(name)));
+
+        for compare in comparisons {
+            match compare {
+                Some(compare) => {
+                    let compare_str = Self::compare_str(compare, compare.to_a_flex_str());
+                    Self::write_item(buffer, false, &compare_str)
+                }
+                None => self.unused_column(buffer, 0),
+            }
+        }
+    }
+}
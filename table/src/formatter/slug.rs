@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+
+use flexstr::{a_flex_fmt, AFlexStr};
+use serde::Deserialize;
+
+// Kramdown's anchor for a heading with no letters or numbers left
+const KRAMDOWN_EMPTY_SLUG: &str = "section";
+
+// *** Slug Strategy ***
+
+/// The rules heading anchors (slugs) are made with, which differ between markdown renderers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlugStrategy {
+    /// Github: lowercase, punctuation removed and spaces replaced by dashes (ex: `Parse (u8)` ->
+    /// `parse-u8`). Letters and numbers of any language are kept
+    #[default]
+    Github,
+    /// GitLab: like Github, but runs of dashes are collapsed into one (ex: `A - B` -> `a-b`)
+    Gitlab,
+    /// Kramdown (ex: Jekyll): only ASCII letters and numbers are kept, anything before the first
+    /// letter is removed and everything else becomes a dash
+    Kramdown,
+}
+
+impl SlugStrategy {
+    /// Returns the anchor of a heading with the text `text`, ignoring any duplicate headings
+    pub fn slug(self, text: &str) -> String {
+        let text = text.trim();
+
+        match self {
+            SlugStrategy::Github | SlugStrategy::Gitlab => {
+                let mut slug = String::with_capacity(text.len());
+
+                for ch in text.chars() {
+                    match ch {
+                        // GitLab collapses runs of dashes into one
+                        ' ' | '-' if self == SlugStrategy::Gitlab && slug.ends_with('-') => {}
+                        ' ' | '-' => slug.push('-'),
+                        '_' => slug.push('_'),
+                        ch if ch.is_alphanumeric() => slug.extend(ch.to_lowercase()),
+                        // Any other punctuation (or emoji) is removed
+                        _ => {}
+                    }
+                }
+
+                slug
+            }
+            SlugStrategy::Kramdown => {
+                let slug: String = text
+                    .chars()
+                    .filter(|ch| ch.is_ascii_alphanumeric() || *ch == ' ' || *ch == '-')
+                    .skip_while(|ch| !ch.is_ascii_alphabetic())
+                    .map(|ch| {
+                        if ch.is_ascii_alphanumeric() {
+                            ch.to_ascii_lowercase()
+                        } else {
+                            '-'
+                        }
+                    })
+                    .collect();
+
+                if slug.is_empty() {
+                    KRAMDOWN_EMPTY_SLUG.to_string()
+                } else {
+                    slug
+                }
+            }
+        }
+    }
+}
+
+// *** Slugger ***
+
+// Makes the anchors of the headings of one document in order, so duplicate headings get unique
+// anchors (ex: `fibonacci`, `fibonacci-1`) just like the renderer gives them
+#[derive(Clone, Debug)]
+pub(crate) struct Slugger<'a> {
+    strategy: SlugStrategy,
+    prefix: &'a str,
+    seen: HashSet<AFlexStr>,
+}
+
+impl<'a> Slugger<'a> {
+    pub(crate) fn new(strategy: SlugStrategy, prefix: &'a str) -> Self {
+        Self {
+            strategy,
+            prefix,
+            seen: HashSet::new(),
+        }
+    }
+
+    // The anchor of the next heading with the text `text`
+    pub(crate) fn slug(&mut self, text: &str) -> AFlexStr {
+        let base = a_flex_fmt!("{}{}", self.prefix, self.strategy.slug(text));
+        let mut slug = base.clone();
+        let mut count = 0;
+
+        while !self.seen.insert(slug.clone()) {
+            count += 1;
+            slug = a_flex_fmt!("{base}-{count}");
+        }
+
+        slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn github() {
+        let slug = |text| SlugStrategy::Github.slug(text);
+
+        assert_eq!(slug("Parse (u8)"), "parse-u8");
+        assert_eq!(slug("  A - B  "), "a---b");
+        assert_eq!(slug("snake_case & Ünïcode 🚀"), "snake_case--ünïcode-");
+    }
+
+    #[test]
+    fn gitlab() {
+        assert_eq!(SlugStrategy::Gitlab.slug("A - B"), "a-b");
+        assert_eq!(SlugStrategy::Gitlab.slug("Parse (u8)"), "parse-u8");
+    }
+
+    #[test]
+    fn kramdown() {
+        let slug = |text| SlugStrategy::Kramdown.slug(text);
+
+        assert_eq!(slug("1. Parse (u8)"), "parse-u8");
+        assert_eq!(slug("Ünïcode"), "ncode");
+        assert_eq!(slug("123"), KRAMDOWN_EMPTY_SLUG);
+    }
+
+    #[test]
+    fn slugger_duplicates() {
+        let mut slugger = Slugger::new(SlugStrategy::Github, "bench-");
+
+        assert_eq!(slugger.slug("Fibonacci"), "bench-fibonacci");
+        assert_eq!(slugger.slug("Fibonacci"), "bench-fibonacci-1");
+        assert_eq!(slugger.slug("Fibonacci"), "bench-fibonacci-2");
+        assert_eq!(slugger.slug("Parse"), "bench-parse");
+    }
+}
@@ -0,0 +1,153 @@
+use super::json::TableData;
+use crate::{CellData, ColumnInfo, Comparison, Error, Footnote, Formatter, Metadata, Toc};
+use flexstr::AFlexStr;
+use handlebars::Handlebars;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+// The name the user's template is registered under
+const TEMPLATE_NAME: &str = "tables";
+
+// *** Template Formatter ***
+
+/// This formatter renders the processed tables through a user supplied
+/// [Handlebars](https://handlebarsjs.com/) template, for small tweaks to the output without writing
+/// a new `Formatter`. The template is passed `top_comments` (header -> comment), `tables` and
+/// `metadata` (any provenance enabled in the footer options: `timestamp`, `git_commit`,
+/// `rustc_version` and `hostname`). Each
+/// table holds its name (`table`), `anchor`, `comment`, `section` (when split into sections),
+/// `columns`, `rows`, `summary` (when a summary row is enabled) and `footnotes` (each with its
+/// `marker` and `text`, when it has any). Each row holds its `name` and
+/// `cells`, one per column (`null` if blank), each with the same raw numbers as JSON output plus
+/// the values as other formats display them: `time`, `lower_bound`, `upper_bound`, `measurement`,
+/// `comparison` and `marker`. Values are not HTML escaped, so templates can output any format
+#[derive(Clone, Debug)]
+pub struct TemplateFormatter {
+    registry: Handlebars<'static>,
+    top_comments: IndexMap<AFlexStr, AFlexStr>,
+    section: Option<AFlexStr>,
+    table: Option<(TableData, Option<AFlexStr>)>,
+    tables: Vec<Value>,
+    metadata: Metadata,
+}
+
+impl TemplateFormatter {
+    /// Create a new `TemplateFormatter` from the text of a Handlebars `template`, failing if it
+    /// doesn't compile
+    pub fn new(template: &str) -> Result<Self, Error> {
+        let mut registry = Handlebars::new();
+        registry.register_escape_fn(handlebars::no_escape);
+        registry.register_template_string(TEMPLATE_NAME, template)?;
+
+        Ok(Self {
+            registry,
+            top_comments: IndexMap::new(),
+            section: None,
+            table: None,
+            tables: Vec::new(),
+            metadata: Default::default(),
+        })
+    }
+}
+
+impl Formatter for TemplateFormatter {
+    fn start(
+        &mut self,
+        _buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        _toc: &Toc,
+    ) {
+        self.top_comments = top_comments.clone();
+    }
+
+    fn end(&mut self, buffer: &mut String) {
+        let data = json!({
+            "top_comments": self.top_comments,
+            "tables": self.tables,
+            "metadata": self.metadata,
+        });
+
+        // Formatters can't fail, so a template that can't be rendered (ex: an unknown helper)
+        // outputs the error instead
+        match self.registry.render(TEMPLATE_NAME, &data) {
+            Ok(output) => buffer.push_str(&output),
+            Err(err) => buffer.push_str(&format!("Unable to render template: {err}\n")),
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn start_section(&mut self, _buffer: &mut String, name: &AFlexStr) {
+        self.section = Some(name.clone());
+    }
+
+    fn end_section(&mut self, _buffer: &mut String) {
+        self.section = None;
+    }
+
+    fn start_table(
+        &mut self,
+        _buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        let table_data = TableData::new(name, columns).with_display();
+        self.table = Some((table_data, comment.cloned()));
+    }
+
+    fn end_table(&mut self, _buffer: &mut String) {
+        if let Some((table_data, comment)) = self.table.take() {
+            let mut table = table_data.to_value();
+            if let Some(name) = table["table"].as_str() {
+                table["anchor"] = json!(self.anchor(name));
+            }
+            table["comment"] = json!(comment);
+            // Only set when output is split into sections (ex: workspace members)
+            if let Some(section) = &self.section {
+                table["section"] = json!(section);
+            }
+            self.tables.push(table);
+        }
+    }
+
+    fn start_row(&mut self, _buffer: &mut String, name: &AFlexStr, _max_width: usize) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.start_row(name);
+        }
+    }
+
+    fn end_row(&mut self, _buffer: &mut String) {}
+
+    fn used_column(&mut self, _buffer: &mut String, cell: &CellData, _max_width: usize) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.add_cell(Some(cell));
+        }
+    }
+
+    fn unused_column(&mut self, _buffer: &mut String, _max_width: usize) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.add_cell(None);
+        }
+    }
+
+    fn summary_row(
+        &mut self,
+        _buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.set_summary(name, comparisons);
+        }
+    }
+
+    fn footnotes(&mut self, _buffer: &mut String, footnotes: &[Footnote]) {
+        if let Some((table_data, _)) = &mut self.table {
+            table_data.set_footnotes(footnotes);
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{
+    display_width, CellData, CellRole, ChangeType, ColumnInfo, Comparison, EmbedOptions, Footnote,
+    Formatter, Metadata, Toc,
+};
+use flexstr::{AFlexStr, ToAFlexStr};
+use indexmap::IndexMap;
+
+// Width of the parens around the comparison + one space
+const USED_EXTRA_WIDTH: usize = " ()".len();
+
+const BOLD: &str = "\x1b[1m";
+const GREEN: &str = "\x1b[32m";
+const BOLD_GREEN: &str = "\x1b[1;32m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+// *** Term Options ***
+
+/// The characters the borders of each table are drawn with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoxStyle {
+    /// Unicode box-drawing characters
+    #[default]
+    Unicode,
+    /// Plain ASCII (`+`, `-` and `|`)
+    Ascii,
+}
+
+impl BoxStyle {
+    // The horizontal and vertical line chars
+    fn lines(self) -> (char, char) {
+        match self {
+            BoxStyle::Unicode => ('─', '│'),
+            BoxStyle::Ascii => ('-', '|'),
+        }
+    }
+
+    // The left, middle, and right junction chars of the top, middle, and bottom borders
+    fn junctions(self) -> [[char; 3]; 3] {
+        match self {
+            BoxStyle::Unicode => [['┌', '┬', '┐'], ['├', '┼', '┤'], ['└', '┴', '┘']],
+            BoxStyle::Ascii => [['+'; 3]; 3],
+        }
+    }
+
+    // The marker of the fastest or slowest cell of a row, if highlighted
+    fn role_marker(self, role: CellRole) -> &'static str {
+        match (self, role) {
+            (BoxStyle::Unicode, _) | (_, CellRole::Normal) => role.marker(),
+            (BoxStyle::Ascii, CellRole::Best) => "+ ",
+            (BoxStyle::Ascii, CellRole::Worst) => "- ",
+        }
+    }
+
+    // The symbol marking criterion's verdict on a change, if any
+    fn change_symbol(self, verdict: ChangeType) -> Option<char> {
+        match (self, verdict) {
+            (_, ChangeType::NoChange) => None,
+            (BoxStyle::Unicode, ChangeType::Improved) => Some('✓'),
+            (BoxStyle::Unicode, ChangeType::Regressed) => Some('✗'),
+            (BoxStyle::Ascii, ChangeType::Improved) => Some('*'),
+            (BoxStyle::Ascii, ChangeType::Regressed) => Some('!'),
+        }
+    }
+}
+
+// *** Term Formatter ***
+
+/// This formatter outputs plain text tables for viewing in a terminal, with the borders drawn via
+/// `style`. Faster cells are colored green and slower cells red via ANSI escape codes if `color`
+/// is set
+#[derive(Clone, Debug, Default)]
+pub struct TermFormatter {
+    style: BoxStyle,
+    color: bool,
+    // The inner width of each column of the current table
+    widths: Vec<usize>,
+    // The footnotes of the current table, written below its bottom border
+    footnotes: Vec<Footnote>,
+    // The footer options and provenance, written by `end`
+    metadata: Metadata,
+    // Only the tables are written, if set (see `EmbedOptions`)
+    embed: Option<EmbedOptions>,
+}
+
+impl TermFormatter {
+    /// Create a new `TermFormatter`
+    #[inline]
+    pub fn new(style: BoxStyle, color: bool) -> Self {
+        Self {
+            style,
+            color,
+            widths: Vec::new(),
+            footnotes: Vec::new(),
+            metadata: Default::default(),
+            embed: None,
+        }
+    }
+
+    // Same thresholds as the GFM formatter
+    fn compare_style(compare: &Comparison) -> &'static str {
+        if *compare >= 1.8 {
+            BOLD_GREEN
+        } else if *compare > 0.9 {
+            GREEN
+        } else if *compare < 0.9 {
+            RED
+        } else {
+            ""
+        }
+    }
+
+    fn write_styled(&self, buffer: &mut String, style: &str, s: &str) {
+        if self.color && !style.is_empty() {
+            buffer.push_str(style);
+            buffer.push_str(s);
+            buffer.push_str(RESET);
+        } else {
+            buffer.push_str(s);
+        }
+    }
+
+    fn write_border(&self, buffer: &mut String, row: usize) {
+        let (horizontal, _) = self.style.lines();
+        let [left, middle, right] = self.style.junctions()[row];
+
+        buffer.push(left);
+        for (idx, width) in self.widths.iter().enumerate() {
+            if idx > 0 {
+                buffer.push(middle);
+            }
+            // Includes the space on either side of the cell
+            for _ in 0..width + 2 {
+                buffer.push(horizontal);
+            }
+        }
+        buffer.push(right);
+        buffer.push('\n');
+    }
+
+    fn write_cell(&self, buffer: &mut String, style: &str, data: &str, width: usize) {
+        let (_, vertical) = self.style.lines();
+
+        buffer.push(vertical);
+        buffer.push(' ');
+        self.write_styled(buffer, style, data);
+
+        // Pad the rest of the column (inclusive to handle trailing space)
+        let remaining = width.saturating_sub(display_width(data));
+        for _ in 0..=remaining {
+            buffer.push(' ');
+        }
+    }
+}
+
+impl Formatter for TermFormatter {
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        _toc: &Toc,
+    ) {
+        if self.embed.is_some() {
+            return;
+        }
+
+        // There is nothing to link to, so no ToC - just the comment sections and comments
+        for (header, comment) in top_comments {
+            self.write_styled(buffer, BOLD, header);
+            buffer.push_str("\n\n");
+            buffer.push_str(comment);
+            buffer.push('\n');
+        }
+    }
+
+    // There is no credit by default (it would only be noise in a terminal), only any custom text
+    // and provenance
+    fn end(&mut self, buffer: &mut String) {
+        if self.embed.is_some() || !self.metadata.footer {
+            return;
+        }
+
+        if let Some(text) = &self.metadata.footer_text {
+            buffer.push_str(text);
+            buffer.push('\n');
+        }
+        if let Some(provenance) = self.metadata.provenance_str() {
+            buffer.push_str(&provenance);
+            buffer.push('\n');
+        }
+    }
+
+    fn metadata(&mut self, metadata: &Metadata) {
+        self.metadata = metadata.clone();
+    }
+
+    fn embed(&mut self, options: &EmbedOptions) {
+        self.embed = Some(*options);
+    }
+
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        if EmbedOptions::headings(self.embed) {
+            self.write_styled(buffer, BOLD, &format!("== {name} =="));
+            buffer.push_str("\n\n");
+        }
+    }
+
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        if EmbedOptions::headings(self.embed) {
+            self.write_styled(buffer, BOLD, name);
+            buffer.push_str("\n\n");
+        }
+
+        if let Some(comments) = comment {
+            buffer.push_str(comments);
+            buffer.push('\n');
+        }
+
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        self.widths = vec![columns[0].max_width];
+        self.widths.extend(
+            columns[1..]
+                .iter()
+                .map(|column| column.max_width + USED_EXTRA_WIDTH),
+        );
+
+        self.write_border(buffer, 0);
+        self.write_cell(buffer, "", "", self.widths[0]);
+        for (column, width) in columns[1..].iter().zip(&self.widths[1..]) {
+            self.write_cell(buffer, BOLD, &column.name, *width);
+        }
+        self.end_row(buffer);
+        self.write_border(buffer, 1);
+    }
+
+    fn end_table(&mut self, buffer: &mut String) {
+        self.write_border(buffer, 2);
+
+        for footnote in self.footnotes.drain(..) {
+            buffer.push_str(&footnote.marker);
+            buffer.push(' ');
+            buffer.push_str(&footnote.text);
+            buffer.push('\n');
+        }
+        buffer.push('\n');
+    }
+
+    fn footnotes(&mut self, _buffer: &mut String, footnotes: &[Footnote]) {
+        self.footnotes = footnotes.to_vec();
+    }
+
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, max_width: usize) {
+        self.write_cell(buffer, "", name, max_width);
+    }
+
+    fn end_row(&mut self, buffer: &mut String) {
+        let (_, vertical) = self.style.lines();
+        buffer.push(vertical);
+        buffer.push('\n');
+    }
+
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, max_width: usize) {
+        // Noise isn't emphasized as faster or slower
+        let style = if cell.is_noise() {
+            ""
+        } else {
+            Self::compare_style(&cell.compare)
+        };
+
+        // Only speedup tiers (if any) mark the comparison
+        let mut data = match cell.tier.or("") {
+            "" => format!(
+                "{}{} ({})",
+                self.style.role_marker(cell.role),
+                cell.measurement(),
+                cell.compare_str()
+            ),
+            marker => format!(
+                "{}{} ({marker} {})",
+                self.style.role_marker(cell.role),
+                cell.measurement(),
+                cell.compare_str()
+            ),
+        };
+
+        if let Some(change) = cell.change {
+            data.push(' ');
+            if let Some(symbol) = self.style.change_symbol(change.verdict) {
+                data.push(symbol);
+                data.push(' ');
+            }
+            data.push_str(&change.to_a_flex_str());
+        }
+
+        if !cell.tags.is_empty() {
+            let tags: Vec<_> = cell.tags.iter().map(AFlexStr::as_str).collect();
+            data.push_str(&format!(" [{}]", tags.join(", ")));
+        }
+
+        self.write_cell(buffer, style, &data, max_width + USED_EXTRA_WIDTH);
+    }
+
+    fn unused_column(&mut self, buffer: &mut String, max_width: usize) {
+        self.write_cell(buffer, "", "N/A", max_width + USED_EXTRA_WIDTH);
+    }
+
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        columns: &[ColumnInfo],
+    ) {
+        self.write_border(buffer, 1);
+        // Safety: Any slicing up to index 1 is always safe - guaranteed to have at least one column
+        self.write_cell(buffer, BOLD, name, columns[0].max_width);
+
+        for (compare, column) in comparisons.iter().zip(&columns[1..]) {
+            match compare {
+                Some(compare) => self.write_cell(
+                    buffer,
+                    Self::compare_style(compare),
+                    &compare.to_a_flex_str(),
+                    column.max_width + USED_EXTRA_WIDTH,
+                ),
+                None => self.unused_column(buffer, column.max_width),
+            }
+        }
+
+        self.end_row(buffer);
+    }
+}
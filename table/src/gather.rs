@@ -0,0 +1,187 @@
+// Collects what the library is given but leaves to its caller to collect, since that means running
+// commands (ex: `git`) or reading system files
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+
+use criterion_table::footer::{FooterOptions, Provenance};
+use flexstr::{a_flex_fmt, AFlexStr, IntoFlex, ToFlex};
+use indexmap::IndexMap;
+#[cfg(feature = "sysinfo")]
+use {criterion_table::Measurement, flexstr::ToAFlexStr, sysinfo::CpuRefreshKind};
+
+// The provenance enabled in `options`. Any that can't be determined (ex: outside a git
+// repository) is left blank
+pub fn provenance(options: &FooterOptions) -> Provenance {
+    Provenance {
+        git_commit: options
+            .git_commit
+            .then(|| command_output("git", &["rev-parse", "--short", "HEAD"]))
+            .flatten(),
+        rustc_version: options.rustc_version.then(rustc_version).flatten(),
+        hostname: options.hostname.then(hostname).flatten(),
+    }
+}
+
+// The benchmarking environment (label -> value). Anything that can't be determined (ex: the
+// criterion version outside a cargo project) is left out
+pub fn environment() -> IndexMap<AFlexStr, AFlexStr> {
+    let system = System::gather();
+    let gathered = [
+        ("CPU", system.cpu.or_else(cpu_model)),
+        (
+            "Cores",
+            thread::available_parallelism()
+                .ok()
+                .map(|cores| a_flex_fmt!("{cores}")),
+        ),
+        ("OS", Some(system.os.unwrap_or_else(os))),
+        ("Memory", system.memory),
+        ("rustc", rustc_version()),
+        ("criterion", criterion_version()),
+    ];
+
+    gathered
+        .into_iter()
+        .filter_map(|(label, value)| Some((label.to_flex(), value?)))
+        .collect()
+}
+
+// What is known of the system via `sysinfo`, used before what is read otherwise
+#[derive(Default)]
+struct System {
+    cpu: Option<AFlexStr>,
+    os: Option<AFlexStr>,
+    memory: Option<AFlexStr>,
+}
+
+impl System {
+    #[cfg(feature = "sysinfo")]
+    fn gather() -> Self {
+        let mut system = sysinfo::System::new();
+        system.refresh_cpu_list(CpuRefreshKind::nothing());
+        system.refresh_memory();
+
+        let cpu = system.cpus().first().map(|cpu| cpu.brand().trim());
+        Self {
+            cpu: cpu.filter(|cpu| !cpu.is_empty()).map(ToFlex::to_flex),
+            os: sysinfo::System::long_os_version().map(|name| a_flex_fmt!("{name} ({})", family())),
+            memory: Some(Measurement::new(system.total_memory() as f64, "B").to_a_flex_str()),
+        }
+    }
+
+    #[cfg(not(feature = "sysinfo"))]
+    #[inline]
+    fn gather() -> Self {
+        Self::default()
+    }
+}
+
+fn cpu_model() -> Option<AFlexStr> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        let model = cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == "model name").then(|| value.trim().to_flex())
+        });
+        if model.is_some() {
+            return model;
+        }
+    }
+
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"]).or_else(|| {
+        env::var("PROCESSOR_IDENTIFIER")
+            .ok()
+            .map(|cpu| cpu.to_flex())
+    })
+}
+
+// The family of the OS and the architecture (ex: `linux x86_64`)
+fn family() -> AFlexStr {
+    a_flex_fmt!("{} {}", env::consts::OS, env::consts::ARCH)
+}
+
+// The name of the OS (ex: `Ubuntu 22.04.4 LTS`, from `/etc/os-release` on Linux), its family
+// and the architecture (ex: `linux x86_64`)
+fn os() -> AFlexStr {
+    let name = fs::read_to_string("/etc/os-release")
+        .ok()
+        .and_then(|release| {
+            release.lines().find_map(|line| {
+                let name = line.strip_prefix("PRETTY_NAME=")?.trim_matches('"');
+                (!name.is_empty()).then(|| name.to_string())
+            })
+        });
+    let name = name.or_else(|| {
+        let name = command_output("sw_vers", &["-productName"])?;
+        let version = command_output("sw_vers", &["-productVersion"])?;
+        Some(format!("{name} {version}"))
+    });
+
+    match name {
+        Some(name) => a_flex_fmt!("{name} ({})", family()),
+        None => family(),
+    }
+}
+
+// The version of criterion in the `Cargo.lock` of the current directory (or the nearest one above)
+fn criterion_version() -> Option<AFlexStr> {
+    let dir = env::current_dir().ok()?;
+    let lock = dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file())?;
+    lock_version(&lock, "criterion")
+}
+
+fn lock_version(lock: &Path, package: &str) -> Option<AFlexStr> {
+    let lock: toml::Value = toml::from_str(&fs::read_to_string(lock).ok()?).ok()?;
+
+    let versions: Vec<_> = lock
+        .get("package")?
+        .as_array()?
+        .iter()
+        .filter(|pkg| pkg.get("name").and_then(toml::Value::as_str) == Some(package))
+        .filter_map(|pkg| pkg.get("version")?.as_str())
+        .collect();
+    // More than one version can be locked (ex: a dependency with its own)
+    (!versions.is_empty()).then(|| versions.join(", ").to_flex())
+}
+
+// The trimmed stdout of the command, if it succeeded and printed anything
+fn command_output(program: &str, args: &[&str]) -> Option<AFlexStr> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.trim();
+    (!stdout.is_empty()).then(|| stdout.to_flex())
+}
+
+// The version of rustc without its name (ex: `1.80.0 (051478957 2024-07-21)`)
+fn rustc_version() -> Option<AFlexStr> {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = command_output(&rustc, &["--version"])?;
+    Some(match version.strip_prefix("rustc ") {
+        Some(version) => version.to_flex(),
+        None => version,
+    })
+}
+
+fn hostname() -> Option<AFlexStr> {
+    // `HOSTNAME` is usually a shell variable (not exported) and `COMPUTERNAME` is Windows only
+    env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .map(IntoFlex::into_flex)
+        .or_else(|| {
+            let hostname = fs::read_to_string("/etc/hostname").ok()?;
+            let hostname = hostname.trim();
+            (!hostname.is_empty()).then(|| hostname.to_flex())
+        })
+        .or_else(|| command_output("hostname", &[]))
+}
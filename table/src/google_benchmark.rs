@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::io::Read;
+
+use flexstr::AFlexStr;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{BenchmarkComplete, Error, RawCriterionData};
+
+// *** Google Benchmark Data Structs ***
+
+// NOTE: Only the fields we need from Google Benchmark's JSON output (via
+// `--benchmark_format=json` or `--benchmark_out`)
+
+#[derive(Deserialize)]
+struct Output {
+    benchmarks: Vec<Benchmark>,
+}
+
+#[derive(Deserialize)]
+struct Benchmark {
+    name: AFlexStr,
+    // Missing in output of versions before 1.5
+    run_name: Option<AFlexStr>,
+    #[serde(default)]
+    run_type: RunType,
+    aggregate_name: Option<String>,
+    #[serde(default)]
+    error_occurred: bool,
+    real_time: f64,
+    time_unit: AFlexStr,
+}
+
+#[derive(Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RunType {
+    #[default]
+    Iteration,
+    Aggregate,
+}
+
+impl Benchmark {
+    fn real_time_ns(&self) -> Result<f64, Error> {
+        let multiplier = match self.time_unit.as_str() {
+            "ns" => 1.0,
+            "us" => 1_000.0,
+            "ms" => 1_000_000.0,
+            "s" => 1_000_000_000.0,
+            unit => {
+                return Err(Error::UnknownUnit {
+                    unit: unit.to_string(),
+                })
+            }
+        };
+        Ok(self.real_time * multiplier)
+    }
+}
+
+// The repetitions and aggregates of one benchmark
+#[derive(Default)]
+struct Runs {
+    times: Vec<f64>,
+    aggregates: HashMap<String, f64>,
+}
+
+impl Runs {
+    fn into_benchmark(self, id: AFlexStr) -> BenchmarkComplete {
+        let mut sorted = self.times.clone();
+        sorted.sort_by(f64::total_cmp);
+        let len = sorted.len() as f64;
+
+        // Prefer the aggregates Google Benchmark computed itself (from `--benchmark_repetitions`)
+        let mean = self
+            .aggregates
+            .get("mean")
+            .copied()
+            .unwrap_or_else(|| sorted.iter().sum::<f64>() / len);
+        let median = self
+            .aggregates
+            .get("median")
+            .copied()
+            .unwrap_or_else(|| match sorted.len() {
+                0 => mean,
+                len if len % 2 == 0 => (sorted[len / 2 - 1] + sorted[len / 2]) / 2.0,
+                len => sorted[len / 2],
+            });
+        let stddev = self.aggregates.get("stddev").copied().unwrap_or_else(|| {
+            if sorted.len() > 1 {
+                let variance =
+                    sorted.iter().map(|time| (time - mean).powi(2)).sum::<f64>() / (len - 1.0);
+                variance.sqrt()
+            } else {
+                0.0
+            }
+        });
+
+        BenchmarkComplete::from_samples(id, mean, median, stddev, self.times)
+    }
+}
+
+// *** Functions ***
+
+/// Load raw benchmark data from Google Benchmark's JSON output. The id of each benchmark is its
+/// run name (ex: `BM_Fib/10`, or `Fib/cpp/10` via `->Name("Fib/cpp")`), split via the id scheme
+/// like any criterion id. Repetitions of a benchmark are combined (using its `mean`, `median` and
+/// `stddev` aggregates if present) and benchmarks that failed are skipped
+pub fn from_reader(r: impl Read) -> Result<Vec<RawCriterionData>, Error> {
+    let output: Output = serde_json::from_reader(r)?;
+    let mut all_runs: IndexMap<AFlexStr, Runs> = IndexMap::new();
+
+    for benchmark in output.benchmarks {
+        if benchmark.error_occurred {
+            continue;
+        }
+
+        let time = benchmark.real_time_ns()?;
+        let id = benchmark
+            .run_name
+            .clone()
+            .unwrap_or_else(|| benchmark.name.clone());
+        let runs = all_runs.entry(id).or_default();
+
+        match (benchmark.run_type, benchmark.aggregate_name) {
+            (RunType::Aggregate, Some(aggregate)) => {
+                runs.aggregates.insert(aggregate, time);
+            }
+            // Unnamed aggregates (ex: from a custom statistic in older versions) aren't times
+            (RunType::Aggregate, None) => {}
+            (RunType::Iteration, _) => runs.times.push(time),
+        }
+    }
+
+    Ok(all_runs
+        .into_iter()
+        // A benchmark with only custom aggregates has no time to tabulate
+        .filter(|(_, runs)| !runs.times.is_empty() || runs.aggregates.contains_key("mean"))
+        .map(|(id, runs)| RawCriterionData::Benchmark(Box::new(runs.into_benchmark(id))))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(benchmarks: &str) -> Vec<RawCriterionData> {
+        from_reader(format!(r#"{{"context": {{}}, "benchmarks": [{benchmarks}]}}"#).as_bytes())
+            .expect("valid output")
+    }
+
+    #[test]
+    fn repetitions_without_aggregates() {
+        let raw_data = read(
+            r#"{"name": "BM_Fib/10", "run_type": "iteration", "real_time": 1.0, "time_unit": "us"},
+            {"name": "BM_Fib/10", "run_type": "iteration", "real_time": 3.0, "time_unit": "us"},
+            {"name": "BM_Fib/20", "real_time": 2.0, "time_unit": "ms"}"#,
+        );
+        assert_eq!(raw_data.len(), 2);
+
+        let benchmark = raw_data[0].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.id, "BM_Fib/10");
+        assert_eq!(benchmark.mean.estimate, 2_000.0);
+        assert_eq!(benchmark.median.estimate, 2_000.0);
+        assert_eq!(benchmark.measured_values, [1_000.0, 3_000.0]);
+
+        let benchmark = raw_data[1].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.mean.estimate, 2_000_000.0);
+    }
+
+    #[test]
+    fn aggregates_and_errors() {
+        let raw_data = read(
+            r#"{"name": "BM_Fib/10", "run_name": "Fib/cpp/10", "run_type": "iteration",
+                "real_time": 10.0, "time_unit": "ns"},
+            {"name": "BM_Fib/10_mean", "run_name": "Fib/cpp/10", "run_type": "aggregate",
+                "aggregate_name": "mean", "real_time": 12.0, "time_unit": "ns"},
+            {"name": "BM_Fib/10_median", "run_name": "Fib/cpp/10", "run_type": "aggregate",
+                "aggregate_name": "median", "real_time": 11.0, "time_unit": "ns"},
+            {"name": "BM_Fail", "error_occurred": true, "real_time": 0.0, "time_unit": "ns"}"#,
+        );
+        assert_eq!(raw_data.len(), 1);
+
+        let benchmark = raw_data[0].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.id, "Fib/cpp/10");
+        assert_eq!(benchmark.mean.estimate, 12.0);
+        assert_eq!(benchmark.median.estimate, 11.0);
+    }
+
+    #[test]
+    fn unknown_unit() {
+        let output = r#"{"benchmarks": [{"name": "BM_Fib", "real_time": 1.0, "time_unit": "ks"}]}"#;
+        assert!(matches!(
+            from_reader(output.as_bytes()),
+            Err(Error::UnknownUnit { unit }) if unit == "ks"
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flexstr::AFlexStr;
+use serde::{Deserialize, Serialize};
+
+use crate::{CriterionTableData, Error};
+
+// *** History ***
+
+/// A run recorded in the history store
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// The label of the run (ex: a commit hash)
+    pub label: AFlexStr,
+    /// When the run was recorded, in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// The table data of the run (before any config is applied)
+    pub data: CriterionTableData,
+}
+
+/// Append a run with the given `label` (ex: a commit hash) to the history store at `path` (a
+/// JSON Lines file, created if needed)
+pub fn append(
+    path: impl AsRef<Path>,
+    label: impl Into<AFlexStr>,
+    data: CriterionTableData,
+) -> Result<(), Error> {
+    let entry = HistoryEntry {
+        label: label.into(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|dur| dur.as_secs())
+            .unwrap_or_default(),
+        data,
+    };
+
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Load every run recorded in the history store at `path`, oldest first. A missing store has no
+/// runs
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<HistoryEntry>, Error> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line).map_err(|source| Error::HistoryLine {
+                line: idx + 1,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Build table data showing the trend of each benchmark over the `last` runs of `entries`, with
+/// a column per run (see `CriterionTableData::trend`)
+pub fn trend(entries: &[HistoryEntry], last: usize) -> Result<CriterionTableData, Error> {
+    let start = entries.len().saturating_sub(last);
+    let runs: Vec<_> = entries[start..]
+        .iter()
+        .map(|entry| (entry.label.clone(), &entry.data))
+        .collect();
+    CriterionTableData::trend(&runs)
+}
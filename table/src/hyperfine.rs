@@ -0,0 +1,181 @@
+use std::io::Read;
+
+use flexstr::{AFlexStr, ToFlex};
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{BenchmarkComplete, Error, IdParser, RawCriterionData};
+
+// Hyperfine records times in seconds, which are converted to nanoseconds so small times scale
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+
+// *** Hyperfine Data Structs ***
+
+// NOTE: Only the fields we need from hyperfine's `--export-json` output
+
+#[derive(Deserialize)]
+struct Export {
+    results: Vec<CommandResult>,
+}
+
+#[derive(Deserialize)]
+struct CommandResult {
+    command: AFlexStr,
+    mean: f64,
+    stddev: Option<f64>,
+    median: f64,
+    #[serde(default)]
+    times: Vec<f64>,
+}
+
+impl CommandResult {
+    fn into_benchmark(self) -> BenchmarkComplete {
+        let ns = |secs: f64| secs * NANOS_PER_SECOND;
+
+        BenchmarkComplete::from_samples(
+            self.command,
+            ns(self.mean),
+            ns(self.median),
+            ns(self.stddev.unwrap_or_default()),
+            self.times.into_iter().map(ns).collect(),
+        )
+    }
+}
+
+// *** Hyperfine Options ***
+
+/// Options used to map hyperfine commands to tables (ex: `[hyperfine]` in the config)
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct HyperfineOptions {
+    /// The name of the table commands are put in, unless captured by `command_regex`
+    pub table: AFlexStr,
+    /// A regex with a `column` and (optionally) `row` and `table` named capture groups, matched
+    /// against each command (ex: `^(?P<column>\S+) .*?(?P<row>\d+)$`). Without it, each command is
+    /// a column of a single row
+    #[serde(deserialize_with = "deserialize_command_regex")]
+    pub command_regex: Option<Regex>,
+}
+
+impl Default for HyperfineOptions {
+    fn default() -> Self {
+        Self {
+            table: "Commands".into(),
+            command_regex: None,
+        }
+    }
+}
+
+impl IdParser for HyperfineOptions {
+    fn parse_id(&self, id: &str) -> Result<(AFlexStr, AFlexStr, AFlexStr), Error> {
+        let regex = match &self.command_regex {
+            Some(regex) => regex,
+            None => return Ok((self.table.clone(), id.to_flex(), Default::default())),
+        };
+
+        let captures = regex.captures(id).ok_or_else(|| Error::MalformedId {
+            id: id.to_string(),
+            reason: "Command doesn't match the command regex",
+        })?;
+        let capture = |name| captures.name(name).map(|m| m.as_str().to_flex());
+
+        match capture("column") {
+            Some(column) => Ok((
+                capture("table").unwrap_or_else(|| self.table.clone()),
+                column,
+                capture("row").unwrap_or_default(),
+            )),
+            None => Err(Error::MalformedId {
+                id: id.to_string(),
+                reason: "Command regex didn't capture a column",
+            }),
+        }
+    }
+}
+
+// The regex must have a `column` capture group to be of any use
+fn deserialize_command_regex<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Regex>, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    let regex = Regex::new(&pattern).map_err(de::Error::custom)?;
+
+    if regex.capture_names().flatten().any(|name| name == "column") {
+        Ok(Some(regex))
+    } else {
+        Err(de::Error::custom(
+            "the command regex must have a `column` named capture group",
+        ))
+    }
+}
+
+// *** Functions ***
+
+/// Load raw benchmark data from hyperfine's JSON output (via `--export-json`). The id of each
+/// benchmark is its command, which should be split via `HyperfineOptions` as the id parser
+pub fn from_reader(r: impl Read) -> Result<Vec<RawCriterionData>, Error> {
+    let export: Export = serde_json::from_reader(r)?;
+
+    Ok(export
+        .results
+        .into_iter()
+        .map(|result| RawCriterionData::Benchmark(Box::new(result.into_benchmark())))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"{"results": [
+        {"command": "fib-rs 10", "mean": 0.002, "stddev": 0.0001, "median": 0.0019,
+            "times": [0.0018, 0.0019, 0.0023]},
+        {"command": "fib-c 10", "mean": 0.001, "stddev": null, "median": 0.001}
+    ]}"#;
+
+    #[test]
+    fn from_reader_in_nanos() {
+        let raw_data = from_reader(EXPORT.as_bytes()).expect("valid export");
+        assert_eq!(raw_data.len(), 2);
+
+        let benchmark = raw_data[0].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.id, "fib-rs 10");
+        assert_eq!(benchmark.mean.estimate, 2_000_000.0);
+        assert_eq!(benchmark.median.estimate, 1_900_000.0);
+        assert_eq!(benchmark.mean.lower_bound, 1_900_000.0);
+        assert_eq!(benchmark.measured_values.len(), 3);
+
+        // Without a deviation, the bounds are the estimate
+        let benchmark = raw_data[1].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.mean.lower_bound, benchmark.mean.estimate);
+        assert!(benchmark.measured_values.is_empty());
+    }
+
+    #[test]
+    fn parse_id() {
+        let options = HyperfineOptions::default();
+        let (table, column, row) = options.parse_id("fib-rs 10").expect("an id");
+        assert_eq!(
+            (table.as_str(), column.as_str(), row.as_str()),
+            ("Commands", "fib-rs 10", "")
+        );
+
+        let options: HyperfineOptions =
+            toml::from_str(r#"command_regex = '^(?P<column>\S+) (?P<row>\d+)$'"#).expect("options");
+        let (table, column, row) = options.parse_id("fib-rs 10").expect("an id");
+        assert_eq!(
+            (table.as_str(), column.as_str(), row.as_str()),
+            ("Commands", "fib-rs", "10")
+        );
+        assert!(matches!(
+            options.parse_id("fib-rs"),
+            Err(Error::MalformedId { .. })
+        ));
+    }
+
+    #[test]
+    fn command_regex_needs_column() {
+        let options = toml::from_str::<HyperfineOptions>(r#"command_regex = '^(?P<row>\d+)$'"#);
+        assert!(options.is_err());
+    }
+}
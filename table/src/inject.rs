@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+
+use crate::Error;
+
+/// The marker line before the generated section of a document
+pub const START_MARKER: &str = "<!-- criterion-table start -->";
+/// The marker line after the generated section of a document
+pub const END_MARKER: &str = "<!-- criterion-table end -->";
+
+// The extension of the temporary file written next to the target before replacing it
+const TEMP_EXTENSION: &str = "criterion-table.tmp";
+
+/// Replace everything between the `START_MARKER` and `END_MARKER` comments of `document` (ex: a
+/// README) with `tables`, keeping the markers and everything outside of them as is
+pub fn inject(document: &str, tables: &str) -> Result<String, Error> {
+    let start = document
+        .find(START_MARKER)
+        .ok_or(Error::MissingStartMarker)?
+        + START_MARKER.len();
+    let end = document[start..]
+        .find(END_MARKER)
+        .ok_or(Error::MissingEndMarker)?
+        + start;
+
+    let mut buffer = String::with_capacity(document.len() + tables.len());
+    buffer.push_str(&document[..start]);
+    buffer.push_str("\n\n");
+    buffer.push_str(tables.trim());
+    buffer.push_str("\n\n");
+    buffer.push_str(&document[end..]);
+    Ok(buffer)
+}
+
+/// Replace the marked section of the file at `path` with `tables` (see `inject`). The result is
+/// written to a temporary file next to it first and then renamed over it, so the file is never
+/// left partially written
+pub fn inject_file(path: impl AsRef<Path>, tables: &str) -> Result<(), Error> {
+    let path = path.as_ref();
+    let file_error = |err: Error| Error::File {
+        path: path.to_path_buf(),
+        source: Box::new(err),
+    };
+
+    let document = fs::read_to_string(path).map_err(|err| file_error(err.into()))?;
+    let document = inject(&document, tables).map_err(file_error)?;
+
+    let temp_path = path.with_extension(TEMP_EXTENSION);
+    fs::write(&temp_path, document).map_err(|err| file_error(err.into()))?;
+    fs::rename(&temp_path, path).map_err(|err| {
+        // Don't leave the temporary file behind
+        let _ = fs::remove_file(&temp_path);
+        file_error(err.into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(section: &str) -> String {
+        format!("# Title\n\nIntro\n\n{START_MARKER}{section}{END_MARKER}\n\nOutro\n")
+    }
+
+    #[test]
+    fn round_trip() {
+        let injected = inject(&document("\nold tables\n"), "  new tables\n").expect("markers");
+        assert_eq!(injected, document("\n\nnew tables\n\n"));
+
+        // Injecting again replaces the section the last time injected
+        let reinjected = inject(&injected, "newer tables").expect("markers");
+        assert_eq!(reinjected, document("\n\nnewer tables\n\n"));
+        assert_eq!(
+            inject(&reinjected, "newer tables").expect("markers"),
+            reinjected
+        );
+    }
+
+    #[test]
+    fn missing_markers() {
+        assert!(inject("no markers", "tables").is_err());
+        assert!(inject(&format!("{END_MARKER} {START_MARKER}"), "tables").is_err());
+    }
+
+    #[test]
+    fn inject_file_in_place() {
+        let path = std::env::temp_dir().join(format!("inject-{}.md", std::process::id()));
+        fs::write(&path, document("")).expect("written");
+
+        inject_file(&path, "tables").expect("injected");
+        let injected = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(injected.expect("read"), document("\n\ntables\n\n"));
+        assert!(!path.with_extension(TEMP_EXTENSION).exists());
+    }
+}
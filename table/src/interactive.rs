@@ -0,0 +1,447 @@
+use std::cmp::Ordering;
+use std::io;
+
+use flexstr::{a_flex_fmt, AFlexStr, ToAFlexStr};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Cell, Row as TableRow, Table as TableWidget};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::{display_width, natural_cmp, Column, CriterionTableData, Row, Table};
+
+// Lines that aren't rows: the title, a blank line, the header, a blank line and the help (or
+// filter) line
+const CHROME_HEIGHT: u16 = 5;
+const COLUMN_GAP: u16 = 2;
+const MISSING_CELL: &str = "-";
+
+const HELP: &str = "q: quit  tab: next table  arrows: scroll/select column  s: sort  \
+                    /: filter  v: view";
+
+// *** View ***
+
+// What each cell of the viewer displays
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum View {
+    /// The time of the benchmark
+    #[default]
+    Time,
+    /// The throughput of the benchmark, if it has one
+    Throughput,
+    /// The ratio of the baseline (the first column of the row) to the benchmark
+    Ratio,
+}
+
+impl View {
+    fn next(self) -> Self {
+        match self {
+            View::Time => View::Throughput,
+            View::Throughput => View::Ratio,
+            View::Ratio => View::Time,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            View::Time => "time",
+            View::Throughput => "throughput",
+            View::Ratio => "ratio",
+        }
+    }
+
+    // The value of `col` displayed in this view, if it has one
+    fn value(self, col: &Column) -> Option<AFlexStr> {
+        match self {
+            View::Time => Some(col.time().to_a_flex_str()),
+            View::Throughput => col
+                .throughput()
+                .map(|throughput| throughput.to_a_flex_str()),
+            View::Ratio => Some(a_flex_fmt!("{:.2}x", col.comparison().ratio())),
+        }
+    }
+
+    // The value `col` is sorted by in this view, if it has one
+    fn sort_key(self, col: &Column) -> Option<f64> {
+        match self {
+            View::Time => Some(col.time().base_value()),
+            View::Throughput => col.throughput().map(|throughput| throughput.per_second),
+            View::Ratio => Some(col.comparison().ratio()),
+        }
+    }
+}
+
+// *** Keys ***
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Tab,
+    BackTab,
+    Enter,
+    Esc,
+    Backspace,
+    Interrupt,
+    Char(char),
+    Other,
+}
+
+impl From<KeyEvent> for Key {
+    fn from(key: KeyEvent) -> Self {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Key::Interrupt,
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Left => Key::Left,
+            KeyCode::Right => Key::Right,
+            KeyCode::PageUp => Key::PageUp,
+            KeyCode::PageDown => Key::PageDown,
+            KeyCode::Home => Key::Home,
+            KeyCode::End => Key::End,
+            KeyCode::Tab => Key::Tab,
+            KeyCode::BackTab => Key::BackTab,
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Esc => Key::Esc,
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Char(ch) => Key::Char(ch),
+            _ => Key::Other,
+        }
+    }
+}
+
+// *** Viewer ***
+
+// The state of the viewer: which table is shown and how
+struct Viewer<'a> {
+    tables: Vec<&'a Table>,
+    table_idx: usize,
+    // The selected column: 0 is the row names, followed by each column of the table
+    column: usize,
+    // The first data column shown, when they don't all fit the width of the terminal
+    column_offset: usize,
+    // The column the rows are sorted by and whether it is descending
+    sort: Option<(usize, bool)>,
+    // Only rows whose name contains this (ignoring case) are shown
+    filter: String,
+    editing_filter: bool,
+    view: View,
+    scroll: usize,
+}
+
+impl<'a> Viewer<'a> {
+    fn new(data: &'a CriterionTableData) -> Self {
+        Self {
+            tables: data.tables().collect(),
+            table_idx: 0,
+            column: 0,
+            column_offset: 0,
+            sort: None,
+            filter: String::new(),
+            editing_filter: false,
+            view: View::default(),
+            scroll: 0,
+        }
+    }
+
+    #[inline]
+    fn table(&self) -> &'a Table {
+        self.tables[self.table_idx]
+    }
+
+    fn column_names(&self) -> Vec<&'a AFlexStr> {
+        self.table().column_names().collect()
+    }
+
+    // The rows shown: filtered, then sorted (rows without a value in the sort column are last)
+    fn rows(&self) -> Vec<&'a Row> {
+        let filter = self.filter.to_lowercase();
+        let mut rows: Vec<_> = self
+            .table()
+            .rows()
+            .filter(|row| row.name().to_lowercase().contains(&filter))
+            .collect();
+
+        if let Some((column, descending)) = self.sort {
+            let directed = |ordering: Ordering| {
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            };
+
+            if column == 0 {
+                rows.sort_by(|row1, row2| directed(natural_cmp(row1.name(), row2.name())));
+            } else {
+                let name = self.column_names()[column - 1];
+                let key = |row: &Row| row.cell(name).and_then(|col| self.view.sort_key(col));
+
+                rows.sort_by(|row1, row2| match (key(row1), key(row2)) {
+                    (Some(key1), Some(key2)) => directed(key1.total_cmp(&key2)),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                });
+            }
+        }
+
+        rows
+    }
+
+    fn select_table(&mut self, idx: usize) {
+        self.table_idx = idx;
+        self.column = 0;
+        self.column_offset = 0;
+        self.sort = None;
+        self.scroll = 0;
+    }
+
+    // Cycles the sort of the selected column: ascending, descending, then unsorted
+    fn toggle_sort(&mut self) {
+        self.sort = match self.sort {
+            Some((column, false)) if column == self.column => Some((column, true)),
+            Some((column, true)) if column == self.column => None,
+            _ => Some((self.column, false)),
+        };
+    }
+
+    // Handles a key press, with `page` rows shown at a time. Returns false to quit
+    fn handle(&mut self, key: Key, page: usize) -> bool {
+        if self.editing_filter {
+            match key {
+                Key::Char(ch) => self.filter.push(ch),
+                Key::Backspace => {
+                    self.filter.pop();
+                }
+                Key::Enter => self.editing_filter = false,
+                Key::Esc => {
+                    self.filter.clear();
+                    self.editing_filter = false;
+                }
+                Key::Interrupt => return false,
+                _ => {}
+            }
+            self.scroll = 0;
+            return true;
+        }
+
+        let tables = self.tables.len();
+        let columns = self.column_names().len();
+
+        match key {
+            Key::Char('q') | Key::Esc | Key::Interrupt => return false,
+            Key::Tab | Key::Char('n') => self.select_table((self.table_idx + 1) % tables),
+            Key::BackTab | Key::Char('p') => {
+                self.select_table((self.table_idx + tables - 1) % tables)
+            }
+            Key::Up | Key::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            Key::Down | Key::Char('j') => self.scroll += 1,
+            Key::PageUp => self.scroll = self.scroll.saturating_sub(page),
+            Key::PageDown | Key::Char(' ') => self.scroll += page,
+            Key::Home | Key::Char('g') => self.scroll = 0,
+            // Clamped to the last page before drawing
+            Key::End | Key::Char('G') => self.scroll = usize::MAX,
+            Key::Left | Key::Char('h') => self.column = self.column.saturating_sub(1),
+            Key::Right | Key::Char('l') => self.column = (self.column + 1).min(columns),
+            Key::Char('s') => self.toggle_sort(),
+            Key::Char('/') => self.editing_filter = true,
+            Key::Char('v') => self.view = self.view.next(),
+            _ => {}
+        }
+
+        true
+    }
+
+    // Shows as many data columns as fit in `width` (the row names are always shown), scrolling
+    // horizontally so the selected column is one of them. Returns the end of the shown columns
+    fn fit_columns(&mut self, widths: &[usize], width: usize) -> usize {
+        let gap = COLUMN_GAP as usize;
+        let shown_width = |start: usize, end: usize| {
+            widths[0] + widths[start..end].iter().map(|w| w + gap).sum::<usize>()
+        };
+
+        // The first data column is index 1 in `widths`
+        let selected = self.column.max(1);
+        self.column_offset = self.column_offset.clamp(1, selected);
+        while self.column_offset < selected && shown_width(self.column_offset, selected + 1) > width
+        {
+            self.column_offset += 1;
+        }
+
+        let mut end = (selected + 1).min(widths.len());
+        while end < widths.len() && shown_width(self.column_offset, end + 1) <= width {
+            end += 1;
+        }
+        end
+    }
+
+    // Draws the whole screen. Returns the number of rows shown at most
+    fn draw(&mut self, frame: &mut Frame) -> usize {
+        let [title, table_area, help] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        let page = frame.area().height.saturating_sub(CHROME_HEIGHT).max(1) as usize;
+
+        let table = self.table();
+        let names = self.column_names();
+        let rows = self.rows();
+        self.scroll = self.scroll.min(rows.len().saturating_sub(page));
+
+        let cells: Vec<Vec<AFlexStr>> = rows
+            .iter()
+            .map(|row| {
+                names
+                    .iter()
+                    .map(|name| {
+                        row.cell(name)
+                            .and_then(|col| self.view.value(col))
+                            .unwrap_or_else(|| MISSING_CELL.into())
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut widths = vec![rows
+            .iter()
+            .map(|row| display_width(row.name()))
+            .max()
+            .unwrap_or_default()];
+        for (idx, name) in names.iter().enumerate() {
+            let cell_width = cells.iter().map(|row| display_width(&row[idx])).max();
+            widths.push(display_width(name).max(cell_width.unwrap_or_default()));
+        }
+        let end = self.fit_columns(&widths, table_area.width as usize);
+        let shown: Vec<_> = std::iter::once(0).chain(self.column_offset..end).collect();
+
+        // *** Title ***
+
+        let sort = match self.sort {
+            None => "none".to_string(),
+            Some((column, descending)) => {
+                let name = if column == 0 {
+                    "row"
+                } else {
+                    names[column - 1]
+                };
+                format!("{name} {}", if descending { "desc" } else { "asc" })
+            }
+        };
+        let status = format!(
+            "  ({}/{})  view: {}  sort: {sort}  rows: {}/{}",
+            self.table_idx + 1,
+            self.tables.len(),
+            self.view.name(),
+            rows.len(),
+            table.rows().count()
+        );
+        frame.render_widget(
+            Line::from(vec![
+                Span::from(table.name().as_str()).bold(),
+                status.into(),
+            ]),
+            title,
+        );
+
+        // *** Table ***
+
+        // Data is right aligned, so its units line up
+        let cell = |text: &str, idx: usize| {
+            let line = Line::from(text.to_string());
+            Cell::from(if idx > 0 { line.right_aligned() } else { line })
+        };
+
+        let header = shown.iter().map(|&idx| {
+            let name = if idx == 0 {
+                ""
+            } else {
+                names[idx - 1].as_str()
+            };
+            let style = if idx == self.column {
+                Style::new().reversed()
+            } else {
+                Style::new()
+            };
+            cell(name, idx).style(style)
+        });
+        let table_rows =
+            rows.iter()
+                .zip(&cells)
+                .skip(self.scroll)
+                .take(page)
+                .map(|(row, row_cells)| {
+                    TableRow::new(shown.iter().map(|&idx| match idx {
+                        0 => cell(row.name(), 0),
+                        _ => cell(&row_cells[idx - 1], idx),
+                    }))
+                });
+        let constraints = shown
+            .iter()
+            .map(|&idx| Constraint::Length(widths[idx] as u16));
+
+        frame.render_widget(
+            TableWidget::new(table_rows, constraints)
+                .header(TableRow::new(header).underlined())
+                .column_spacing(COLUMN_GAP),
+            table_area,
+        );
+
+        // *** Help ***
+
+        let help_text = if self.editing_filter {
+            format!("/{}_", self.filter)
+        } else if !self.filter.is_empty() {
+            format!("filter: {}  {HELP}", self.filter)
+        } else {
+            HELP.to_string()
+        };
+        frame.render_widget(Line::from(help_text), help.rows().nth(1).unwrap_or(help));
+
+        page
+    }
+
+    // Draws and handles key presses until quit
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            let mut page = 1;
+            terminal.draw(|frame| page = self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.handle(Key::from(key), page) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+// *** Functions ***
+
+/// Open an interactive viewer of the tables of `data` on the terminal, until it is quit. Each table
+/// can be scrolled, sorted by any column, filtered by row name and shown as times, throughput or
+/// ratios to the baseline. Keys are read from the terminal even if stdin isn't one, so `data` can
+/// be read from stdin
+pub fn run(data: &CriterionTableData) -> io::Result<()> {
+    let mut viewer = Viewer::new(data);
+    if viewer.tables.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "No tables to view",
+        ));
+    }
+
+    let mut terminal = ratatui::try_init()?;
+    let result = viewer.run(&mut terminal);
+    ratatui::restore();
+    result
+}
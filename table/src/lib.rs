@@ -11,21 +11,73 @@
 //!
 //! [Benchmark Report](https://github.com/nu11ptr/criterion-table/blob/master/example/README.md)
 
+/// This module makes badges (shields.io endpoint JSON or SVG) of the headline numbers of tables
+pub mod badge;
+/// This module holds `TableBuilder`, which builds tables from library code in one chain of calls
+pub mod builder;
+/// This module draws SVG bar charts of table data
+pub mod chart;
+mod compare;
+mod config;
+/// This module loads benchmark data directly from criterion's output directory (`target/criterion`)
+pub mod criterion_dir;
+/// This module loads benchmark data from divan's JSON output
+pub mod divan;
+/// This module holds the options of the section at the top of the output recording the
+/// benchmarking environment (ex: the CPU model)
+pub mod environment;
+/// This module holds the options of the footer and the provenance included in it
+pub mod footer;
 /// This module holds the various formatters that can be used to format the output
 pub mod formatter;
+/// This module loads benchmark data from Google Benchmark's JSON output
+pub mod google_benchmark;
+/// This module records processed runs in a local history store and builds trend tables from it
+pub mod history;
+/// This module loads benchmark data from hyperfine's JSON output
+pub mod hyperfine;
+/// This module injects generated tables into a marked section of an existing document (ex: a README)
+pub mod inject;
+/// This module holds an interactive terminal viewer for exploring tables (requires the
+/// `interactive` feature)
+#[cfg(feature = "interactive")]
+pub mod interactive;
+/// This module loads benchmark data from the text output of libtest's `#[bench]` harness
+pub mod libtest;
+/// This module holds the mdBook preprocessor used to render tables inside book chapters
+pub mod mdbook;
+mod measurement;
+mod message;
+/// This module builds multi-file reports (a summary page plus a detail page per table)
+pub mod report;
+/// This module aggregates benchmark data from all members of a cargo workspace into one report
+pub mod workspace;
+/// This module writes tables to the worksheets of an Excel workbook (requires the `xlsx` feature)
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
+
+pub use compare::*;
+pub use config::*;
+pub use measurement::*;
+pub use message::*;
 
 use std::cmp::{max, Ordering};
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufReader, ErrorKind, Read};
-use std::ops::Div;
-use std::path::Path;
-
-use anyhow::anyhow;
-use flexstr::{flex_fmt, FlexStr, IntoFlex, ToCase, ToFlex, ToFlexStr};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flexstr::{a_flex_fmt, AFlexStr, IntoFlex, ToAFlexStr, ToCase, ToFlex};
 use indexmap::map::Entry;
 use indexmap::IndexMap;
-use serde::Deserialize;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use unicode_width::UnicodeWidthStr;
+
+use crate::builder::TableBuilder;
+use crate::chart::ChartOptions;
+use crate::formatter::FormatterRegistry;
 
 // Trick to test README samples (from: https://github.com/rust-lang/cargo/issues/383#issuecomment-720873790)
 #[cfg(doctest)]
@@ -40,349 +92,1345 @@ mod test_readme {
     external_doc_test!(include_str!("../../README.md"));
 }
 
+// The column names used by `CriterionTableData::diff`
+const BASELINE_LABEL: &str = "baseline";
+const LATEST_LABEL: &str = "latest";
+
+// The tag of a benchmark that got slower run over run across its last `MONOTONIC_RUNS` runs
+const REGRESSING_TAG: &str = "regressing";
+const MONOTONIC_RUNS: usize = 3;
+
+// The fraction a comparison must differ from even by to be significant
+const SIGNIFICANT_CHANGE: f64 = 0.1;
+
+// Id patterns with this prefix are regexes instead of globs
+const REGEX_PATTERN_PREFIX: &str = "re:";
+
+// The name of the summary row holding the geometric mean of each column's comparisons
+const GEOMEAN_ROW_NAME: &str = "Geomean";
+
+// How a comparison within the noise threshold is displayed
+const NOISE_STR: &str = "~equal";
+
+/// The directory criterion writes its results and reports to, relative to the workspace root
+pub const CRITERION_DIR: &str = "target/criterion";
+
+/// The display width of the widest `CellRole` marker (an emoji and a space)
+pub const ROLE_MARKER_WIDTH: usize = 3;
+
 // Starting capacity for the String buffer used to build the page
-const BUFFER_CAPACITY: usize = 65535;
+pub(crate) const BUFFER_CAPACITY: usize = 65535;
+
+// *** Errors ***
+
+/// The errors that can occur while loading and processing benchmark data
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A benchmark id couldn't be split into table, column, and row names
+    #[error("{reason}: {id}")]
+    MalformedId {
+        /// The benchmark id
+        id: String,
+        /// Why it couldn't be split (ex: `Malformed id`)
+        reason: &'static str,
+    },
+    /// A time has a unit that isn't recognized
+    #[error("Unrecognized time unit: {unit}")]
+    UnknownUnit {
+        /// The unit
+        unit: String,
+    },
+    /// A time couldn't be parsed as a number
+    #[error("Invalid time: {time}")]
+    InvalidTime {
+        /// The time as it was given
+        time: String,
+    },
+    /// A row has more than one benchmark in the same column
+    #[error("Duplicate column: {column}")]
+    DuplicateColumn {
+        /// The name of the column
+        column: String,
+    },
+    /// Merged inputs have the same benchmark and the duplicate policy is `error`
+    #[error("Duplicate benchmark: {table}/{column}/{row}")]
+    DuplicateBenchmark {
+        /// The name of the table
+        table: String,
+        /// The name of the column
+        column: String,
+        /// The name of the row
+        row: String,
+    },
+    /// A metric isn't recognized or the parameter of a robust estimate is out of range
+    #[error(
+        "Invalid metric (expected typical, mean, median, slope, trimmed_mean:FRACTION (0.0 to 0.5) \
+         or percentile:PERCENTILE (0 to 100)): {metric}"
+    )]
+    InvalidMetric {
+        /// The metric as it was given
+        metric: String,
+    },
+    /// No formatter is registered under the name of a format
+    #[error("Unknown format `{format}` (expected one of: {known})")]
+    UnknownFormat {
+        /// The name of the format
+        format: String,
+        /// The names of the registered formats
+        known: String,
+    },
+    /// The format of the input couldn't be detected
+    #[error(
+        "Unknown input format (expected criterion, hyperfine, Google Benchmark, divan or libtest \
+         output)"
+    )]
+    UnknownInputFormat,
+    /// A (version 2) config has keys that aren't options
+    #[error("Unknown config keys: {keys}")]
+    UnknownKeys {
+        /// The unknown keys
+        keys: String,
+    },
+    /// Config table sections name tables that aren't in the data
+    #[error("Config sections for unknown tables: {tables}")]
+    UnknownTables {
+        /// The names of the tables
+        tables: String,
+    },
+    /// Config entries name tables that aren't in the data (with `version = 2`)
+    #[error("Config entries for unknown tables: {entries}")]
+    UnknownConfigTables {
+        /// Each entry, with a suggestion if there is a similar table
+        entries: String,
+    },
+    /// Renames give two tables, or two columns or rows of a table, the same display name
+    #[error("Both {kind}s `{first}` and `{second}` are renamed to `{name}`")]
+    RenameCollision {
+        /// What was renamed (`table`, `column` or `row`)
+        kind: &'static str,
+        /// The name of the first
+        first: String,
+        /// The name of the second
+        second: String,
+        /// The display name they share
+        name: String,
+    },
+    /// A table has measurements of different kinds (ex: times and bytes), which can't be compared
+    #[error("Table `{table}` mixes {first} and {second} measurements")]
+    MixedMeasurements {
+        /// The name of the table
+        table: String,
+        /// The kind of its first measurement
+        first: String,
+        /// The kind that doesn't match it
+        second: String,
+    },
+    /// There was no table data to merge
+    #[error("No table data to merge")]
+    NoData,
+    /// There was no input to build tables from
+    #[error("No input was added")]
+    NoInput,
+    /// One of several inputs couldn't be loaded
+    #[error("Input {index}: {source}")]
+    Input {
+        /// The position of the input, starting at one
+        index: usize,
+        /// Why it couldn't be loaded
+        source: Box<Error>,
+    },
+    /// A benchmark couldn't be added to its table
+    #[error("{id}: {source}")]
+    Benchmark {
+        /// The benchmark id
+        id: String,
+        /// Why it couldn't be added
+        source: Box<Error>,
+    },
+    /// A file couldn't be loaded
+    #[error("{}: {source}", path.display())]
+    File {
+        /// The path of the file
+        path: PathBuf,
+        /// Why it couldn't be loaded
+        source: Box<Error>,
+    },
+    /// A document has no `inject::START_MARKER`
+    #[error("Unable to find the start marker: {}", inject::START_MARKER)]
+    MissingStartMarker,
+    /// A document has no `inject::END_MARKER` after its `inject::START_MARKER`
+    #[error(
+        "Unable to find the end marker after the start marker: {}",
+        inject::END_MARKER
+    )]
+    MissingEndMarker,
+    /// A line of the history store couldn't be parsed
+    #[error("History line {line}: {source}")]
+    HistoryLine {
+        /// The line number, starting at one
+        line: usize,
+        /// Why it couldn't be parsed
+        source: serde_json::Error,
+    },
+    /// The mdBook context has no book root
+    #[error("mdBook context is missing the book root")]
+    MissingBookRoot,
+    /// A `{{#criterion-table ...}}` placeholder has no closing `}}`
+    #[error("Unterminated criterion-table placeholder")]
+    UnterminatedPlaceholder,
+    /// `cargo metadata` failed
+    #[error("cargo metadata failed: {stderr}")]
+    CargoMetadata {
+        /// What it wrote to stderr
+        stderr: String,
+    },
+    /// No member of the workspace has a data file
+    #[error("No workspace member has a '{}' file", file_name.display())]
+    NoWorkspaceData {
+        /// The name of the data file
+        file_name: PathBuf,
+    },
+    /// A badge selects a cell that has no benchmark
+    #[error("No benchmark for badge: table '{table}', column '{column}', row '{row}'")]
+    NoBadgeBenchmark {
+        /// The name of the table
+        table: String,
+        /// The name of the column
+        column: String,
+        /// The name of the row
+        row: String,
+    },
+    /// A Handlebars template couldn't be compiled
+    #[cfg(feature = "template")]
+    #[error(transparent)]
+    Template(#[from] handlebars::TemplateError),
+    /// The config couldn't be parsed
+    #[error(transparent)]
+    Config(#[from] toml::de::Error),
+    /// A pattern isn't a valid regex
+    #[error(transparent)]
+    Pattern(#[from] regex::Error),
+    /// Input couldn't be parsed as JSON
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Input couldn't be parsed as CBOR
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    Cbor(#[from] serde_cbor::Error),
+    /// An I/O error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// An Excel workbook couldn't be written
+    #[cfg(feature = "xlsx")]
+    #[error(transparent)]
+    Xlsx(#[from] rust_xlsxwriter::XlsxError),
+}
+
+// *** Processing Summary ***
+
+/// Why a benchmark was dropped from the tables
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// Its id didn't match any `include` pattern, or matched an `exclude` pattern
+    Pattern,
+    /// Its column is hidden (via `hide_columns`)
+    HiddenColumn,
+    /// It has a tag in `exclude_tags`
+    Tag,
+    /// Nothing in its table changed (via `changed_tables_only`)
+    Unchanged,
+    /// It didn't regress enough (via `regressions_only`)
+    NoRegression,
+}
+
+impl ToAFlexStr for DropReason {
+    fn to_a_flex_str(&self) -> AFlexStr {
+        match self {
+            DropReason::Pattern => "include/exclude pattern".into(),
+            DropReason::HiddenColumn => "hidden column".into(),
+            DropReason::Tag => "excluded tag".into(),
+            DropReason::Unchanged => "unchanged table".into(),
+            DropReason::NoRegression => "no regression".into(),
+        }
+    }
+}
+
+/// A benchmark dropped from the tables by a filter of the config
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DroppedBenchmark {
+    /// The name of its table
+    pub table: AFlexStr,
+    /// The name of its column
+    pub column: AFlexStr,
+    /// The name of its row (may be blank)
+    pub row: AFlexStr,
+    /// Why it was dropped
+    pub reason: DropReason,
+}
 
-// *** Raw JSON Data Structs ***
+/// A table created from the raw data (with its display names), before any filters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableSummary {
+    /// The name of the table
+    pub name: AFlexStr,
+    /// The names of its columns
+    pub columns: Vec<AFlexStr>,
+    /// The names of its rows
+    pub rows: Vec<AFlexStr>,
+}
+
+/// What was consumed, created and dropped while processing raw benchmark data (ex: for tests,
+/// tooling, or `--verbose` output). The records are counted when the table data is built, and
+/// the tables and dropped benchmarks are filled in by `apply_config`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProcessingSummary {
+    /// The number of benchmark records consumed
+    pub benchmarks: usize,
+    /// The number of benchmark group records consumed
+    pub groups: usize,
+    /// The number of records of kinds that aren't tabulated, which were ignored
+    pub unknown: usize,
+    /// The number of benchmarks skipped as invalid (see `CriterionTableData::from_raw_lenient`)
+    pub skipped: usize,
+    /// Each table created
+    pub tables: Vec<TableSummary>,
+    /// Each benchmark dropped by a filter, in the order the filters were applied
+    pub dropped: Vec<DroppedBenchmark>,
+}
 
-// NOTE: These were shamelessly copied (with translation) from:
-// https://github.com/bheisler/cargo-criterion/blob/main/src/message_formats/json.rs
+impl ProcessingSummary {
+    fn add_records(&mut self, raw_data: &[RawCriterionData]) {
+        for item in raw_data {
+            match item {
+                RawCriterionData::Benchmark(_) => self.benchmarks += 1,
+                RawCriterionData::BenchmarkGroup(_) => self.groups += 1,
+                RawCriterionData::Other => self.unknown += 1,
+            }
+        }
+    }
 
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-struct ConfidenceInterval {
-    estimate: f64,
-    lower_bound: f64,
-    upper_bound: f64,
-    unit: FlexStr,
+    fn add(&mut self, other: &Self) {
+        self.benchmarks += other.benchmarks;
+        self.groups += other.groups;
+        self.unknown += other.unknown;
+        self.skipped += other.skipped;
+    }
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-struct Throughput {
-    per_iteration: u64,
-    unit: FlexStr,
+impl ToAFlexStr for ProcessingSummary {
+    fn to_a_flex_str(&self) -> AFlexStr {
+        let mut summary = format!(
+            "Read {} benchmark(s), {} group(s) and {} unknown record(s), skipped {} invalid \
+             benchmark(s)\n",
+            self.benchmarks, self.groups, self.unknown, self.skipped
+        );
+        for table in &self.tables {
+            let columns: Vec<_> = table.columns.iter().map(AFlexStr::as_str).collect();
+            summary.push_str(&format!(
+                "Created table `{}` with {} row(s) and column(s): {}\n",
+                table.name,
+                table.rows.len(),
+                columns.join(", ")
+            ));
+        }
+        for dropped in &self.dropped {
+            let mut name = format!("{}/{}", dropped.table, dropped.column);
+            if !dropped.row.is_empty() {
+                name.push('/');
+                name.push_str(&dropped.row);
+            }
+            summary.push_str(&format!(
+                "Dropped `{name}` ({})\n",
+                dropped.reason.to_a_flex_str()
+            ));
+        }
+        summary.into_flex()
+    }
 }
 
-#[derive(Debug, Deserialize)]
-enum ChangeType {
-    NoChange,
-    Improved,
-    Regressed,
+// *** Metric ***
+
+/// The estimate of each benchmark that is tabulated and compared. On the command line, the robust
+/// estimates are given as `trimmed_mean:FRACTION` and `percentile:PERCENTILE` (ex: `percentile:50`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Metric {
+    /// Criterion's typical estimate (the slope, if available, otherwise the mean)
+    #[default]
+    Typical,
+    /// The mean
+    Mean,
+    /// The median (less sensitive to outliers in noisy benchmarks)
+    Median,
+    /// The slope of the linear regression (the typical estimate if there is none)
+    Slope,
+    /// The mean of the time per iteration of the samples, after trimming this fraction (0.0 to
+    /// 0.5, ex: 0.1 for 10%) of them from each end, recomputed from the raw samples for benchmarks
+    /// skewed by outliers (ex: warm-up). The typical estimate if there are no samples
+    #[serde(rename = "trimmed_mean")]
+    TrimmedMean(f64),
+    /// This percentile (0 to 100) of the time per iteration of the samples (ex: 50 for the median
+    /// sample), recomputed from the raw samples. The typical estimate if there are no samples
+    Percentile(f64),
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-struct ChangeDetails {
-    mean: ConfidenceInterval,
-    median: ConfidenceInterval,
+impl Metric {
+    /// Returns an error if the parameter of a robust estimate is out of range
+    pub fn validate(self) -> Result<Self, Error> {
+        let valid = match self {
+            Metric::TrimmedMean(fraction) => (0.0..0.5).contains(&fraction),
+            Metric::Percentile(percentile) => (0.0..=100.0).contains(&percentile),
+            Metric::Typical | Metric::Mean | Metric::Median | Metric::Slope => true,
+        };
+
+        if valid {
+            Ok(self)
+        } else {
+            Err(Error::InvalidMetric {
+                metric: self.to_a_flex_str().to_string(),
+            })
+        }
+    }
+}
 
-    change: ChangeType,
+impl ToAFlexStr for Metric {
+    fn to_a_flex_str(&self) -> AFlexStr {
+        match self {
+            Metric::Typical => "typical".into(),
+            Metric::Mean => "mean".into(),
+            Metric::Median => "median".into(),
+            Metric::Slope => "slope".into(),
+            Metric::TrimmedMean(fraction) => a_flex_fmt!("trimmed_mean:{fraction}"),
+            Metric::Percentile(percentile) => a_flex_fmt!("percentile:{percentile}"),
+        }
+    }
 }
 
-/// Raw deserialized JSON Criterion benchmark data
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-pub struct BenchmarkComplete {
-    id: FlexStr,
-    report_directory: FlexStr,
-    iteration_count: Vec<u64>,
-    measured_values: Vec<f64>,
-    unit: FlexStr,
+impl FromStr for Metric {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidMetric {
+            metric: s.to_string(),
+        };
+        let param = |param: &str| param.parse::<f64>().map_err(|_| invalid());
 
-    throughput: Vec<Throughput>,
+        let metric = match s.split_once(':') {
+            None => match s {
+                "typical" => Metric::Typical,
+                "mean" => Metric::Mean,
+                "median" => Metric::Median,
+                "slope" => Metric::Slope,
+                _ => return Err(invalid()),
+            },
+            Some(("trimmed_mean", fraction)) => Metric::TrimmedMean(param(fraction)?),
+            Some(("percentile", percentile)) => Metric::Percentile(param(percentile)?),
+            Some(_) => return Err(invalid()),
+        };
+        metric.validate()
+    }
+}
 
-    typical: ConfidenceInterval,
-    mean: ConfidenceInterval,
-    median: ConfidenceInterval,
-    median_abs_dev: ConfidenceInterval,
-    slope: Option<ConfidenceInterval>,
+// *** Row Sort ***
 
-    change: Option<ChangeDetails>,
+/// The order rows of each table are sorted in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum RowSort {
+    /// By row name, with any numbers compared by value (ex: `10`, `20`, `100`)
+    Name,
+    /// By the time of the baseline (the first column) of each row
+    Time,
+    /// By the largest speedup over the baseline of each row
+    Speedup,
 }
 
-/// Raw deserialized JSON Criterion benchmark group data
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-pub struct BenchmarkGroupComplete {
-    group_name: FlexStr,
-    benchmarks: Vec<FlexStr>,
-    report_directory: FlexStr,
+// *** Id Parsing ***
+
+/// Implement this trait to split benchmark ids into table, column, and row names in a custom way
+pub trait IdParser {
+    /// Split the benchmark `id` (without any `#tag1,tag2` suffix) into the table, column, and row
+    /// names respectively. The row name may be blank
+    fn parse_id(&self, id: &str) -> Result<(AFlexStr, AFlexStr, AFlexStr), Error>;
+
+    /// Like `parse_id`, but for a benchmark of criterion benchmark group `group` (as listed by the
+    /// group's `group-complete` message). By default the group is ignored
+    fn parse_group_id(
+        &self,
+        _group: &str,
+        id: &str,
+    ) -> Result<(AFlexStr, AFlexStr, AFlexStr), Error> {
+        self.parse_id(id)
+    }
 }
 
-/// Enum that can hold either Raw deserialized JSON benchmark or benchmark group data
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-pub enum RawCriterionData {
-    /// Raw benchmark data
-    Benchmark(Box<BenchmarkComplete>),
-    /// Raw benchmark group data
-    BenchmarkGroup(Box<BenchmarkGroupComplete>),
+/// The built-in schemes for splitting benchmark ids into table, column, and row names
+#[derive(Clone, Debug, Default, Deserialize)]
+pub enum IdScheme {
+    /// `table/column/row` (ex: `Fibonacci/Recursive/20` as given by a benchmark group with a
+    /// function and parameter). The row is blank if not present, and deeper ids have the rest of
+    /// their parts joined into the row name (ex: `dataset/size` of `group/impl/dataset/size`)
+    #[default]
+    #[serde(rename = "table/column/row")]
+    TableColumnRow,
+    /// `table/row/column`. The row is blank if only two parts are present, and deeper ids have the
+    /// last part as the column and the parts before it joined into the row name
+    #[serde(rename = "table/row/column")]
+    TableRowColumn,
+    /// `table/column`, where the column is everything after the first `/` and the row is blank
+    #[serde(rename = "table/column")]
+    TableColumn,
+    /// A regex with `table`, `column` and (optionally) `row` named capture groups
+    #[serde(rename = "regex", deserialize_with = "deserialize_id_regex")]
+    Regex(Regex),
+    /// The name of the criterion benchmark group is the table (even if it contains a `/`), and the
+    /// rest of the id is `column/row`. Benchmarks outside any group are split as
+    /// `table/column/row`
+    #[serde(rename = "group")]
+    Group,
 }
 
-impl RawCriterionData {
-    /// Load raw Criterion JSON data from the given reader. It returns a `Vec` of enum wrapped raw
-    /// benchmark or group data
-    pub fn from_reader(r: impl Read) -> serde_json::error::Result<Vec<Self>> {
-        let reader = BufReader::new(r);
-        let mut de = serde_json::Deserializer::from_reader(reader);
-        let mut data_vec = Vec::new();
+impl IdParser for IdScheme {
+    fn parse_id(&self, id: &str) -> Result<(AFlexStr, AFlexStr, AFlexStr), Error> {
+        if let IdScheme::Regex(regex) = self {
+            let captures = regex.captures(id).ok_or_else(|| Error::MalformedId {
+                id: id.to_string(),
+                reason: "Id doesn't match the id regex",
+            })?;
+            let capture = |name| captures.name(name).map(|m| m.as_str().to_flex());
+
+            return match (capture("table"), capture("column")) {
+                (Some(table), Some(column)) => {
+                    Ok((table, column, capture("row").unwrap_or_default()))
+                }
+                _ => Err(Error::MalformedId {
+                    id: id.to_string(),
+                    reason: "Id regex didn't capture a table and column",
+                }),
+            };
+        }
+
+        let mut parts: Vec<&str> = id.split('/').collect();
+        if parts.len() < 2 {
+            return Err(Error::MalformedId {
+                id: id.to_string(),
+                reason: "Malformed id",
+            });
+        }
+
+        let table_name = parts.remove(0);
+        // If we don't have a row name then we will work with a blank row name. Any parts beyond
+        // the third are kept in the row name, so deeper ids don't collide
+        let (column_name, row_name) = match self {
+            IdScheme::TableRowColumn if parts.len() > 1 => {
+                let column_name = parts.pop().unwrap_or_default();
+                (column_name, parts.join("/"))
+            }
+            IdScheme::TableColumn => (&id[table_name.len() + 1..], String::new()),
+            _ => {
+                let column_name = parts.remove(0);
+                (column_name, parts.join("/"))
+            }
+        };
+
+        Ok((
+            table_name.to_flex(),
+            column_name.to_flex(),
+            row_name.into_flex(),
+        ))
+    }
+
+    fn parse_group_id(
+        &self,
+        group: &str,
+        id: &str,
+    ) -> Result<(AFlexStr, AFlexStr, AFlexStr), Error> {
+        let rest = id
+            .strip_prefix(group)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .filter(|_| matches!(self, IdScheme::Group));
 
-        loop {
-            match RawCriterionData::deserialize(&mut de) {
-                Ok(data) => data_vec.push(data),
-                Err(err) if err.is_eof() => break,
-                Err(err) => return Err(err),
+        match rest {
+            Some(rest) => {
+                let (column_name, row_name) = rest.split_once('/').unwrap_or((rest, ""));
+                Ok((group.to_flex(), column_name.to_flex(), row_name.to_flex()))
             }
+            None => self.parse_id(id),
         }
+    }
+}
 
-        Ok(data_vec)
+// The regex must have `table` and `column` capture groups to be of any use
+fn deserialize_id_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    let regex = Regex::new(&pattern).map_err(de::Error::custom)?;
+
+    let names: Vec<_> = regex.capture_names().flatten().collect();
+    if names.contains(&"table") && names.contains(&"column") {
+        Ok(regex)
+    } else {
+        Err(de::Error::custom(
+            "the id regex must have `table` and `column` named capture groups",
+        ))
     }
 }
 
-// *** Tables Config ***
+// *** Id Patterns ***
+
+/// A pattern matched against benchmark ids (without any `#tag1,tag2` suffix): either a glob
+/// matching the whole id, where `*` matches anything (including `/`) and `?` matches any one
+/// character (ex: `Fibonacci/*`), or a regex prefixed by `re:` that matches any part of the id
+/// (ex: `re:^Parse .*/(std|simd)`)
+#[derive(Clone, Debug)]
+pub struct IdPattern(Regex);
 
-#[derive(Default, Deserialize)]
-/// Configuration file format for adding comments to tables
-pub struct TablesConfig {
-    /// Top level comments
-    pub top_comments: IndexMap<FlexStr, FlexStr>,
-    /// Per table comments (table -> comment)
-    pub table_comments: HashMap<FlexStr, FlexStr>,
+impl IdPattern {
+    /// True if `id` matches this pattern
+    #[inline]
+    pub fn is_match(&self, id: &str) -> bool {
+        self.0.is_match(id)
+    }
 }
 
-impl TablesConfig {
-    /// Try to load the config from the given reader
-    pub fn try_load_config(r: impl Read) -> anyhow::Result<Self> {
-        let mut reader = BufReader::new(r);
-        let mut buffer = String::with_capacity(16384);
-        reader.read_to_string(&mut buffer)?;
+impl FromStr for IdPattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(pattern) = s.strip_prefix(REGEX_PATTERN_PREFIX) {
+            return Ok(Self(Regex::new(pattern)?));
+        }
+
+        let mut pattern = String::with_capacity(s.len() + 2);
+        pattern.push('^');
+        for ch in s.chars() {
+            match ch {
+                '*' => pattern.push_str(".*"),
+                '?' => pattern.push('.'),
+                ch => pattern.push_str(&regex::escape(ch.encode_utf8(&mut [0; 4]))),
+            }
+        }
+        pattern.push('$');
+
+        Ok(Self(Regex::new(&pattern)?))
+    }
+}
 
-        let config: TablesConfig = toml::from_str(&buffer)?;
-        Ok(config)
+impl<'de> Deserialize<'de> for IdPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        pattern.parse().map_err(de::Error::custom)
     }
 }
 
+// *** Duplicate Policy ***
+
+/// What to do when merging benchmark data with the same table, column, and row names
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicatePolicy {
+    /// Fail with an error
+    #[default]
+    Error,
+    /// Keep the benchmark seen first
+    KeepFirst,
+    /// Keep the fastest benchmark
+    KeepBest,
+    /// Average the times (and other estimates) of all the benchmarks
+    Average,
+}
+
 // *** Criterion Data Structures ***
 
 // ### Column Info ###
 
+/// How the cells of a column are aligned
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Alignment {
+    /// Left justified
+    #[default]
+    Left,
+    /// Centered
+    Center,
+    /// Right justified (ex: so the digits of times line up)
+    Right,
+}
+
 /// Column maximum width data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ColumnInfo {
     /// The name of the column
-    pub name: FlexStr,
+    pub name: AFlexStr,
     /// The maximum display width for this column
     pub max_width: usize,
+    /// How the cells of this column are aligned
+    #[serde(default)]
+    pub align: Alignment,
 }
 
 impl ColumnInfo {
     #[inline]
     /// Create a new `ColumnInfo` using an initial width
-    pub fn new(name: FlexStr, width: usize) -> Self {
+    pub fn new(name: AFlexStr, width: usize) -> Self {
         Self {
             name,
             max_width: width,
+            align: Alignment::Left,
         }
     }
 
+    #[inline]
+    /// Align the cells of this column as given
+    pub fn with_align(mut self, align: Alignment) -> Self {
+        self.align = align;
+        self
+    }
+
     #[inline]
     fn update_info(&mut self, width: usize) {
         self.max_width = max(self.max_width, width);
     }
 }
 
-// ### Time Unit ###
+// ### Extra Stats ###
 
-/// Time unit of a particular measurement
-#[derive(Clone, Copy, Debug)]
-pub enum TimeUnit {
-    /// Time is in seconds
-    Second(f64),
-    /// Time is in milliseconds
-    Millisecond(f64),
-    /// Time is in microseconds
-    Microsecond(f64),
-    /// Time is in nanoseconds
-    Nanosecond(f64),
-    /// Time is in picoseconds
-    Picosecond(f64),
+/// Extra statistics of each benchmark displayed after its time (ex: `2.00 us, σ 0.05 us`), or as
+/// additional columns in CSV and TSV output. The standard deviation, minimum and maximum are of
+/// the time per iteration of the samples criterion measured
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtraStatOptions {
+    /// The standard deviation (displayed as `σ`)
+    pub stddev: bool,
+    /// Criterion's median absolute deviation (displayed as `MAD`)
+    pub mad: bool,
+    /// The fastest sample (displayed as `min`)
+    pub min: bool,
+    /// The slowest sample (displayed as `max`)
+    pub max: bool,
 }
 
-impl TimeUnit {
-    /// Create a new `TimeUnit` taking the time and initial unit string as input
-    pub fn try_new(time: f64, unit: &str) -> anyhow::Result<Self> {
-        match unit {
-            "ms" if time > 1000.0 => Self::try_new(time / 1000.0, "s"),
-            "us" if time > 1000.0 => Self::try_new(time / 1000.0, "ms"),
-            "ns" if time > 1000.0 => Self::try_new(time / 1000.0, "us"),
-            "ps" if time > 1000.0 => Self::try_new(time / 1000.0, "ns"),
-            "s" => Ok(TimeUnit::Second(time)),
-            "ms" => Ok(TimeUnit::Millisecond(time)),
-            "us" => Ok(TimeUnit::Microsecond(time)),
-            "ns" => Ok(TimeUnit::Nanosecond(time)),
-            "ps" => Ok(TimeUnit::Picosecond(time)),
-            _ => Err(anyhow!("Unrecognized time unit: {unit}")),
-        }
+impl ExtraStatOptions {
+    /// Returns whether each statistic is displayed, in display order (as `ExtraStats::all`)
+    #[inline]
+    pub fn enabled(&self) -> [bool; 4] {
+        [self.stddev, self.mad, self.min, self.max]
     }
+}
+
+// ### Table of Contents ###
+
+/// The title of the document and its table of contents. Tables can be nested in the table of
+/// contents (and in sections of the document) by the prefix of their name (ex: `parser::ascii` and
+/// `parser::utf8` under `parser`)
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TocOptions {
+    /// The title of the document (default: `Benchmarks`)
+    pub title: AFlexStr,
+    /// Write the table of contents (default: true)
+    pub enabled: bool,
+    /// Nest each table under the part of its name before the last occurrence of this separator
+    /// (ex: `::`). Tables sharing that prefix are grouped in the order the first of them appears
+    pub nest_separator: Option<AFlexStr>,
+}
 
-    /// Returns the display width in chars for this `TimeUnit`
+impl Default for TocOptions {
     #[inline]
-    pub fn width(&self) -> usize {
-        self.to_flex_str().chars().count()
+    fn default() -> Self {
+        Self {
+            title: "Benchmarks".into(),
+            enabled: true,
+            nest_separator: None,
+        }
     }
+}
 
-    fn as_picoseconds(&self) -> f64 {
-        match *self {
-            TimeUnit::Second(s) => s * 1_000_000_000_000.0,
-            TimeUnit::Millisecond(ms) => ms * 1_000_000_000.0,
-            TimeUnit::Microsecond(us) => us * 1_000_000.0,
-            TimeUnit::Nanosecond(ns) => ns * 1_000.0,
-            TimeUnit::Picosecond(ps) => ps,
+impl TocOptions {
+    /// Returns the table of contents passed to `Formatter::start` holding `entries`
+    #[inline]
+    pub fn toc<'a>(&'a self, entries: Vec<TocEntry<'a>>) -> Toc<'a> {
+        Toc {
+            title: &self.title,
+            enabled: self.enabled,
+            entries,
         }
     }
 }
 
-impl Div for TimeUnit {
-    type Output = f64;
+// ### Embedding ###
 
-    fn div(self, rhs: Self) -> Self::Output {
-        let unit1 = self.as_picoseconds();
-        let unit2 = rhs.as_picoseconds();
-        unit1 / unit2
-    }
+/// Write only the tables: no title, table of contents, top level comments or footer, nor anything
+/// else a formatter writes around them (ex: the `<html>` of HTML output). The output can then be
+/// placed in a larger generated document as is
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmbedOptions {
+    /// Write the heading of each table (and of each section holding tables)
+    pub headings: bool,
 }
 
-impl ToFlexStr for TimeUnit {
-    fn to_flex_str(&self) -> FlexStr {
-        match self {
-            TimeUnit::Second(time) => flex_fmt!("{time:.2} s"),
-            TimeUnit::Millisecond(time) => flex_fmt!("{time:.2} ms"),
-            TimeUnit::Microsecond(time) => flex_fmt!("{time:.2} us"),
-            TimeUnit::Nanosecond(time) => flex_fmt!("{time:.2} ns"),
-            TimeUnit::Picosecond(time) => flex_fmt!("{time:.2} ps"),
-        }
+impl EmbedOptions {
+    /// Returns true if headings are written by a formatter embedding with `embed` (always true
+    /// when not embedding)
+    #[inline]
+    pub fn headings(embed: Option<Self>) -> bool {
+        embed.is_none_or(|embed| embed.headings)
     }
 }
 
-// ### Comparison ###
+// ### Highlight ###
 
-/// A comparison time of a benchmark to its baseline
-#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
-pub struct Comparison(f64);
+/// Which cells of each row are highlighted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum Highlight {
+    /// No cells are highlighted
+    #[default]
+    Off,
+    /// The fastest cell of each row is highlighted (ex: `🏆 1.38 ns`)
+    Best,
+    /// The fastest and slowest cells of each row are highlighted (ex: `🐌 1.73 ms`)
+    BestWorst,
+}
+
+/// The role of a cell in its row, passed to `Formatter::used_column` via `CellData`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CellRole {
+    /// A cell that isn't highlighted
+    #[default]
+    Normal,
+    /// The fastest cell of the row
+    Best,
+    /// The slowest cell of the row
+    Worst,
+}
 
-impl Comparison {
-    /// The display width in chars of this comparison data
+impl CellRole {
+    /// Returns the marker written before the measurement of the cell (blank for normal cells). Its
+    /// display width is at most `ROLE_MARKER_WIDTH`
     #[inline]
-    pub fn width(self) -> usize {
-        self.to_flex_str().chars().count()
+    pub fn marker(self) -> &'static str {
+        match self {
+            CellRole::Normal => "",
+            CellRole::Best => "🏆 ",
+            CellRole::Worst => "🐌 ",
+        }
     }
 }
 
-impl ToFlexStr for Comparison {
-    fn to_flex_str(&self) -> FlexStr {
-        if self.0 > 1.0 {
-            flex_fmt!("{:.2}x faster", self.0)
-        } else if self.0 < 1.0 {
-            flex_fmt!("{:.2}x slower", 1.0 / self.0)
-        } else {
-            flex_fmt!("{:.2}x", self.0)
+// ### Stats ###
+
+// The remaining estimates of a benchmark (beyond the typical time used for comparisons)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Stats {
+    // The benchmark id (without any tags), empty in snapshots written before it was recorded
+    #[serde(default)]
+    id: AFlexStr,
+    lower_bound: Measurement,
+    upper_bound: Measurement,
+    mean: Measurement,
+    median: Measurement,
+    median_abs_dev: Measurement,
+    slope: Option<Measurement>,
+    // Derived from the `metric` estimate
+    throughput: Option<ThroughputRate>,
+    // The change since criterion's previous run, if known
+    change: Option<Change>,
+    // The standard deviation, minimum and maximum of the time per iteration of the samples (blank
+    // without samples, or in snapshots written before they were recorded)
+    #[serde(default)]
+    std_dev: Option<Measurement>,
+    #[serde(default)]
+    min: Option<Measurement>,
+    #[serde(default)]
+    max: Option<Measurement>,
+    // Where criterion wrote the HTML report (blank if unknown, or in snapshots written before it
+    // was recorded)
+    #[serde(default)]
+    report_directory: AFlexStr,
+}
+
+impl Stats {
+    // The bounds are those of the `metric` estimate
+    fn new(bm: &BenchmarkComplete, metric: Metric) -> Self {
+        let time = |ci: &ConfidenceInterval| Measurement::new(ci.estimate, &ci.unit);
+        let estimate = bm.estimate(metric);
+        let estimate_time = time(&estimate);
+        let [std_dev, min, max] = Self::sample_stats(bm);
+
+        Self {
+            id: match bm.id.split_once('#') {
+                Some((id, _)) => id.to_flex(),
+                None => bm.id.clone(),
+            },
+            lower_bound: Measurement::new(estimate.lower_bound, &estimate.unit),
+            upper_bound: Measurement::new(estimate.upper_bound, &estimate.unit),
+            mean: time(&bm.mean),
+            median: time(&bm.median),
+            median_abs_dev: time(&bm.median_abs_dev),
+            slope: bm.slope.as_ref().map(time),
+            throughput: ThroughputRate::new(&bm.throughput, estimate_time),
+            // Criterion only tracks the change of the mean and median
+            change: bm.change.as_ref().map(|change| Change {
+                verdict: change.change,
+                relative: match metric {
+                    Metric::Median | Metric::Percentile(_) => change.median.estimate,
+                    Metric::Typical | Metric::Mean | Metric::Slope | Metric::TrimmedMean(_) => {
+                        change.mean.estimate
+                    }
+                },
+            }),
+            std_dev,
+            min,
+            max,
+            report_directory: bm.report_directory.clone(),
         }
     }
-}
 
-impl PartialEq<f64> for Comparison {
-    #[inline]
-    fn eq(&self, other: &f64) -> bool {
-        f64::eq(&self.0, other)
+    // The standard deviation, minimum and maximum of the time per iteration of the samples of
+    // `bm`, if it has any
+    fn sample_stats(bm: &BenchmarkComplete) -> [Option<Measurement>; 3] {
+        let samples = bm.samples();
+        if samples.is_empty() {
+            return [None, None, None];
+        }
+
+        let len = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / len;
+        let variance = if samples.len() > 1 {
+            samples
+                .iter()
+                .map(|time| (time - mean).powi(2))
+                .sum::<f64>()
+                / (len - 1.0)
+        } else {
+            0.0
+        };
+        let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        let time = |value: f64| Some(Measurement::new(value, &bm.unit));
+        [time(variance.sqrt()), time(min), time(max)]
     }
-}
 
-impl PartialOrd<f64> for Comparison {
-    #[inline]
-    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
-        f64::partial_cmp(&self.0, other)
+    // The mean of each estimate of the given stats (which must not be empty). Optional estimates
+    // are only averaged if all the stats have them
+    fn mean(stats: &[&Stats]) -> Self {
+        let time = |f: fn(&Stats) -> &Measurement| {
+            Measurement::mean(
+                &stats
+                    .iter()
+                    .map(|stats| f(stats).clone())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let slopes: Option<Vec<_>> = stats.iter().map(|stats| stats.slope.clone()).collect();
+        let throughputs: Option<Vec<_>> = stats.iter().map(|stats| stats.throughput).collect();
+        let changes: Option<Vec<_>> = stats.iter().map(|stats| stats.change).collect();
+        // Optional times are only kept if all the stats have them
+        let all = |f: fn(&Stats) -> &Option<Measurement>| -> Option<Vec<_>> {
+            stats.iter().map(|stats| f(stats).clone()).collect()
+        };
+        let by_time =
+            |t1: &Measurement, t2: &Measurement| t1.base_value().total_cmp(&t2.base_value());
+
+        Self {
+            id: stats
+                .first()
+                .map(|stats| stats.id.clone())
+                .unwrap_or_default(),
+            lower_bound: time(|stats| &stats.lower_bound),
+            upper_bound: time(|stats| &stats.upper_bound),
+            mean: time(|stats| &stats.mean),
+            median: time(|stats| &stats.median),
+            median_abs_dev: time(|stats| &stats.median_abs_dev),
+            slope: slopes.map(|slopes| Measurement::mean(&slopes)),
+            throughput: throughputs.and_then(|throughputs| {
+                let unit = throughputs.first()?.unit;
+                throughputs
+                    .iter()
+                    .all(|throughput| throughput.unit == unit)
+                    .then(|| ThroughputRate {
+                        per_second: throughputs.iter().map(|tp| tp.per_second).sum::<f64>()
+                            / throughputs.len() as f64,
+                        unit,
+                    })
+            }),
+            change: changes.and_then(|changes| {
+                let verdict = changes.first()?.verdict;
+                Some(Change {
+                    // Criterion's verdict can't be averaged, so only keep a unanimous one
+                    verdict: if changes.iter().all(|change| change.verdict == verdict) {
+                        verdict
+                    } else {
+                        ChangeType::NoChange
+                    },
+                    relative: changes.iter().map(|change| change.relative).sum::<f64>()
+                        / changes.len() as f64,
+                })
+            }),
+            std_dev: all(|stats| &stats.std_dev).map(|std_devs| Measurement::mean(&std_devs)),
+            // The extremes of all the samples
+            min: all(|stats| &stats.min).and_then(|mins| mins.into_iter().min_by(by_time)),
+            max: all(|stats| &stats.max).and_then(|maxes| maxes.into_iter().max_by(by_time)),
+            report_directory: stats
+                .first()
+                .map(|stats| stats.report_directory.clone())
+                .unwrap_or_default(),
+        }
     }
 }
 
 // #### Column ###
 
-#[derive(Clone, Debug)]
-struct Column {
-    #[allow(dead_code)]
-    name: FlexStr,
-    time_unit: TimeUnit,
+/// The benchmark in one column of a row (a single cell of a table)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Column {
+    name: AFlexStr,
+    // Named as it was when only times were measured, so older snapshots can still be read
+    #[serde(rename = "time_unit")]
+    measurement: Measurement,
     pct: Comparison,
+    stats: Stats,
+    tags: Vec<AFlexStr>,
+    // The URL of the report, when linked to
+    #[serde(skip)]
+    link: Option<AFlexStr>,
+    // The marker of the speedup tier of the comparison, if in any
+    #[serde(skip)]
+    tier: Option<AFlexStr>,
 }
 
 impl Column {
-    pub fn new(name: FlexStr, time_unit: TimeUnit, first_col_time: Option<TimeUnit>) -> Self {
+    /// The name of the column
+    #[inline]
+    pub fn name(&self) -> &AFlexStr {
+        &self.name
+    }
+
+    /// The time (or other measurement) of the tabulated estimate
+    #[inline]
+    pub fn time(&self) -> &Measurement {
+        &self.measurement
+    }
+
+    /// The lower bound of the confidence interval of the tabulated estimate
+    #[inline]
+    pub fn lower_bound(&self) -> &Measurement {
+        &self.stats.lower_bound
+    }
+
+    /// The upper bound of the confidence interval of the tabulated estimate
+    #[inline]
+    pub fn upper_bound(&self) -> &Measurement {
+        &self.stats.upper_bound
+    }
+
+    /// The comparison to the first column of the row
+    #[inline]
+    pub fn comparison(&self) -> &Comparison {
+        &self.pct
+    }
+
+    /// The throughput, if the benchmark reported any
+    #[inline]
+    pub fn throughput(&self) -> Option<ThroughputRate> {
+        self.stats.throughput
+    }
+
+    /// Criterion's change since the previous run, if known
+    #[inline]
+    pub fn change(&self) -> Option<Change> {
+        self.stats.change
+    }
+
+    /// The tags of the benchmark (from a `#tag1,tag2` id suffix)
+    #[inline]
+    pub fn tags(&self) -> &[AFlexStr] {
+        &self.tags
+    }
+
+    /// The directory criterion wrote the HTML report of the benchmark to (blank if unknown)
+    #[inline]
+    pub fn report_directory(&self) -> &AFlexStr {
+        &self.stats.report_directory
+    }
+
+    pub(crate) fn new(
+        name: AFlexStr,
+        measurement: Measurement,
+        stats: Stats,
+        tags: Vec<AFlexStr>,
+        first_col_time: Option<&Measurement>,
+    ) -> Self {
         let pct = match first_col_time {
-            Some(first_col_time) => Comparison(first_col_time / time_unit),
-            None => Comparison(1.0),
+            Some(first_col_time) => Comparison::new(first_col_time, &measurement),
+            None => Comparison::new(&measurement, &measurement),
         };
 
         Self {
             name,
-            time_unit,
+            measurement,
             pct,
+            stats,
+            tags,
+            link: None,
+            tier: None,
+        }
+    }
+
+    // A column with `time` and `pct` summarizing several benchmarks (this is one of them). Only the
+    // time itself is known for an aggregate
+    fn aggregate(&self, time: Measurement, pct: Comparison) -> Self {
+        let mut col = self.clone();
+        col.stats.lower_bound = time.clone();
+        col.stats.upper_bound = time.clone();
+        col.measurement = time;
+        col.pct = pct;
+        col.stats.throughput = None;
+        col.stats.change = None;
+        col.stats.std_dev = None;
+        col.stats.min = None;
+        col.stats.max = None;
+        col.tier = None;
+        col
+    }
+
+    #[inline]
+    fn cell_data(&self, options: CellOptions) -> CellData<'_> {
+        let throughput = match options.throughput_display {
+            ThroughputDisplay::Off => None,
+            ThroughputDisplay::Alongside | ThroughputDisplay::Instead => self.stats.throughput,
+        };
+        let time_format = options.time_format;
+
+        CellData {
+            time: self.measurement.with_unit(&time_format),
+            compare: self.pct.clone(),
+            lower_bound: self.stats.lower_bound.with_unit(&time_format),
+            upper_bound: self.stats.upper_bound.with_unit(&time_format),
+            tags: &self.tags,
+            throughput,
+            show_time: throughput.is_none()
+                || options.throughput_display != ThroughputDisplay::Instead,
+            change: if options.change_column {
+                self.stats.change
+            } else {
+                None
+            },
+            time_format,
+            comparison_style: options.comparison_style,
+            noise_threshold: options.noise_threshold,
+            role: CellRole::Normal,
+            relative_time: 1.0,
+            extra_stats: ExtraStats {
+                std_dev: options
+                    .extra_stats
+                    .stddev
+                    .then(|| self.stats.std_dev.clone())
+                    .flatten(),
+                median_abs_dev: options
+                    .extra_stats
+                    .mad
+                    .then(|| self.stats.median_abs_dev.clone()),
+                min: options
+                    .extra_stats
+                    .min
+                    .then(|| self.stats.min.clone())
+                    .flatten(),
+                max: options
+                    .extra_stats
+                    .max
+                    .then(|| self.stats.max.clone())
+                    .flatten(),
+            }
+            .with_unit(&time_format),
+            link: if options.link_cells {
+                self.link.as_ref()
+            } else {
+                None
+            },
+            // Noise isn't marked as faster or slower
+            tier: match &self.tier {
+                _ if !options.tiered => TierMarker::Untiered,
+                Some(marker)
+                    if !options
+                        .noise_threshold
+                        .is_some_and(|threshold| self.pct.is_noise(threshold)) =>
+                {
+                    TierMarker::Marked(marker)
+                }
+                _ => TierMarker::Unmarked,
+            },
         }
     }
 
-    // This returns the "width" of the resulting text in chars. Since we don't know how it will be
-    // formatted we return width of: TimeUnit + Percent. Any additional spaces or formatting chars
-    // are not considered and must be added by the formatter
+    // This returns the display "width" of the resulting text. Since we don't know how it will be
+    // formatted we return width of: Measurement + Throughput (as `time, throughput` when both are
+    // shown) + extra stats (as `, σ time` each) + Percent + change (as ` XX +1.23%`, with a symbol
+    // up to two columns wide) + tags (as ` [tag1, tag2]`) + any link (as `[...](link)`) + any
+    // tier marker (as `marker `). Any additional spaces or formatting chars are not considered and
+    // must be added by the formatter
     #[inline]
-    pub fn width(&self) -> usize {
-        self.time_unit.width() + self.pct.width()
+    pub(crate) fn width(&self, options: CellOptions) -> usize {
+        let cell = self.cell_data(options);
+        let measurement_width = display_width(&cell.measurement());
+
+        let change_width = match cell.change {
+            Some(change) => change.width() + 4,
+            None => 0,
+        };
+
+        measurement_width
+            + display_width(&cell.compare_str())
+            + change_width
+            + tags_width(&self.tags)
+            + link_width(cell.link)
+            + match cell.tier {
+                TierMarker::Marked(marker) => display_width(marker) + 1,
+                TierMarker::Untiered | TierMarker::Unmarked => 0,
+            }
     }
 }
 
+// ### Cell Options ###
+
+// The options of a table that change how each of its cells is displayed
+#[derive(Clone, Copy, Debug, Default)]
+struct CellOptions {
+    throughput_display: ThroughputDisplay,
+    change_column: bool,
+    time_format: TimeFormat,
+    comparison_style: ComparisonStyle,
+    noise_threshold: Option<f64>,
+    extra_stats: ExtraStatOptions,
+    link_cells: bool,
+    tiered: bool,
+}
+
 // ### Row ###
 
-#[derive(Clone, Debug)]
-struct Row {
-    name: FlexStr,
-    column_data: IndexMap<FlexStr, Column>,
+/// A row of a table, with the benchmark of each of its columns
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Row {
+    name: AFlexStr,
+    column_data: IndexMap<AFlexStr, Column>,
 }
 
 impl Row {
     #[inline]
-    pub fn new(name: FlexStr) -> Self {
+    pub(crate) fn new(name: AFlexStr) -> Self {
         Self {
             name,
             column_data: Default::default(),
         }
     }
 
+    /// The name of the row (may be blank)
+    #[inline]
+    pub fn name(&self) -> &AFlexStr {
+        &self.name
+    }
+
+    /// Each cell of the row, in column order. Columns the row has no benchmark for are skipped
+    #[inline]
+    pub fn cells(&self) -> impl Iterator<Item = &Column> {
+        self.column_data.values()
+    }
+
+    /// The cell of the column named `column`, if the row has one
+    #[inline]
+    pub fn cell(&self, column: &str) -> Option<&Column> {
+        self.column_data.get(&column.to_flex())
+    }
+
+    // The report link of the row name: that of its first cell
+    #[inline]
+    fn link(&self) -> Option<&AFlexStr> {
+        self.column_data.values().next()?.link.as_ref()
+    }
+
+    // Returns the names of the fastest and slowest columns of the row (none if they are all the same)
+    fn extremes(&self) -> Option<(&AFlexStr, &AFlexStr)> {
+        let by_ratio = |(_, col1): &(&AFlexStr, &Column), (_, col2): &(&AFlexStr, &Column)| {
+            col1.pct.ratio().total_cmp(&col2.pct.ratio())
+        };
+        let (best, best_col) = self.column_data.iter().max_by(by_ratio)?;
+        let (worst, worst_col) = self.column_data.iter().min_by(by_ratio)?;
+
+        (best_col.pct.ratio() != worst_col.pct.ratio()).then_some((best, worst))
+    }
+
+    // The comparison ratio of the fastest column of the row (NaN if the row is empty)
+    fn best_ratio(&self) -> f64 {
+        self.column_data
+            .values()
+            .map(|col| col.pct.ratio())
+            .fold(f64::NAN, f64::max)
+    }
+
     // NOTE: The 'first' column here reflects the first column seen for THIS row NOT for the whole table
     // This means our timings COULD be based off different columns in different rows
-    fn first_column_time(&self) -> Option<TimeUnit> {
+    fn first_column_time(&self) -> Option<&Measurement> {
         self.column_data
             .first()
-            .map(|(_, Column { time_unit, .. })| *time_unit)
+            .map(|(_, Column { measurement, .. })| measurement)
     }
 
-    fn add_column(&mut self, name: FlexStr, time_unit: TimeUnit) -> anyhow::Result<&Column> {
-        let first_time = self.first_column_time();
+    fn add_column(
+        &mut self,
+        name: AFlexStr,
+        measurement: Measurement,
+        stats: Stats,
+        tags: Vec<AFlexStr>,
+    ) -> Result<&Column, Error> {
+        let first_time = self.first_column_time().cloned();
 
         match self.column_data.entry(name.clone()) {
-            Entry::Occupied(_) => Err(anyhow!("Duplicate column: {name}")),
+            Entry::Occupied(_) => Err(Error::DuplicateColumn {
+                column: name.to_string(),
+            }),
             Entry::Vacant(entry) => {
-                let col = Column::new(name, time_unit, first_time);
+                let col = Column::new(name, measurement, stats, tags, first_time.as_ref());
                 Ok(entry.insert(col))
             }
         }
     }
+
+    // Recalculate each comparison, since the first column may have changed. Throughput is compared
+    // instead of time, if requested and both benchmarks have throughput in the same unit
+    fn update_comparisons(&mut self, by_throughput: bool) {
+        let first = self.column_data.first().map(|(_, col)| col.clone());
+
+        if let Some(first) = first {
+            for col in self.column_data.values_mut() {
+                col.pct = match (first.stats.throughput, col.stats.throughput) {
+                    (Some(first_tp), Some(tp)) if by_throughput && first_tp.unit == tp.unit => {
+                        Comparison::with_times(
+                            tp.per_second / first_tp.per_second,
+                            &first.measurement,
+                            &col.measurement,
+                        )
+                    }
+                    _ => Comparison::new(&first.measurement, &col.measurement),
+                };
+            }
+        }
+    }
 }
 
 // ### Column Info Map ###
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 struct ColumnInfoVec(Vec<ColumnInfo>);
 
 impl ColumnInfoVec {
-    pub fn update_column_info(&mut self, idx: usize, name: FlexStr, width: usize) {
+    pub fn update_column_info(&mut self, idx: usize, name: AFlexStr, width: usize) {
         match self.0.iter_mut().find(|col| col.name == name) {
             Some(col_info) => col_info.update_info(width),
             None => self.0.insert(idx, ColumnInfo::new(name, width)),
@@ -392,253 +1440,2979 @@ impl ColumnInfoVec {
 
 // ### Table ###
 
-#[derive(Clone, Debug)]
-struct Table {
-    name: FlexStr,
+/// A table of benchmarks, laid out in rows and columns
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Table {
+    name: AFlexStr,
     columns: ColumnInfoVec,
-    rows: IndexMap<FlexStr, Row>,
+    rows: IndexMap<AFlexStr, Row>,
+    throughput_display: ThroughputDisplay,
+    change_column: bool,
+    #[serde(default)]
+    time_format: TimeFormat,
+    #[serde(default)]
+    comparison_style: ComparisonStyle,
+    #[serde(default)]
+    highlight: Highlight,
+    #[serde(default)]
+    noise_threshold: Option<f64>,
+    #[serde(default)]
+    extra_stats: ExtraStatOptions,
+    geomean_row: bool,
+    #[serde(default)]
+    footnotes: Vec<Footnote>,
+    // The footnote marker of each column and row name with a footnote
+    #[serde(default)]
+    column_markers: HashMap<AFlexStr, AFlexStr>,
+    #[serde(default)]
+    row_markers: HashMap<AFlexStr, AFlexStr>,
+    // The group of each column in one and the name it is displayed as within the group
+    #[serde(default)]
+    column_groups: HashMap<AFlexStr, (AFlexStr, AFlexStr)>,
+    #[serde(default)]
+    link_target: LinkTarget,
+    #[serde(default)]
+    aggregate_rows: Vec<AggregateRow>,
+    #[serde(default)]
+    speedup_tiers: Vec<SpeedupTier>,
 }
 
 impl Table {
     #[inline]
-    pub fn new(name: FlexStr) -> Self {
+    pub(crate) fn new(name: AFlexStr) -> Self {
         Self {
             name,
             columns: Default::default(),
             rows: Default::default(),
+            throughput_display: Default::default(),
+            change_column: false,
+            time_format: Default::default(),
+            comparison_style: Default::default(),
+            highlight: Default::default(),
+            noise_threshold: None,
+            extra_stats: Default::default(),
+            geomean_row: false,
+            footnotes: Vec::new(),
+            column_markers: HashMap::new(),
+            row_markers: HashMap::new(),
+            column_groups: HashMap::new(),
+            link_target: LinkTarget::None,
+            aggregate_rows: Vec::new(),
+            speedup_tiers: Vec::new(),
+        }
+    }
+
+    /// The name of the table
+    #[inline]
+    pub fn name(&self) -> &AFlexStr {
+        &self.name
+    }
+
+    /// The names of the columns, in display order
+    #[inline]
+    pub fn column_names(&self) -> impl Iterator<Item = &AFlexStr> {
+        // The first column is blank - it only holds the row names
+        self.columns.0.iter().skip(1).map(|col| &col.name)
+    }
+
+    /// Each row of the table, in display order
+    #[inline]
+    pub fn rows(&self) -> impl Iterator<Item = &Row> {
+        self.rows.values()
+    }
+
+    /// The row named `name`, if any
+    #[inline]
+    pub fn row(&self, name: &str) -> Option<&Row> {
+        self.rows.get(&name.to_flex())
+    }
+
+    #[inline]
+    fn cell_options(&self) -> CellOptions {
+        CellOptions {
+            throughput_display: self.throughput_display,
+            change_column: self.change_column,
+            time_format: self.time_format,
+            comparison_style: self.comparison_style,
+            noise_threshold: self.noise_threshold,
+            extra_stats: self.extra_stats,
+            link_cells: self.link_target == LinkTarget::Cell,
+            tiered: !self.speedup_tiers.is_empty(),
         }
     }
 
-    pub fn add_column_data(
+    pub(crate) fn add_column_data(
         &mut self,
         idx: usize,
-        column_name: FlexStr,
-        row_name: FlexStr,
-        time: TimeUnit,
-    ) -> anyhow::Result<()> {
+        column_name: AFlexStr,
+        row_name: AFlexStr,
+        time: Measurement,
+        stats: Stats,
+        tags: Vec<AFlexStr>,
+    ) -> Result<(), Error> {
+        self.check_kind(&time)?;
         // Assume we have a blank named first column just for holding the row name
         self.columns
-            .update_column_info(0, Default::default(), row_name.chars().count());
+            .update_column_info(0, Default::default(), display_width(&row_name));
 
         let row = self.get_row(row_name);
-        let col = row.add_column(column_name.clone(), time)?;
+        let col = row.add_column(column_name.clone(), time, stats, tags)?;
 
         // Use either the width of the data or the name, whichever is larger
-        let width = max(col.width(), column_name.chars().count());
+        let width = max(col.width(Default::default()), display_width(&column_name));
         self.columns.update_column_info(idx, column_name, width);
         Ok(())
     }
 
-    fn get_row(&mut self, name: FlexStr) -> &mut Row {
-        match self.rows.entry(name.clone()) {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(Row::new(name)),
+    // Every measurement of a table must be of the same kind, so that they can be compared across
+    // rows and columns (ex: transposed, or in a geomean)
+    fn check_kind(&self, measurement: &Measurement) -> Result<(), Error> {
+        let first = self
+            .rows
+            .values()
+            .flat_map(|row| row.column_data.values())
+            .next();
+
+        match first {
+            Some(first) if !first.measurement.same_kind(measurement) => {
+                Err(Error::MixedMeasurements {
+                    table: self.name.to_string(),
+                    first: first.measurement.kind().to_string(),
+                    second: measurement.kind().to_string(),
+                })
+            }
+            _ => Ok(()),
         }
     }
-}
 
-// ### Column Position ###
-
-#[derive(Default, Debug)]
-struct ColumnPosition(IndexMap<(FlexStr, FlexStr), usize>);
+    // Link each cell (or row name) to the report of its benchmark
+    fn set_report_links(&mut self, options: &ReportLinkOptions) {
+        self.link_target = options.link;
+        if options.link == LinkTarget::None {
+            return;
+        }
 
-impl ColumnPosition {
-    pub fn next_idx(&mut self, table_name: FlexStr, row_name: FlexStr) -> usize {
-        match self.0.entry((table_name, row_name)) {
-            Entry::Occupied(mut entry) => {
-                *entry.get_mut() += 1;
-                *entry.get()
-            }
-            Entry::Vacant(entry) => *entry.insert(1),
+        for col in self
+            .rows
+            .values_mut()
+            .flat_map(|row| row.column_data.values_mut())
+        {
+            col.link = options.url(&col.stats.report_directory);
         }
     }
-}
 
-// ### Criterion Table Data ###
+    // Mark the comparison of each cell with its speedup tier
+    fn set_speedup_tiers(&mut self, tiers: &[SpeedupTier]) {
+        self.speedup_tiers = tiers.to_vec();
+        if tiers.is_empty() {
+            return;
+        }
 
-/// Fully processed Criterion benchmark data ready for formatting
-#[derive(Clone, Debug)]
-pub struct CriterionTableData {
-    tables: IndexMap<FlexStr, Table>,
-}
+        for col in self
+            .rows
+            .values_mut()
+            .flat_map(|row| row.column_data.values_mut())
+        {
+            col.tier = tier_marker(tiers, &col.pct).cloned();
+        }
+    }
 
-impl CriterionTableData {
-    /// Build table data from the input raw Criterion data
-    pub fn from_raw(raw_data: &[RawCriterionData]) -> anyhow::Result<Self> {
-        let mut data = Self {
-            tables: Default::default(),
+    // Recalculate the display width of every column (ex: after names have changed)
+    fn update_widths(&mut self) {
+        for col_info in &mut self.columns.0 {
+            col_info.max_width = display_width(&marked_name(&col_info.name, &self.column_markers));
+        }
+        let options = self.cell_options();
+        // Any cell could be highlighted, so room for the marker is left in all of them
+        let marker_width = match self.highlight {
+            Highlight::Off => 0,
+            Highlight::Best | Highlight::BestWorst => ROLE_MARKER_WIDTH,
         };
 
-        data.build_from_raw_data(raw_data)?;
-        Ok(data)
-    }
+        for row in self.rows.values() {
+            // Assume we have a blank named first column just for holding the row name
+            if let Some(first_col) = self.columns.0.first_mut() {
+                let link = match self.link_target {
+                    LinkTarget::Row => row.link(),
+                    LinkTarget::None | LinkTarget::Cell => None,
+                };
+                first_col.update_info(
+                    display_width(&marked_name(&row.name, &self.row_markers)) + link_width(link),
+                );
+            }
 
-    fn build_from_raw_data(&mut self, raw_data: &[RawCriterionData]) -> anyhow::Result<()> {
+            for (name, col) in &row.column_data {
+                let col_info = self.columns.0.iter_mut().skip(1).find(|c| &c.name == name);
+                if let Some(col_info) = col_info {
+                    col_info.update_info(marker_width + col.width(options));
+                }
+            }
+        }
+
+        if self.geomean_row {
+            if let Some(first_col) = self.columns.0.first_mut() {
+                first_col.update_info(display_width(GEOMEAN_ROW_NAME));
+            }
+        }
+
+        for aggregate in &self.aggregate_rows {
+            if let Some(first_col) = self.columns.0.first_mut() {
+                first_col.update_info(display_width(aggregate.name()));
+            }
+        }
+        if self.aggregate_rows.contains(&AggregateRow::GeomeanTime) {
+            for idx in 1..self.columns.0.len() {
+                let col = self.geomean_time(&self.columns.0[idx].name);
+                if let Some(col) = col {
+                    self.columns.0[idx].update_info(marker_width + col.width(options));
+                }
+            }
+        }
+    }
+
+    // Number the footnotes of the columns, then the rows, of this table in display order, so this
+    // must be done after they are final. Columns and rows with the same footnote share its marker
+    fn set_footnotes(&mut self, table: Option<&FootnoteOptions>, all: &FootnoteOptions) {
+        let columns: Vec<_> = self
+            .columns
+            .0
+            .iter()
+            .skip(1)
+            .filter_map(|col| {
+                let text = FootnoteOptions::find(table, all, |notes| &notes.columns, &col.name)?;
+                Some((col.name.clone(), text))
+            })
+            .collect();
+        let rows: Vec<_> = self
+            .rows
+            .keys()
+            .filter_map(|name| {
+                let text = FootnoteOptions::find(table, all, |notes| &notes.rows, name)?;
+                Some((name.clone(), text))
+            })
+            .collect();
+
+        for (name, text) in columns {
+            let marker = self.footnote_marker(text);
+            self.column_markers.insert(name, marker);
+        }
+        for (name, text) in rows {
+            let marker = self.footnote_marker(text);
+            self.row_markers.insert(name, marker);
+        }
+    }
+
+    // Find the group of each column, if any. This must be done after the columns are final
+    fn set_column_groups(&mut self, options: &ColumnGroupOptions) {
+        self.column_groups = self
+            .columns
+            .0
+            .iter()
+            .skip(1)
+            .filter_map(|col| Some((col.name.clone(), options.find(&col.name)?)))
+            .collect();
+    }
+
+    // Each run of adjacent columns in the same group, in column order (columns not in a group have
+    // a blank group). This is empty if no column is in a group
+    fn column_group_spans(&self) -> Vec<ColumnGroup> {
+        let mut spans: Vec<ColumnGroup> = Vec::new();
+        if self.column_groups.is_empty() {
+            return spans;
+        }
+
+        for col in self.columns.0.iter().skip(1) {
+            let name = match self.column_groups.get(&col.name) {
+                Some((group, _)) => group.clone(),
+                None => AFlexStr::default(),
+            };
+
+            match spans.last_mut() {
+                Some(span) if span.name == name => span.span += 1,
+                _ => spans.push(ColumnGroup { name, span: 1 }),
+            }
+        }
+
+        spans
+    }
+
+    // The name column `name` is displayed as: without its group if the groups are displayed, and
+    // with its footnote marker, if any
+    fn display_column_name(&self, name: &AFlexStr, grouped: bool) -> AFlexStr {
+        let display_name = match self.column_groups.get(name) {
+            Some((_, short_name)) if grouped => short_name,
+            _ => name,
+        };
+
+        match self.column_markers.get(name) {
+            Some(marker) => a_flex_fmt!("{display_name} {marker}"),
+            None => display_name.clone(),
+        }
+    }
+
+    // The marker of the footnote with `text`, added as the next footnote if it is new
+    fn footnote_marker(&mut self, text: AFlexStr) -> AFlexStr {
+        if let Some(footnote) = self.footnotes.iter().find(|note| note.text == text) {
+            return footnote.marker.clone();
+        }
+
+        let marker = a_flex_fmt!("[{}]", self.footnotes.len() + 1);
+        self.footnotes.push(Footnote {
+            marker: marker.clone(),
+            text,
+        });
+        marker
+    }
+
+    // Recalculate the comparisons of every row (ex: after the first column of a row has changed)
+    fn update_comparisons(&mut self) {
+        let by_throughput = self.throughput_display != ThroughputDisplay::Off;
+
+        for row in self.rows.values_mut() {
+            row.update_comparisons(by_throughput);
+        }
+    }
+
+    // Remove each cell with any of the given tags, along with any rows or columns left empty
+    fn exclude_tags(&mut self, tags: &[AFlexStr]) {
+        self.retain_cells(|_, col| !col.tags.iter().any(|tag| tags.contains(tag)));
+    }
+
+    // Remove each cell whose benchmark id doesn't match any `include` pattern (if there are any) or
+    // matches any `exclude` pattern. Cells without a recorded id are always kept
+    fn filter_ids(&mut self, include: &[IdPattern], exclude: &[IdPattern]) {
+        self.retain_cells(|_, col| {
+            let id = &col.stats.id;
+            id.is_empty()
+                || ((include.is_empty() || include.iter().any(|pattern| pattern.is_match(id)))
+                    && !exclude.iter().any(|pattern| pattern.is_match(id)))
+        });
+    }
+
+    // Remove each cell in any of the named columns, along with any rows left empty
+    fn hide_columns<'a>(&mut self, names: impl Iterator<Item = &'a AFlexStr> + Clone) {
+        self.retain_cells(|name, _| !names.clone().any(|hidden| hidden == name));
+    }
+
+    // Keep only the cells `keep` returns true for, removing any rows or columns left empty
+    fn retain_cells(&mut self, keep: impl Fn(&AFlexStr, &Column) -> bool) {
+        for row in self.rows.values_mut() {
+            row.column_data.retain(|name, col| keep(name, col));
+        }
+        self.update_comparisons();
+        self.rows.retain(|_, row| !row.column_data.is_empty());
+
+        let rows = &self.rows;
+        let mut idx = 0;
+        // Assume we have a blank named first column just for holding the row name
+        self.columns.0.retain(|col| {
+            idx += 1;
+            idx == 1
+                || rows
+                    .values()
+                    .any(|row| row.column_data.contains_key(&col.name))
+        });
+    }
+
+    // Swap rows and columns. Each cell keeps its comparison, so this must be done after any
+    // comparisons are recalculated
+    fn transpose(&mut self) {
+        let mut rows: IndexMap<AFlexStr, Row> = IndexMap::new();
+
+        // Assume we have a blank named first column just for holding the row name
+        for col_info in self.columns.0.iter().skip(1) {
+            rows.insert(col_info.name.clone(), Row::new(col_info.name.clone()));
+        }
+
+        let mut columns = vec![ColumnInfo::new(Default::default(), 0)];
+        for row in self.rows.values() {
+            columns.push(ColumnInfo::new(row.name.clone(), 0));
+
+            for (name, col) in &row.column_data {
+                if let Some(new_row) = rows.get_mut(name) {
+                    let mut col = col.clone();
+                    col.name = row.name.clone();
+                    new_row.column_data.insert(row.name.clone(), col);
+                }
+            }
+        }
+
+        self.rows = rows;
+        self.columns = ColumnInfoVec(columns);
+    }
+
+    // Replace the rows with one per column, comparing each column to the column of the row (in the
+    // row named `row_name`, or across all rows via geometric means). Nothing changes if the row
+    // isn't found
+    fn make_matrix(&mut self, row_name: Option<&AFlexStr>) {
+        let source: Vec<_> = match row_name {
+            Some(name) => self.rows.get(name).into_iter().collect(),
+            None => self.rows.values().collect(),
+        };
+        if source.is_empty() {
+            return;
+        }
+
+        // Assume we have a blank named first column just for holding the row name
+        let names: Vec<_> = self.columns.0.iter().skip(1).map(|col| &col.name).collect();
+        let mut rows = IndexMap::with_capacity(names.len());
+
+        for &baseline in &names {
+            let mut row = Row::new(baseline.clone());
+
+            for &name in &names {
+                // The benchmarks of both columns, in each row that has both
+                let pairs: Vec<_> = source
+                    .iter()
+                    .filter_map(|row| {
+                        Some((row.column_data.get(baseline)?, row.column_data.get(name)?))
+                    })
+                    .collect();
+
+                let col = match pairs.as_slice() {
+                    [] => continue,
+                    [(baseline, col)] => {
+                        let mut col = (*col).clone();
+                        col.pct = Comparison::new(&baseline.measurement, &col.measurement);
+                        col
+                    }
+                    [(_, first), ..] => {
+                        let baseline_time =
+                            Measurement::geomean(pairs.iter().map(|(col, _)| &col.measurement));
+                        let time =
+                            Measurement::geomean(pairs.iter().map(|(_, col)| &col.measurement));
+                        let compare = Comparison::new(&baseline_time, &time);
+                        first.aggregate(time, compare)
+                    }
+                };
+                row.column_data.insert(name.clone(), col);
+            }
+
+            rows.insert(baseline.clone(), row);
+        }
+
+        self.rows = rows;
+    }
+
+    fn sort_rows(&mut self, sort: RowSort, descending: bool) {
+        // The largest comparison of the row, or 1.0 when there is only a baseline
+        let speedup = |row: &Row| {
+            row.column_data
+                .values()
+                .skip(1)
+                .map(|col| col.pct.ratio())
+                .fold(1.0, f64::max)
+        };
+        let time = |row: &Row| row.first_column_time().map_or(0.0, |t| t.base_value());
+
+        self.rows.sort_by(|_, row1, _, row2| {
+            let ordering = match sort {
+                RowSort::Name => natural_cmp(&row1.name, &row2.name),
+                RowSort::Time => time(row1).total_cmp(&time(row2)),
+                RowSort::Speedup => speedup(row1).total_cmp(&speedup(row2)),
+            };
+
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    // Move the named columns (in the given order) before all others, which keep their relative
+    // order. This changes the first column of rows, so comparisons are recalculated
+    fn order_columns(&mut self, order: &[AFlexStr]) {
+        let rank = |name: &AFlexStr| {
+            order
+                .iter()
+                .position(|ordered| ordered == name)
+                .unwrap_or(order.len())
+        };
+
+        // Assume we have a blank named first column just for holding the row name
+        if let Some(columns) = self.columns.0.get_mut(1..) {
+            columns.sort_by_key(|col| rank(&col.name));
+        }
+        for row in self.rows.values_mut() {
+            row.column_data
+                .sort_by(|name1, _, name2, _| rank(name1).cmp(&rank(name2)));
+        }
+
+        self.update_comparisons();
+    }
+
+    fn fix_widths(&mut self, min_width: Option<usize>, multiple: Option<usize>) {
+        // Assume we have a blank named first column just for holding the row name
+        if let Some(min_width) = min_width {
+            for col_info in self.columns.0.iter_mut().skip(1) {
+                col_info.update_info(min_width);
+            }
+        }
+
+        if let Some(multiple) = multiple.filter(|&multiple| multiple > 1) {
+            for col_info in &mut self.columns.0 {
+                col_info.max_width = col_info.max_width.div_ceil(multiple) * multiple;
+            }
+        }
+    }
+
+    fn rename_columns(&mut self, rename: impl Fn(&AFlexStr) -> AFlexStr) {
+        for col_info in self.columns.0.iter_mut().skip(1) {
+            col_info.name = rename(&col_info.name);
+        }
+
+        for row in self.rows.values_mut() {
+            row.column_data = row
+                .column_data
+                .drain(..)
+                .map(|(name, mut col)| {
+                    col.name = rename(&name);
+                    (col.name.clone(), col)
+                })
+                .collect();
+        }
+    }
+
+    fn rename_rows(&mut self, rename: impl Fn(&AFlexStr) -> AFlexStr) {
+        self.rows = self
+            .rows
+            .drain(..)
+            .map(|(name, mut row)| {
+                row.name = rename(&name);
+                (row.name.clone(), row)
+            })
+            .collect();
+    }
+
+    fn strip_common_affixes(&mut self) {
+        let col_names: Vec<_> = self.columns.0.iter().skip(1).map(|c| &c.name).collect();
+        if let Some((prefix, suffix)) = common_affixes(&col_names) {
+            self.rename_columns(|name| name[prefix..name.len() - suffix].to_flex());
+        }
+
+        let row_names: Vec<_> = self.rows.keys().collect();
+        if let Some((prefix, suffix)) = common_affixes(&row_names) {
+            self.rename_rows(|name| name[prefix..name.len() - suffix].to_flex());
+        }
+    }
+
+    // The lowest comparison (the worst regression) of the row, ignoring its baseline
+    fn worst_comparison(row: &Row) -> f64 {
+        row.column_data
+            .values()
+            .skip(1)
+            .map(|col| col.pct.ratio())
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    // Tag the last cell of each row that got slower column over column across its last
+    // `MONOTONIC_RUNS` columns. Only used in series mode, where each column is a run
+    fn tag_monotonic_regressions(&mut self) {
+        for row in self.rows.values_mut() {
+            let cols: Vec<_> = row.column_data.values().collect();
+            let regressing = cols.len() >= MONOTONIC_RUNS
+                && cols[cols.len() - MONOTONIC_RUNS..].windows(2).all(|pair| {
+                    pair[1].measurement.base_value() > pair[0].measurement.base_value()
+                });
+
+            if regressing {
+                if let Some((_, col)) = row.column_data.last_mut() {
+                    col.tags.push(REGRESSING_TAG.into());
+                }
+            }
+        }
+    }
+
+    // True if any benchmark changed significantly: compared to the previous run (the first column)
+    // in series mode, otherwise criterion's own verdict on the change since its previous run
+    fn has_changes(&self, series: bool) -> bool {
+        self.rows.values().any(|row| {
+            row.column_data.values().enumerate().any(|(idx, col)| {
+                if series {
+                    idx > 0 && col.pct.is_significant()
+                } else {
+                    matches!(col.stats.change, Some(change) if change.verdict != ChangeType::NoChange)
+                }
+            })
+        })
+    }
+
+    // Keep only rows with a regression larger than `threshold` (sorted worst first), their
+    // baseline columns and the regressed columns. Returns the worst regression, if any remain
+    fn retain_regressions(&mut self, threshold: f64) -> Option<f64> {
+        let slower_than = 1.0 / (1.0 + threshold);
+        self.rows
+            .retain(|_, row| Self::worst_comparison(row) < slower_than);
+        self.rows.sort_by(|_, row1, _, row2| {
+            Self::worst_comparison(row1).total_cmp(&Self::worst_comparison(row2))
+        });
+
+        let mut keep = HashSet::new();
+        for row in self.rows.values() {
+            for (idx, (name, col)) in row.column_data.iter().enumerate() {
+                if idx == 0 || col.pct.ratio() < slower_than {
+                    keep.insert(name.clone());
+                }
+            }
+        }
+
+        // Assume we have a blank named first column just for holding the row name
+        let mut idx = 0;
+        self.columns.0.retain(|col| {
+            idx += 1;
+            idx == 1 || keep.contains(&col.name)
+        });
+        for row in self.rows.values_mut() {
+            row.column_data.retain(|name, _| keep.contains(name));
+        }
+
+        self.rows
+            .values()
+            .map(Self::worst_comparison)
+            .reduce(f64::min)
+    }
+
+    // Returns the geometric mean (the only mean that makes sense for ratios) of the comparisons of
+    // the named column across all rows that have it
+    fn geomean(&self, column_name: &AFlexStr) -> Option<Comparison> {
+        let cols = self
+            .rows
+            .values()
+            .filter_map(|row| row.column_data.get(column_name));
+        let first = cols.clone().next()?;
+        let (count, log_sum) = cols.fold((0, 0.0), |(count, sum), col| {
+            (count + 1, sum + col.pct.ratio().ln())
+        });
+
+        Some(first.pct.summary((log_sum / count as f64).exp()))
+    }
+
+    // Returns a column holding the geometric mean of the times of the named column across all rows
+    // that have it, compared as the geometric mean of its comparisons
+    fn geomean_time(&self, column_name: &AFlexStr) -> Option<Column> {
+        let cols = self
+            .rows
+            .values()
+            .filter_map(|row| row.column_data.get(column_name));
+        let first = cols.clone().next()?;
+        let time = Measurement::geomean(cols.map(|col| &col.measurement));
+        let mut col = first.aggregate(time, self.geomean(column_name)?);
+        col.tier = tier_marker(&self.speedup_tiers, &col.pct).cloned();
+        Some(col)
+    }
+
+    // Returns the comparison summarizing the named column across all rows that have it, for the
+    // aggregate rows shown as comparisons
+    fn aggregate(&self, aggregate: AggregateRow, column_name: &AFlexStr) -> Option<Comparison> {
+        let cols = self
+            .rows
+            .values()
+            .filter_map(|row| row.column_data.get(column_name));
+        let first = cols.clone().next()?;
+        let ratios = cols.map(|col| col.pct.ratio());
+
+        let ratio = match aggregate {
+            AggregateRow::GeomeanTime => return self.geomean(column_name),
+            AggregateRow::MeanSpeedup => {
+                let (count, sum) =
+                    ratios.fold((0, 0.0), |(count, sum), ratio| (count + 1, sum + ratio));
+                (count > 0).then(|| sum / count as f64)
+            }
+            AggregateRow::Best => ratios.reduce(f64::max),
+            AggregateRow::Worst => ratios.reduce(f64::min),
+        };
+        ratio.map(|ratio| first.pct.summary(ratio))
+    }
+
+    fn get_row(&mut self, name: AFlexStr) -> &mut Row {
+        match self.rows.entry(name.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Row::new(name)),
+        }
+    }
+}
+
+// ### Common Affixes ###
+
+const AFFIX_SEPARATORS: &[char] = &['_', '-', ' ', '.', ':', '/'];
+
+// Returns the length (in bytes) of the prefix and suffix shared by all names, if any. These only
+// extend up to (and include) a separator so words are never split, and never cover a whole name
+fn common_affixes(names: &[&AFlexStr]) -> Option<(usize, usize)> {
+    let (first, rest) = names.split_first()?;
+    if rest.is_empty() {
+        return None;
+    }
+
+    let (mut prefix, mut suffix): (&str, &str) = (first, first);
+    for name in rest {
+        while !name.starts_with(prefix) {
+            prefix = &prefix[..prefix.char_indices().last().map_or(0, |(idx, _)| idx)];
+        }
+        while !name.ends_with(suffix) {
+            suffix = &suffix[suffix.chars().next().map_or(0, char::len_utf8)..];
+        }
+    }
+
+    let prefix = prefix.rfind(AFFIX_SEPARATORS).map_or(0, |idx| idx + 1);
+    let suffix = suffix
+        .find(AFFIX_SEPARATORS)
+        .map_or(0, |idx| suffix.len() - idx);
+    let shortest = names.iter().map(|name| name.len()).min()?;
+
+    match (prefix, suffix) {
+        (0, 0) => None,
+        (prefix, suffix) if prefix + suffix < shortest => Some((prefix, suffix)),
+        (prefix, _) if prefix > 0 && prefix < shortest => Some((prefix, 0)),
+        (_, suffix) if suffix > 0 && suffix < shortest => Some((0, suffix)),
+        _ => None,
+    }
+}
+
+// ### Natural Order ###
+
+// Compares names such that any runs of digits are compared by value (ex: `10` < `20` < `100`)
+fn natural_cmp(name1: &str, name2: &str) -> Ordering {
+    // Split into alternating runs of digits and non-digits
+    fn chunks(name: &str) -> impl Iterator<Item = &str> {
+        let mut rest = name;
+        std::iter::from_fn(move || {
+            let first = rest.chars().next()?;
+            let end = rest
+                .find(|ch: char| ch.is_ascii_digit() != first.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (chunk, remaining) = rest.split_at(end);
+            rest = remaining;
+            Some(chunk)
+        })
+    }
+
+    let mut chunks1 = chunks(name1);
+    let mut chunks2 = chunks(name2);
+
+    loop {
+        let ordering = match (chunks1.next(), chunks2.next()) {
+            (None, None) => return name1.cmp(name2),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(chunk1), Some(chunk2)) => {
+                let is_number = |chunk: &str| chunk.starts_with(|ch: char| ch.is_ascii_digit());
+
+                if is_number(chunk1) && is_number(chunk2) {
+                    // Compare by value without parsing, so any number of digits is fine
+                    let (num1, num2) = (
+                        chunk1.trim_start_matches('0'),
+                        chunk2.trim_start_matches('0'),
+                    );
+                    num1.len().cmp(&num2.len()).then_with(|| num1.cmp(num2))
+                } else {
+                    chunk1.cmp(chunk2)
+                }
+            }
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+// ### Column Position ###
+
+#[derive(Default, Debug)]
+struct ColumnPosition(IndexMap<(AFlexStr, AFlexStr), usize>);
+
+impl ColumnPosition {
+    pub fn next_idx(&mut self, table_name: AFlexStr, row_name: AFlexStr) -> usize {
+        match self.0.entry((table_name, row_name)) {
+            Entry::Occupied(mut entry) => {
+                *entry.get_mut() += 1;
+                *entry.get()
+            }
+            Entry::Vacant(entry) => *entry.insert(1),
+        }
+    }
+}
+
+// ### Criterion Table Data ###
+
+/// Fully processed Criterion benchmark data ready for formatting
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CriterionTableData {
+    tables: IndexMap<AFlexStr, Table>,
+    metric: Metric,
+    // True when each column is a run, so the first column of each row is its baseline
+    series: bool,
+    #[serde(skip)]
+    summary: ProcessingSummary,
+}
+
+impl CriterionTableData {
+    /// Build table data from the input raw Criterion data, tabulating the `metric` estimate of each
+    /// benchmark. Benchmark ids are split into table, column, and row names via `id_parser` (ex:
+    /// `&IdScheme::default()`)
+    pub fn from_raw(
+        raw_data: &[RawCriterionData],
+        metric: Metric,
+        id_parser: &dyn IdParser,
+    ) -> Result<Self, Error> {
+        let mut data = Self {
+            tables: Default::default(),
+            metric,
+            series: false,
+            summary: Default::default(),
+        };
+
+        data.build_from_raw_data(raw_data, id_parser, Err)?;
+        Ok(data)
+    }
+
+    /// Like `from_raw`, but benchmarks that can't be added (ex: a malformed id or a duplicate
+    /// column) are skipped instead of failing the whole build. Why each was skipped is returned
+    /// alongside the data, in input order
+    pub fn from_raw_lenient(
+        raw_data: &[RawCriterionData],
+        metric: Metric,
+        id_parser: &dyn IdParser,
+    ) -> (Self, Vec<Error>) {
+        let mut data = Self {
+            tables: Default::default(),
+            metric,
+            series: false,
+            summary: Default::default(),
+        };
+        let mut skipped = Vec::new();
+
+        // Every error is collected, so this can't fail
+        let _ = data.build_from_raw_data(raw_data, id_parser, |err| {
+            skipped.push(err);
+            Ok(())
+        });
+        (data, skipped)
+    }
+
+    /// Build table data from a series of labeled runs (ex: from `RawCriterionData::from_dir`).
+    /// Each run label becomes a column and each benchmark's column and row name together become
+    /// the row name, giving a view of each benchmark over time
+    pub fn from_series(
+        runs: &[(AFlexStr, Vec<RawCriterionData>)],
+        metric: Metric,
+        id_parser: &dyn IdParser,
+    ) -> Result<Self, Error> {
+        let mut data = Self {
+            tables: Default::default(),
+            metric,
+            series: true,
+            summary: Default::default(),
+        };
+        let mut col_pos = ColumnPosition::default();
+
+        for (label, raw_data) in runs {
+            let groups = Self::groups(raw_data);
+            data.summary.add_records(raw_data);
+            for bm in Self::benchmarks(raw_data) {
+                let group = groups.get(bm.id.as_str()).copied();
+                let (table_name, column_name, row_name, tags) =
+                    Self::split_id(&bm.id, group, id_parser)?;
+                let row_name = Self::series_row_name(&column_name, &row_name);
+
+                let col_name = label.clone();
+                data.add_benchmark(&mut col_pos, table_name, col_name, row_name, tags, bm)?;
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Build table data from labeled runs (ex: captures from different branches), prefixing each
+    /// column name with the label of its run (ex: `main/std`). Unlike `from_series`, the row names
+    /// are kept as is
+    pub fn from_runs(
+        runs: &[(AFlexStr, Vec<RawCriterionData>)],
+        metric: Metric,
+        id_parser: &dyn IdParser,
+    ) -> Result<Self, Error> {
+        let mut data = Self {
+            tables: Default::default(),
+            metric,
+            series: false,
+            summary: Default::default(),
+        };
+        let mut col_pos = ColumnPosition::default();
+
+        for (label, raw_data) in runs {
+            let groups = Self::groups(raw_data);
+            data.summary.add_records(raw_data);
+            for bm in Self::benchmarks(raw_data) {
+                let group = groups.get(bm.id.as_str()).copied();
+                let (table_name, column_name, row_name, tags) =
+                    Self::split_id(&bm.id, group, id_parser)?;
+                let col_name = a_flex_fmt!("{label}/{column_name}");
+                data.add_benchmark(&mut col_pos, table_name, col_name, row_name, tags, bm)?;
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Build table data comparing `latest` against a `baseline` (ex: a snapshot loaded via
+    /// `from_reader`), laid out like `from_series` with a `baseline` and a `latest` column.
+    /// Benchmarks are matched by table, column and row name. Any missing from the baseline only
+    /// have a `latest` column and any missing from `latest` are left out
+    pub fn diff(baseline: &Self, latest: &Self) -> Result<Self, Error> {
+        let mut data = Self {
+            tables: Default::default(),
+            metric: latest.metric,
+            series: true,
+            summary: latest.summary.clone(),
+        };
+        let mut col_pos = ColumnPosition::default();
+
+        for table in latest.tables.values() {
+            let baseline_table = baseline.tables.get(&table.name);
+
+            for row in table.rows.values() {
+                let baseline_row = baseline_table.and_then(|table| table.rows.get(&row.name));
+
+                for (column_name, col) in &row.column_data {
+                    let baseline_col =
+                        baseline_row.and_then(|row| row.column_data.get(column_name));
+                    let row_name = Self::series_row_name(column_name, &row.name);
+
+                    let runs = [(BASELINE_LABEL, baseline_col), (LATEST_LABEL, Some(col))];
+                    for (label, col) in runs {
+                        if let Some(col) = col {
+                            let idx = col_pos.next_idx(table.name.clone(), row_name.clone());
+                            data.get_table(table.name.clone()).add_column_data(
+                                idx,
+                                label.into(),
+                                row_name.clone(),
+                                col.measurement.clone(),
+                                col.stats.clone(),
+                                col.tags.clone(),
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Build table data comparing `latest` against an earlier run (ex: of the previous release),
+    /// added as a leading column named `label` in every table that all other columns are compared
+    /// to. The earlier benchmark of each row is the one with the table, row and column name of the
+    /// first column of the row. Rows without one are compared to their first column as usual.
+    /// Like `from_series`, `regressions` are measured against the earlier run
+    pub fn with_baseline_run(
+        baseline: &Self,
+        label: &AFlexStr,
+        latest: &Self,
+    ) -> Result<Self, Error> {
+        let mut data = Self {
+            tables: Default::default(),
+            metric: latest.metric,
+            // The earlier run is the baseline of each row, so regressions are measured against it
+            series: true,
+            summary: latest.summary.clone(),
+        };
+        data.summary.add(&baseline.summary);
+        let mut col_pos = ColumnPosition::default();
+
+        for table in latest.tables.values() {
+            let baseline_table = baseline.tables.get(&table.name);
+
+            for row in table.rows.values() {
+                let baseline_col = row.column_data.keys().next().and_then(|column_name| {
+                    baseline_table
+                        .and_then(|table| table.rows.get(&row.name))
+                        .and_then(|row| row.column_data.get(column_name))
+                });
+                let cols = baseline_col
+                    .map(|col| (label, col))
+                    .into_iter()
+                    .chain(&row.column_data);
+
+                for (column_name, col) in cols {
+                    let idx = col_pos.next_idx(table.name.clone(), row.name.clone());
+                    data.get_table(table.name.clone()).add_column_data(
+                        idx,
+                        column_name.clone(),
+                        row.name.clone(),
+                        col.measurement.clone(),
+                        col.stats.clone(),
+                        col.tags.clone(),
+                    )?;
+                }
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Build table data showing the trend of each benchmark over the given labeled `runs` (ex:
+    /// loaded from the `history` store), laid out like `from_series` with a column per run.
+    /// Benchmarks are matched by table, column and row name. The latest cell of each benchmark that
+    /// got slower run over run across its last three runs is tagged `regressing`
+    pub fn trend(runs: &[(AFlexStr, &Self)]) -> Result<Self, Error> {
+        let mut data = Self {
+            tables: Default::default(),
+            metric: runs.last().map(|(_, run)| run.metric).unwrap_or_default(),
+            series: true,
+            summary: Default::default(),
+        };
+        let mut col_pos = ColumnPosition::default();
+
+        for (label, run) in runs {
+            for table in run.tables.values() {
+                for row in table.rows.values() {
+                    for (column_name, col) in &row.column_data {
+                        let row_name = Self::series_row_name(column_name, &row.name);
+                        let idx = col_pos.next_idx(table.name.clone(), row_name.clone());
+                        data.get_table(table.name.clone()).add_column_data(
+                            idx,
+                            label.clone(),
+                            row_name,
+                            col.measurement.clone(),
+                            col.stats.clone(),
+                            col.tags.clone(),
+                        )?;
+                    }
+                }
+            }
+        }
+
+        for table in data.tables.values_mut() {
+            table.tag_monotonic_regressions();
+        }
+
+        Ok(data)
+    }
+
+    /// Merge table data (ex: built from the raw data of multiple machines or CI shards) into one.
+    /// Benchmarks with the same table, column, and row names are handled according to `policy`.
+    /// Any new tables, rows, or columns are added after those seen before
+    pub fn merge(all_data: Vec<Self>, policy: DuplicatePolicy) -> Result<Self, Error> {
+        let mut all_data = all_data.into_iter();
+        let mut data = all_data.next().ok_or(Error::NoData)?;
+        // Every duplicate of each benchmark (by table, row, and column name) to be averaged
+        let mut duplicates: IndexMap<(AFlexStr, AFlexStr, AFlexStr), Vec<Column>> = IndexMap::new();
+
+        for other in all_data {
+            data.summary.add(&other.summary);
+            for (table_name, other_table) in other.tables {
+                let table = data.get_table(table_name.clone());
+
+                for (row_name, other_row) in other_table.rows {
+                    for (column_name, col) in other_row.column_data {
+                        table.check_kind(&col.measurement)?;
+                        let existing = table
+                            .rows
+                            .get_mut(&row_name)
+                            .and_then(|row| row.column_data.get_mut(&column_name));
+
+                        match (existing, policy) {
+                            (None, _) => {
+                                let idx = table.columns.0.len();
+                                table.add_column_data(
+                                    idx,
+                                    column_name,
+                                    row_name.clone(),
+                                    col.measurement,
+                                    col.stats,
+                                    col.tags,
+                                )?;
+                            }
+                            (Some(_), DuplicatePolicy::Error) => {
+                                return Err(Error::DuplicateBenchmark {
+                                    table: table_name.to_string(),
+                                    column: column_name.to_string(),
+                                    row: row_name.to_string(),
+                                });
+                            }
+                            (Some(_), DuplicatePolicy::KeepFirst) => {}
+                            (Some(existing), DuplicatePolicy::KeepBest) => {
+                                if col.measurement.base_value() < existing.measurement.base_value()
+                                {
+                                    *existing = col;
+                                }
+                            }
+                            (Some(_), DuplicatePolicy::Average) => {
+                                let key = (table_name.clone(), row_name.clone(), column_name);
+                                duplicates.entry(key).or_default().push(col);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for ((table_name, row_name, column_name), mut cols) in duplicates {
+            let existing = data
+                .tables
+                .get_mut(&table_name)
+                .and_then(|table| table.rows.get_mut(&row_name))
+                .and_then(|row| row.column_data.get_mut(&column_name));
+
+            if let Some(existing) = existing {
+                cols.push(existing.clone());
+                let times: Vec<_> = cols.iter().map(|col| col.measurement.clone()).collect();
+                let stats: Vec<_> = cols.iter().map(|col| &col.stats).collect();
+                existing.measurement = Measurement::mean(&times);
+                existing.stats = Stats::mean(&stats);
+            }
+        }
+
+        for table in data.tables.values_mut() {
+            table.update_comparisons();
+        }
+
+        Ok(data)
+    }
+
+    /// Load table data from a JSON snapshot written by `to_writer`
+    pub fn from_reader(r: impl Read) -> serde_json::error::Result<Self> {
+        serde_json::from_reader(BufReader::new(r))
+    }
+
+    /// Write this table data as a JSON snapshot (before `apply_config` is called, so it can be
+    /// compared against later via `diff` with any config)
+    pub fn to_writer(&self, w: impl Write) -> serde_json::error::Result<()> {
+        serde_json::to_writer_pretty(w, self)
+    }
+
+    /// Each table, in display order
+    #[inline]
+    pub fn tables(&self) -> impl Iterator<Item = &Table> {
+        self.tables.values()
+    }
+
+    /// The table named `name`, if any
+    #[inline]
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(&name.to_flex())
+    }
+
+    /// The estimate of each benchmark that was tabulated
+    #[inline]
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    // In a series, each benchmark's column and row name together become the row name
+    fn series_row_name(column_name: &AFlexStr, row_name: &AFlexStr) -> AFlexStr {
+        if row_name.is_empty() {
+            column_name.clone()
+        } else {
+            a_flex_fmt!("{column_name}/{row_name}")
+        }
+    }
+
+    // Each benchmark that can't be added is passed to `on_invalid`, which either returns the error
+    // to stop the build or `Ok` to skip the benchmark
+    fn build_from_raw_data(
+        &mut self,
+        raw_data: &[RawCriterionData],
+        id_parser: &dyn IdParser,
+        mut on_invalid: impl FnMut(Error) -> Result<(), Error>,
+    ) -> Result<(), Error> {
         let mut col_pos = ColumnPosition::default();
+        let groups = Self::groups(raw_data);
+        self.summary.add_records(raw_data);
+
+        for bm in Self::benchmarks(raw_data) {
+            let group = groups.get(bm.id.as_str()).copied();
+            let (table_name, column_name, row_name, tags) =
+                match Self::split_id(&bm.id, group, id_parser) {
+                    Ok(names) => names,
+                    // The error already holds the id
+                    Err(err) => {
+                        self.summary.skipped += 1;
+                        on_invalid(err)?;
+                        continue;
+                    }
+                };
+
+            if let Err(err) =
+                self.add_benchmark(&mut col_pos, table_name, column_name, row_name, tags, bm)
+            {
+                self.summary.skipped += 1;
+                on_invalid(Error::Benchmark {
+                    id: bm.id.to_string(),
+                    source: Box::new(err),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // We only process benchmark data - skip anything else
+    fn benchmarks(raw_data: &[RawCriterionData]) -> impl Iterator<Item = &BenchmarkComplete> {
+        raw_data.iter().filter_map(|item| match item {
+            RawCriterionData::Benchmark(bm) => Some(&**bm),
+            RawCriterionData::BenchmarkGroup(_) | RawCriterionData::Other => None,
+        })
+    }
+
+    // The benchmark group of each benchmark id, from the `group-complete` messages
+    fn groups(raw_data: &[RawCriterionData]) -> HashMap<&str, &str> {
+        raw_data
+            .iter()
+            .filter_map(|item| match item {
+                RawCriterionData::Benchmark(_) | RawCriterionData::Other => None,
+                RawCriterionData::BenchmarkGroup(group) => Some(&**group),
+            })
+            .flat_map(|group| {
+                group
+                    .benchmarks
+                    .iter()
+                    .map(|id| (id.as_str(), group.group_name.as_str()))
+            })
+            .collect()
+    }
+
+    // Break the id into table, column, row, and tags (an optional `#tag1,tag2` suffix) respectively.
+    // The id is split with its benchmark group, if it has one
+    fn split_id(
+        id: &AFlexStr,
+        group: Option<&str>,
+        id_parser: &dyn IdParser,
+    ) -> Result<(AFlexStr, AFlexStr, AFlexStr, Vec<AFlexStr>), Error> {
+        let (id, tags) = match id.split_once('#') {
+            Some((id, tags)) => (
+                id,
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(ToFlex::to_flex)
+                    .collect(),
+            ),
+            None => (id.as_str(), Vec::new()),
+        };
+
+        let (table_name, column_name, row_name) = match group {
+            Some(group) => id_parser.parse_group_id(group, id)?,
+            None => id_parser.parse_id(id)?,
+        };
+        Ok((table_name, column_name, row_name, tags))
+    }
+
+    fn add_benchmark(
+        &mut self,
+        col_pos: &mut ColumnPosition,
+        table_name: AFlexStr,
+        column_name: AFlexStr,
+        row_name: AFlexStr,
+        tags: Vec<AFlexStr>,
+        bm: &BenchmarkComplete,
+    ) -> Result<(), Error> {
+        // Find our table, calculate our timing, and add data to our column
+        let estimate = bm.estimate(self.metric);
+        let measurement = Measurement::new(estimate.estimate, &estimate.unit);
+        let stats = Stats::new(bm, self.metric);
+        let table = self.get_table(table_name.clone());
+
+        // Checked before the column position is taken, so a skipped duplicate doesn't leave a gap
+        if let Some(row) = table.rows.get(&row_name) {
+            if row.column_data.contains_key(&column_name) {
+                return Err(Error::DuplicateColumn {
+                    column: column_name.to_string(),
+                });
+            }
+        }
+
+        let idx = col_pos.next_idx(table_name, row_name.clone());
+        table.add_column_data(idx, column_name, row_name, measurement, stats, tags)
+    }
+
+    /// What was consumed, created and dropped while processing the data. The tables and dropped
+    /// benchmarks are only known once `apply_config` is called
+    #[inline]
+    pub fn summary(&self) -> &ProcessingSummary {
+        &self.summary
+    }
+
+    // Each benchmark of the tables, as (table, column, row)
+    fn benchmark_names(&self) -> Vec<(AFlexStr, AFlexStr, AFlexStr)> {
+        self.tables
+            .values()
+            .flat_map(|table| {
+                table.rows.values().flat_map(|row| {
+                    row.column_data
+                        .keys()
+                        .map(|column| (table.name.clone(), column.clone(), row.name.clone()))
+                })
+            })
+            .collect()
+    }
+
+    // Adds each benchmark of `before` (from `benchmark_names`) no longer in the tables to the
+    // dropped benchmarks of the summary
+    fn record_dropped(&mut self, before: Vec<(AFlexStr, AFlexStr, AFlexStr)>, reason: DropReason) {
+        let after: HashSet<_> = self.benchmark_names().into_iter().collect();
+
+        for (table, column, row) in before {
+            if !after.contains(&(table.clone(), column.clone(), row.clone())) {
+                self.summary.dropped.push(DroppedBenchmark {
+                    table,
+                    column,
+                    row,
+                    reason,
+                });
+            }
+        }
+    }
+
+    /// Apply the options in `config` that change the processed data (ex: `strip_affixes`). This
+    /// should be called once, before any output is generated. It fails if renames give two
+    /// tables, columns or rows the same name
+    pub fn apply_config(&mut self, config: &TablesConfig) -> Result<(), Error> {
+        self.rename(config)?;
+        self.summary.tables = self
+            .tables
+            .values()
+            .map(|table| TableSummary {
+                name: table.name.clone(),
+                columns: table.column_names().cloned().collect(),
+                rows: table.rows.keys().cloned().collect(),
+            })
+            .collect();
+        self.summary.dropped.clear();
+
+        let before = self.benchmark_names();
+        for table in self.tables.values_mut() {
+            table.throughput_display = config.throughput;
+            table.change_column = config.change_column;
+            table.time_format = config.table_time_format(&table.name).for_times(
+                table
+                    .rows
+                    .values()
+                    .flat_map(|row| row.column_data.values())
+                    .filter_map(|col| col.measurement.as_time()),
+            );
+            table.comparison_style = config.table_comparison_style(&table.name);
+            table.highlight = config.highlight;
+            table.noise_threshold = config.noise_threshold;
+            table.extra_stats = config.extra_stats;
+            table.geomean_row = config.geomean_row;
+            table.aggregate_rows = config.aggregate_rows.clone();
+
+            if !config.include.is_empty() || !config.exclude.is_empty() {
+                table.filter_ids(&config.include, &config.exclude);
+            }
+        }
+        self.record_dropped(before, DropReason::Pattern);
+
+        let before = self.benchmark_names();
+        for table in self.tables.values_mut() {
+            table.hide_columns(config.table_hidden_columns(&table.name));
+            table.order_columns(&config.table_column_order(&table.name));
+        }
+        self.record_dropped(before, DropReason::HiddenColumn);
+        self.tables.retain(|_, table| !table.rows.is_empty());
+
+        if !config.exclude_tags.is_empty() {
+            let before = self.benchmark_names();
+            self.tables.retain(|_, table| {
+                table.exclude_tags(&config.exclude_tags);
+                !table.rows.is_empty()
+            });
+            self.record_dropped(before, DropReason::Tag);
+        }
+
+        if config.changed_tables_only {
+            let before = self.benchmark_names();
+            let series = self.series;
+            self.tables.retain(|_, table| table.has_changes(series));
+            self.record_dropped(before, DropReason::Unchanged);
+        }
+
+        if let Some(threshold) = config.regressions_only {
+            let before = self.benchmark_names();
+            let mut worst = HashMap::new();
+            self.tables
+                .retain(|name, table| match table.retain_regressions(threshold) {
+                    Some(comparison) => {
+                        worst.insert(name.clone(), comparison);
+                        true
+                    }
+                    None => false,
+                });
+            self.tables
+                .sort_by(|name1, _, name2, _| worst[name1].total_cmp(&worst[name2]));
+            self.record_dropped(before, DropReason::NoRegression);
+        }
+
+        for table in self.tables.values_mut() {
+            if let Some(matrix) = config.table_matrix(&table.name) {
+                table.make_matrix(matrix.row.as_ref());
+            }
+            if config.table_transpose(&table.name) {
+                table.transpose();
+            }
+            if config.strip_affixes {
+                table.strip_common_affixes();
+            }
+            if let Some((sort, descending)) = config.table_sort_rows(&table.name) {
+                table.sort_rows(sort, descending);
+            }
+            table.set_column_groups(&config.column_groups);
+            table.set_footnotes(config.table_footnotes(&table.name), &config.footnotes);
+            table.set_report_links(&config.report_links);
+            table.set_speedup_tiers(&config.speedup_tiers);
+
+            table.update_widths();
+            table.fix_widths(config.column_width, config.column_width_multiple);
+        }
+
+        Ok(())
+    }
+
+    // Replace the raw names of tables, columns and rows with their display names
+    fn rename(&mut self, config: &TablesConfig) -> Result<(), Error> {
+        let rename = &config.rename;
+        if !rename.tables.is_empty() {
+            Self::check_renames("table", self.tables.keys(), |name| {
+                rename.tables.get(name).unwrap_or(name).clone()
+            })?;
+            self.tables = self
+                .tables
+                .drain(..)
+                .map(|(name, mut table)| {
+                    table.name = rename.tables.get(&name).cloned().unwrap_or(name);
+                    (table.name.clone(), table)
+                })
+                .collect();
+        }
+
+        for table in self.tables.values_mut() {
+            let table_rename = config
+                .table_options(&table.name)
+                .map(|options| &options.rename);
+            let find = |names: &HashMap<AFlexStr, AFlexStr>,
+                        table_names: Option<&HashMap<AFlexStr, AFlexStr>>,
+                        name: &AFlexStr| {
+                table_names
+                    .and_then(|table_names| table_names.get(name))
+                    .or_else(|| names.get(name))
+                    .unwrap_or(name)
+                    .clone()
+            };
+
+            let columns = table_rename.map(|rename| &rename.columns);
+            if !rename.columns.is_empty() || columns.is_some_and(|columns| !columns.is_empty()) {
+                let rename = |name: &AFlexStr| find(&rename.columns, columns, name);
+                Self::check_renames("column", table.column_names(), rename)?;
+                table.rename_columns(rename);
+            }
+            let rows = table_rename.map(|rename| &rename.rows);
+            if !rename.rows.is_empty() || rows.is_some_and(|rows| !rows.is_empty()) {
+                let rename = |name: &AFlexStr| find(&rename.rows, rows, name);
+                Self::check_renames("row", table.rows.keys(), rename)?;
+                table.rename_rows(rename);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Fails with the first two of `names` that `rename` gives the same display name
+    fn check_renames<'a>(
+        kind: &'static str,
+        names: impl IntoIterator<Item = &'a AFlexStr>,
+        rename: impl Fn(&AFlexStr) -> AFlexStr,
+    ) -> Result<(), Error> {
+        let mut renamed: HashMap<AFlexStr, &AFlexStr> = HashMap::new();
+
+        for name in names {
+            let display_name = rename(name);
+            if let Some(first) = renamed.insert(display_name.clone(), name) {
+                return Err(Error::RenameCollision {
+                    kind,
+                    first: first.to_string(),
+                    second: name.to_string(),
+                    name: display_name.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that each `[table."..."]` section of `config` names a table of this data (by its
+    /// display name). This should be called before `apply_config`, as it can remove tables
+    pub fn check_table_sections(&self, config: &TablesConfig) -> Result<(), Error> {
+        let names: HashSet<_> = self
+            .tables
+            .keys()
+            .map(|name| config.rename.tables.get(name).unwrap_or(name))
+            .collect();
+        let unknown: Vec<_> = config
+            .table
+            .keys()
+            .filter(|name| !names.contains(name))
+            .map(AFlexStr::as_str)
+            .collect();
+
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::UnknownTables {
+                tables: unknown.join(", "),
+            })
+        }
+    }
+
+    /// Returns each per table entry of `config` (ex: a key of `table_comments`) that names no table
+    /// of this data. Like `check_table_sections`, this should be called before `apply_config`
+    pub fn config_warnings(&self, config: &TablesConfig) -> Vec<ConfigWarning> {
+        let names: Vec<_> = self
+            .tables
+            .keys()
+            .map(|name| config.rename.tables.get(name).unwrap_or(name).clone())
+            .collect();
+        let keys: Vec<_> = names.iter().map(Self::encode_key).collect();
+        let raw_names: Vec<_> = self.tables.keys().cloned().collect();
+
+        let mut warnings = Vec::new();
+        let mut check = |option: &str, entries: Vec<&AFlexStr>, names: &[AFlexStr]| {
+            for key in entries {
+                if !names.contains(key) {
+                    warnings.push(ConfigWarning::new(option, key, names));
+                }
+            }
+        };
+
+        check(
+            "rename.tables",
+            config.rename.tables.keys().collect(),
+            &raw_names,
+        );
+        check(
+            "table_comments",
+            config.table_comments.keys().collect(),
+            &keys,
+        );
+        warnings
+    }
+
+    /// Check `config` against this data before `apply_config`: sections for unknown tables are an
+    /// error (see `check_table_sections`), and other entries for unknown tables are returned as
+    /// warnings (an error with `version = 2`)
+    pub fn check_config(&self, config: &TablesConfig) -> Result<Vec<ConfigWarning>, Error> {
+        self.check_table_sections(config)?;
+
+        let warnings = self.config_warnings(config);
+        if config.is_strict() && !warnings.is_empty() {
+            let entries: Vec<_> = warnings.iter().map(ToAFlexStr::to_a_flex_str).collect();
+            let entries: Vec<_> = entries.iter().map(AFlexStr::as_str).collect();
+            return Err(Error::UnknownConfigTables {
+                entries: entries.join("; "),
+            });
+        }
+        Ok(warnings)
+    }
+
+    /// Returns each benchmark that is slower than its baseline by more than `threshold` (ex: 0.05),
+    /// sorted worst first. When built from a series of runs (ex: comparing against a criterion
+    /// baseline) the baseline is the first column of the table (rows without it are skipped),
+    /// otherwise it is criterion's previous run (as given by the `change` of each benchmark)
+    pub fn regressions(&self, threshold: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+
+        for table in self.tables.values() {
+            let baseline_name = table.column_names().next();
+
+            for row in table.rows.values() {
+                // Rows without a benchmark in the baseline column have nothing to compare against
+                let baseline = baseline_name.and_then(|name| row.column_data.get(name));
+
+                for (name, col) in &row.column_data {
+                    let change = if self.series {
+                        baseline
+                            .filter(|_| Some(name) != baseline_name)
+                            .map(|baseline| {
+                                let compare =
+                                    Comparison::new(&baseline.measurement, &col.measurement);
+                                1.0 / compare.ratio() - 1.0
+                            })
+                    } else {
+                        col.stats.change.map(|change| change.relative)
+                    };
+
+                    match change {
+                        Some(change) if change > threshold => regressions.push(Regression {
+                            table: table.name.clone(),
+                            column: name.clone(),
+                            row: row.name.clone(),
+                            change,
+                        }),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        regressions.sort_by(|r1, r2| r2.change.total_cmp(&r1.change));
+        regressions
+    }
+
+    /// Returns each benchmark of `latest` whose time changed by more than `threshold` (ex: 0.05
+    /// for ±5%) either way since `baseline`, sorted by change (slowest first). Benchmarks are
+    /// matched by table, column and row name, so any missing from either are left out (as are any
+    /// with a zero time in `baseline`)
+    pub fn deltas(baseline: &Self, latest: &Self, threshold: f64) -> Vec<Delta> {
+        let mut deltas = Vec::new();
+
+        for table in latest.tables.values() {
+            let baseline_table = baseline.tables.get(&table.name);
+
+            for row in table.rows.values() {
+                let baseline_row = baseline_table.and_then(|table| table.rows.get(&row.name));
+
+                for (name, col) in &row.column_data {
+                    let baseline_col = baseline_row.and_then(|row| row.column_data.get(name));
+
+                    if let Some(baseline_col) = baseline_col {
+                        let (old, new) = (&baseline_col.measurement, &col.measurement);
+                        // A zero time has no relative change to compare
+                        if old.base_value() == 0.0 {
+                            continue;
+                        }
+                        let change = new.base_value() / old.base_value() - 1.0;
+
+                        if change.abs() > threshold {
+                            deltas.push(Delta {
+                                table: table.name.clone(),
+                                column: name.clone(),
+                                row: row.name.clone(),
+                                old: old.clone(),
+                                new: new.clone(),
+                                change,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        deltas.sort_by(|d1, d2| d2.change.total_cmp(&d1.change));
+        deltas
+    }
+
+    fn get_table(&mut self, name: AFlexStr) -> &mut Table {
+        match self.tables.entry(name.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Table::new(name)),
+        }
+    }
+
+    fn encode_key(s: &AFlexStr) -> AFlexStr {
+        s.replace(' ', "_").into_flex().to_lower()
+    }
+
+    /// Given a `Formatter` (used only to generate link anchors), generate a JSON index of each
+    /// table's name, anchor and summary stats as a `String`. This is intended to be written
+    /// alongside the formatted report so tooling can deep-link to specific tables
+    pub fn make_index(&self, f: &impl Formatter) -> serde_json::Result<String> {
+        let tables: Vec<_> = self
+            .tables
+            .values()
+            .map(|table| TableIndexEntry::new(table, f.anchor(&table.name)))
+            .collect();
+
+        serde_json::to_string_pretty(&TableIndex { tables })
+    }
+
+    /// Given a `Formatter` and `TablesConfig`, generate formatted tables as a `String`
+    pub fn make_tables(&self, f: impl Formatter, config: &TablesConfig) -> String {
+        let metadata = Metadata::new(&config.footer);
+        let environment = config.environment.entries();
+        self.make_document(f, config, &metadata, &environment)
+    }
+
+    /// Format the tables through each formatter (ex: markdown for a README and CSV for a
+    /// spreadsheet), returning the document of each in the same order. The metadata (with
+    /// its timestamp) and environment are made once and shared by every document
+    pub fn make_tables_multi<F: Formatter>(
+        &self,
+        formatters: impl IntoIterator<Item = F>,
+        config: &TablesConfig,
+    ) -> Vec<String> {
+        let metadata = Metadata::new(&config.footer);
+        let environment = config.environment.entries();
+
+        formatters
+            .into_iter()
+            .map(|f| self.make_document(f, config, &metadata, &environment))
+            .collect()
+    }
+
+    // Formats all tables as one document
+    fn make_document(
+        &self,
+        mut f: impl Formatter,
+        config: &TablesConfig,
+        metadata: &Metadata,
+        environment: &IndexMap<AFlexStr, AFlexStr>,
+    ) -> String {
+        let mut buffer = String::with_capacity(BUFFER_CAPACITY);
+
+        // Start of doc
+        f.metadata(metadata);
+        if let Some(embed) = &config.embed {
+            f.embed(embed);
+        }
+        let top_comments = environment::with_environment(environment, &config.top_comments, &f);
+        let groups = match &config.toc.nest_separator {
+            Some(separator) => self.nested_tables(separator),
+            None => self
+                .tables
+                .values()
+                .map(|table| (None, vec![table]))
+                .collect(),
+        };
+        let entries = groups
+            .iter()
+            .map(|(prefix, tables)| match prefix {
+                Some(prefix) => TocEntry {
+                    name: prefix,
+                    children: tables
+                        .iter()
+                        .map(|table| TocEntry::new(&table.name))
+                        .collect(),
+                },
+                None => TocEntry::new(&tables[0].name),
+            })
+            .collect();
+        f.start(&mut buffer, &top_comments, &config.toc.toc(entries));
+
+        for (prefix, tables) in &groups {
+            if let Some(prefix) = prefix {
+                f.start_section(&mut buffer, prefix);
+            }
+            for table in tables {
+                Self::write_table(table, &mut f, config, &mut buffer);
+            }
+            if prefix.is_some() {
+                f.end_section(&mut buffer);
+            }
+        }
+
+        // End of doc
+        f.end(&mut buffer);
+
+        buffer
+    }
+
+    /// Format each table as its own document, instead of all tables in one. The key of each
+    /// document is the name of its table. A new formatter is made via `make_formatter` for each
+    /// table, since formatters can hold state for the whole document. The first error making a
+    /// formatter is returned
+    pub fn make_tables_split<F: Formatter, E>(
+        &self,
+        mut make_formatter: impl FnMut() -> Result<F, E>,
+        config: &TablesConfig,
+    ) -> Result<IndexMap<AFlexStr, String>, E> {
+        // Collected once, so every file has the same provenance
+        let metadata = Metadata::new(&config.footer);
+
+        self.tables
+            .values()
+            .map(|table| {
+                let f = make_formatter()?;
+                Ok((
+                    table.name.clone(),
+                    Self::split_table(table, f, config, &metadata),
+                ))
+            })
+            .collect()
+    }
+
+    /// Like `make_tables_split`, but each table is formatted on a rayon thread pool, which is
+    /// faster for suites with many tables. Since `make_formatter` is called from the pool, the
+    /// formatter is made on the thread that uses it. The documents are in the same order as with
+    /// `make_tables_split`
+    #[cfg(feature = "parallel")]
+    pub fn par_make_tables_split<F: Formatter, E: Send>(
+        &self,
+        make_formatter: impl Fn() -> Result<F, E> + Sync,
+        config: &TablesConfig,
+    ) -> Result<IndexMap<AFlexStr, String>, E> {
+        use rayon::prelude::*;
+
+        let metadata = Metadata::new(&config.footer);
+        let tables: Vec<_> = self.tables.values().collect();
+
+        let documents: Vec<_> = tables
+            .par_iter()
+            .map(|table| {
+                let f = make_formatter()?;
+                Ok((
+                    table.name.clone(),
+                    Self::split_table(table, f, config, &metadata),
+                ))
+            })
+            .collect::<Result<_, E>>()?;
+        Ok(documents.into_iter().collect())
+    }
+
+    // A document with just `table`
+    fn split_table<F: Formatter>(
+        table: &Table,
+        mut f: F,
+        config: &TablesConfig,
+        metadata: &Metadata,
+    ) -> String {
+        let mut buffer = String::with_capacity(BUFFER_CAPACITY);
+
+        f.metadata(metadata);
+        if let Some(embed) = &config.embed {
+            f.embed(embed);
+        }
+        let toc = config.toc.toc(vec![TocEntry::new(&table.name)]);
+        f.start(&mut buffer, &IndexMap::new(), &toc);
+        Self::write_table(table, &mut f, config, &mut buffer);
+        f.end(&mut buffer);
+
+        buffer
+    }
+
+    /// Draw an SVG bar chart of the timings of each table (one group of bars per row, with one bar
+    /// per column). The key of each chart is the name of its table
+    pub fn make_charts(&self, options: &ChartOptions) -> IndexMap<AFlexStr, String> {
+        self.tables
+            .values()
+            .map(|table| (table.name.clone(), chart::bar_chart(table, options)))
+            .collect()
+    }
+
+    // Groups the tables by the part of their name before the last `separator` (the section they
+    // are nested under), in order of first appearance. Tables without one are alone in a group
+    // without a section
+    fn nested_tables(&self, separator: &str) -> Vec<(Option<AFlexStr>, Vec<&Table>)> {
+        let mut groups: Vec<(Option<AFlexStr>, Vec<&Table>)> = Vec::new();
+
+        for table in self.tables.values() {
+            let prefix = table
+                .name
+                .rsplit_once(separator)
+                .map(|(prefix, _)| prefix.to_flex());
+            let group = prefix
+                .as_ref()
+                .and_then(|prefix| groups.iter().position(|(p, _)| p.as_ref() == Some(prefix)));
+
+            match group {
+                Some(idx) => groups[idx].1.push(table),
+                None => groups.push((prefix, vec![table])),
+            }
+        }
+
+        groups
+    }
+
+    // Returns a table of contents entry for each table
+    pub(crate) fn toc_entries(&self) -> Vec<TocEntry<'_>> {
+        self.tables.keys().map(TocEntry::new).collect()
+    }
+
+    // Formats each table into the buffer (but not the start/end of the document)
+    pub(crate) fn write_tables(
+        &self,
+        f: &mut impl Formatter,
+        config: &TablesConfig,
+        buffer: &mut String,
+    ) {
+        for table in self.tables.values() {
+            Self::write_table(table, f, config, buffer);
+        }
+    }
+
+    // Formats a single table into the buffer
+    fn write_table(
+        table: &Table,
+        f: &mut impl Formatter,
+        config: &TablesConfig,
+        buffer: &mut String,
+    ) {
+        let col_info = &table.columns.0;
+        // Columns are named without their group when the formatter displays the groups
+        let spans = table.column_group_spans();
+        let grouped = !spans.is_empty() && f.column_groups(&spans);
+        // The names displayed have footnote markers, if any
+        let display_cols: Vec<_> = col_info
+            .iter()
+            .enumerate()
+            .map(|(idx, col)| {
+                ColumnInfo::new(table.display_column_name(&col.name, grouped), col.max_width)
+                    .with_align(config.alignment.find(idx, &col.name))
+            })
+            .collect();
+
+        if let Some(first_col) = col_info.first() {
+            // Start of table
+            let comments = config.table_comment(&table.name);
+            f.start_table(buffer, &table.name, comments, &display_cols);
+
+            for row in table.rows.values() {
+                // Start of row
+                let row_name = marked_name(&row.name, &table.row_markers);
+                match row.link().filter(|_| table.link_target == LinkTarget::Row) {
+                    Some(link) => f.start_linked_row(buffer, &row_name, link, first_col.max_width),
+                    None => f.start_row(buffer, &row_name, first_col.max_width),
+                }
+                let extremes = match table.highlight {
+                    Highlight::Off => None,
+                    Highlight::Best | Highlight::BestWorst => row.extremes(),
+                };
+                let best_ratio = row.best_ratio();
+
+                for col in &col_info[1..] {
+                    match row.column_data.get(&col.name) {
+                        // Used column
+                        Some(col_data) => {
+                            let mut cell = col_data.cell_data(table.cell_options());
+                            cell.role = match extremes {
+                                Some((best, _)) if *best == col.name => CellRole::Best,
+                                Some((_, worst))
+                                    if *worst == col.name
+                                        && table.highlight == Highlight::BestWorst =>
+                                {
+                                    CellRole::Worst
+                                }
+                                _ => CellRole::Normal,
+                            };
+                            cell.relative_time = best_ratio / col_data.pct.ratio();
+                            f.used_column(buffer, &cell, col.max_width)
+                        }
+                        // Unused column
+                        None => f.unused_column(buffer, col.max_width),
+                    }
+                }
+
+                // End of row
+                f.end_row(buffer);
+            }
+
+            if table.geomean_row {
+                let comparisons: Vec<_> = col_info[1..]
+                    .iter()
+                    .map(|col| table.geomean(&col.name))
+                    .collect();
+                f.summary_row(
+                    buffer,
+                    &GEOMEAN_ROW_NAME.into(),
+                    &comparisons,
+                    &display_cols,
+                );
+            }
+
+            for &aggregate in &table.aggregate_rows {
+                let name = aggregate.name().into();
+
+                // The geomean time is written like any other row, as it has a time to display
+                if aggregate == AggregateRow::GeomeanTime {
+                    f.start_row(buffer, &name, first_col.max_width);
+                    for col in &col_info[1..] {
+                        match table.geomean_time(&col.name) {
+                            Some(col_data) => {
+                                let cell = col_data.cell_data(table.cell_options());
+                                f.used_column(buffer, &cell, col.max_width)
+                            }
+                            None => f.unused_column(buffer, col.max_width),
+                        }
+                    }
+                    f.end_row(buffer);
+                } else {
+                    let comparisons: Vec<_> = col_info[1..]
+                        .iter()
+                        .map(|col| table.aggregate(aggregate, &col.name))
+                        .collect();
+                    f.summary_row(buffer, &name, &comparisons, &display_cols);
+                }
+            }
+
+            if !table.footnotes.is_empty() {
+                f.footnotes(buffer, &table.footnotes);
+            }
+
+            // End of table
+            f.end_table(buffer);
+        }
+    }
+}
+
+#[cfg(test)]
+impl CriterionTableData {
+    // Table data of benchmarks with the given ids and times (in nanoseconds)
+    pub(crate) fn from_times(benchmarks: &[(&str, f64)]) -> Self {
+        let raw_data: Vec<_> = benchmarks
+            .iter()
+            .map(|&(id, time)| {
+                let benchmark =
+                    BenchmarkComplete::from_samples(id.into(), time, time, 0.0, Vec::new());
+                RawCriterionData::Benchmark(Box::new(benchmark))
+            })
+            .collect();
+
+        Self::from_raw(&raw_data, Metric::Typical, &IdScheme::default()).expect("valid benchmarks")
+    }
+}
+
+// *** Table Index ***
 
-        for item in raw_data {
-            // We only process benchmark data - skip anything else
-            if let RawCriterionData::Benchmark(bm) = item {
-                // Break the id into table, column, and row respectively
-                let mut parts: Vec<FlexStr> = bm.id.split('/').map(|s| s.to_flex()).collect();
-                if parts.len() < 2 {
-                    return Err(anyhow::anyhow!("Malformed id: {}", &bm.id));
-                }
+#[derive(Serialize)]
+struct ColumnSummary<'a> {
+    name: &'a AFlexStr,
+    benchmarks: usize,
+    geomean_speedup: f64,
+    // In the base unit of the measurement (ex: picoseconds for times, see `base_value`)
+    fastest_base: f64,
+    slowest_base: f64,
+}
 
-                let (table_name, column_name) = (parts.remove(0), parts.remove(0));
-                // If we don't have a row name then we will work with a blank row name
-                let row_name = if !parts.is_empty() {
-                    parts.remove(0)
-                } else {
-                    "".into()
-                };
+impl<'a> ColumnSummary<'a> {
+    fn new(name: &'a AFlexStr, table: &Table) -> Self {
+        let cols: Vec<_> = table
+            .rows
+            .values()
+            .filter_map(|row| row.column_data.get(name))
+            .collect();
+        let times = cols.iter().map(|col| col.measurement.base_value());
+
+        Self {
+            name,
+            benchmarks: cols.len(),
+            geomean_speedup: table.geomean(name).unwrap_or_default().ratio(),
+            fastest_base: times.clone().fold(f64::INFINITY, f64::min),
+            slowest_base: times.fold(0.0, f64::max),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TableIndexEntry<'a> {
+    name: &'a AFlexStr,
+    anchor: AFlexStr,
+    rows: usize,
+    columns: Vec<ColumnSummary<'a>>,
+}
 
-                // Find our table, calculate our timing, and add data to our column
-                let table = self.get_table(table_name.clone());
-                let time_unit = TimeUnit::try_new(bm.typical.estimate, &bm.typical.unit)?;
+impl<'a> TableIndexEntry<'a> {
+    fn new(table: &'a Table, anchor: AFlexStr) -> Self {
+        // Skip the first column - it only holds the row names
+        let columns = table
+            .columns
+            .0
+            .iter()
+            .skip(1)
+            .map(|col| ColumnSummary::new(&col.name, table))
+            .collect();
 
-                let idx = col_pos.next_idx(table_name, row_name.clone());
-                table.add_column_data(idx, column_name, row_name, time_unit)?;
-            }
+        Self {
+            name: &table.name,
+            anchor,
+            rows: table.rows.len(),
+            columns,
         }
+    }
+}
 
-        Ok(())
+#[derive(Serialize)]
+struct TableIndex<'a> {
+    tables: Vec<TableIndexEntry<'a>>,
+}
+
+// *** Formatter ***
+
+/// The title of the document and its table of contents, passed to `Formatter::start`
+#[derive(Clone, Debug)]
+pub struct Toc<'a> {
+    /// The title of the document
+    pub title: &'a AFlexStr,
+    /// Write the table of contents. If false, only the title is written
+    pub enabled: bool,
+    /// The entries of the table of contents
+    pub entries: Vec<TocEntry<'a>>,
+}
+
+/// An entry in the table of contents. Entries are either a table or a section (ex: a crate in a
+/// workspace report) holding table entries
+#[derive(Clone, Debug)]
+pub struct TocEntry<'a> {
+    /// The name of the table or section
+    pub name: &'a AFlexStr,
+    /// Entries nested under this one (always empty for tables)
+    pub children: Vec<TocEntry<'a>>,
+}
+
+impl<'a> TocEntry<'a> {
+    #[inline]
+    fn new(name: &'a AFlexStr) -> Self {
+        Self {
+            name,
+            children: Vec::new(),
+        }
     }
+}
 
-    fn get_table(&mut self, name: FlexStr) -> &mut Table {
-        match self.tables.entry(name.clone()) {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(Table::new(name)),
+/// The footer of the output and the provenance of the results (ex: the git commit), passed to
+/// `Formatter::metadata`. Each item of provenance is blank unless enabled and known
+#[derive(Clone, Debug, Serialize)]
+pub struct Metadata {
+    /// Write the footer. If false, nothing is written at the end, including any provenance
+    #[serde(skip)]
+    pub footer: bool,
+    /// Text used instead of the formatter's own footer (ex: `Made with criterion-table`), written
+    /// as is
+    #[serde(skip)]
+    pub footer_text: Option<AFlexStr>,
+    /// When the output was generated (ex: `2024-05-01 12:34:56 UTC`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<AFlexStr>,
+    /// The short git commit of the current directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<AFlexStr>,
+    /// The version of rustc (ex: `1.80.0 (051478957 2024-07-21)`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rustc_version: Option<AFlexStr>,
+    /// The hostname of the machine
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<AFlexStr>,
+}
+
+impl Default for Metadata {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            footer: true,
+            footer_text: None,
+            timestamp: None,
+            git_commit: None,
+            rustc_version: None,
+            hostname: None,
         }
     }
+}
 
-    fn encode_key(s: &FlexStr) -> FlexStr {
-        s.replace(' ', "_").into_flex().to_lower()
+impl Metadata {
+    /// Returns the label and value of each known item of provenance (ex: `("commit", "1a2b3c4")`)
+    pub fn provenance(&self) -> impl Iterator<Item = (&'static str, &AFlexStr)> {
+        [
+            ("Generated", &self.timestamp),
+            ("commit", &self.git_commit),
+            ("rustc", &self.rustc_version),
+            ("host", &self.hostname),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value.as_ref()?)))
     }
 
-    /// Given a `Formatter` and `TablesConfig`, generate formatted tables as a `String`
-    pub fn make_tables(&self, mut f: impl Formatter, config: &TablesConfig) -> String {
-        let mut buffer = String::with_capacity(BUFFER_CAPACITY);
+    /// Returns the known provenance as one line (ex: `Generated 2024-05-01 12:34:56 UTC · commit
+    /// 1a2b3c4`), if there is any and the footer is written
+    pub fn provenance_str(&self) -> Option<AFlexStr> {
+        let items: Vec<_> = self
+            .provenance()
+            .map(|(label, value)| format!("{label} {value}"))
+            .collect();
+        (self.footer && !items.is_empty()).then(|| items.join(" · ").into_flex())
+    }
+}
 
-        // Start of doc
-        let table_names: Vec<_> = self.tables.keys().collect();
-        f.start(&mut buffer, &config.top_comments, &table_names);
+/// A run of adjacent columns in the same group, passed to `Formatter::column_groups`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnGroup {
+    /// The name of the group (blank for columns not in a group)
+    pub name: AFlexStr,
+    /// The number of columns in the run
+    pub span: usize,
+}
 
-        for table in self.tables.values() {
-            let col_info = &table.columns.0;
+/// The extra statistics of a benchmark displayed after its time. Each is blank if it shouldn't be
+/// displayed or isn't known (ex: the samples weren't recorded)
+#[derive(Clone, Debug, Default)]
+pub struct ExtraStats {
+    /// The standard deviation of the time per iteration of the samples
+    pub std_dev: Option<Measurement>,
+    /// Criterion's median absolute deviation
+    pub median_abs_dev: Option<Measurement>,
+    /// The time per iteration of the fastest sample
+    pub min: Option<Measurement>,
+    /// The time per iteration of the slowest sample
+    pub max: Option<Measurement>,
+}
 
-            if let Some(first_col) = col_info.first() {
-                // Start of table
-                let comments = config.table_comments.get(&Self::encode_key(&table.name));
-                f.start_table(&mut buffer, &table.name, comments, col_info);
+impl ExtraStats {
+    /// The label each statistic is displayed with (ex: `σ 0.05 us`), in display order
+    pub const LABELS: [&'static str; 4] = ["σ", "MAD", "min", "max"];
 
-                for row in table.rows.values() {
-                    // Start of row
-                    f.start_row(&mut buffer, &row.name, first_col.max_width);
+    /// True if there are no statistics to display
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
 
-                    for col in &col_info[1..] {
-                        match row.column_data.get(&col.name) {
-                            // Used column
-                            Some(col_data) => f.used_column(
-                                &mut buffer,
-                                col_data.time_unit,
-                                col_data.pct,
-                                col.max_width,
-                            ),
-                            // Unused column
-                            None => f.unused_column(&mut buffer, col.max_width),
-                        }
-                    }
+    /// Returns each statistic in display order, blank or not
+    #[inline]
+    pub fn all(&self) -> [Option<&Measurement>; 4] {
+        [
+            self.std_dev.as_ref(),
+            self.median_abs_dev.as_ref(),
+            self.min.as_ref(),
+            self.max.as_ref(),
+        ]
+    }
 
-                    // End of row
-                    f.end_row(&mut buffer);
-                }
+    /// Returns the label and time of each statistic to display
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Measurement)> {
+        Self::LABELS
+            .into_iter()
+            .zip(self.all())
+            .filter_map(|(label, time)| Some((label, time?)))
+    }
+
+    // This returns these statistics in the unit given by `format` (if fixed), or as is
+    fn with_unit(&self, format: &TimeFormat) -> Self {
+        let with_unit =
+            |time: &Option<Measurement>| time.as_ref().map(|time| time.with_unit(format));
+        Self {
+            std_dev: with_unit(&self.std_dev),
+            median_abs_dev: with_unit(&self.median_abs_dev),
+            min: with_unit(&self.min),
+            max: with_unit(&self.max),
+        }
+    }
+}
+
+/// The data of a populated cell of a table, passed to `Formatter::used_column`
+#[derive(Clone, Debug)]
+pub struct CellData<'a> {
+    /// The time measurement
+    pub time: Measurement,
+    /// The comparison to the baseline (the first column of the row)
+    pub compare: Comparison,
+    /// The lower bound of the confidence interval of the time
+    pub lower_bound: Measurement,
+    /// The upper bound of the confidence interval of the time
+    pub upper_bound: Measurement,
+    /// The benchmark's tags (possibly empty)
+    pub tags: &'a [AFlexStr],
+    /// The throughput, if it should be displayed and the benchmark has any
+    pub throughput: Option<ThroughputRate>,
+    /// Display the time. This is only false when the throughput is displayed instead
+    pub show_time: bool,
+    /// Criterion's change since its previous run, if it should be displayed and is known
+    pub change: Option<Change>,
+    /// How the times of the cell are displayed (its unit is already applied to them)
+    pub time_format: TimeFormat,
+    /// How the comparison to the baseline is displayed
+    pub comparison_style: ComparisonStyle,
+    /// Comparisons within this fraction of the baseline (ex: 0.05 for ±5%) are noise
+    pub noise_threshold: Option<f64>,
+    /// Whether the cell is the fastest or slowest of its row, when highlighted
+    pub role: CellRole,
+    /// How many times slower the cell is than the fastest cell of its row (1.0 for the fastest).
+    /// This is by throughput when throughput is compared instead of time
+    pub relative_time: f64,
+    /// The extra statistics of the benchmark that should be displayed and are known
+    pub extra_stats: ExtraStats,
+    /// The URL of criterion's HTML report of the benchmark, if cells link to them
+    pub link: Option<&'a AFlexStr>,
+    /// The marker of the speedup tier of the comparison
+    pub tier: TierMarker<'a>,
+}
+
+impl CellData<'_> {
+    /// Returns `time` (ex: the time or a bound) as it should be displayed (ex: `2.00 us`)
+    #[inline]
+    pub fn format_time(&self, time: &Measurement) -> AFlexStr {
+        time.format(&self.time_format)
+    }
+
+    /// True if the comparison to the baseline is within the noise threshold, so it shouldn't be
+    /// emphasized as faster or slower
+    #[inline]
+    pub fn is_noise(&self) -> bool {
+        self.noise_threshold
+            .is_some_and(|threshold| self.compare.is_noise(threshold))
+    }
+
+    /// Returns the comparison to the baseline as it should be displayed (ex: `1.43x faster`, or
+    /// `~equal` if it is noise)
+    #[inline]
+    pub fn compare_str(&self) -> AFlexStr {
+        if self.is_noise() {
+            NOISE_STR.into()
+        } else {
+            self.compare
+                .format(self.comparison_style, &self.time_format)
+        }
+    }
 
-                // End of table
-                f.end_table(&mut buffer);
+    /// Returns the time and/or throughput as they should be displayed (ex: `2.00 us, 47.68 MiB/s`),
+    /// followed by any extra statistics (ex: `2.00 us, σ 0.05 us`)
+    pub fn measurement(&self) -> AFlexStr {
+        let measurement = match (self.show_time, self.throughput) {
+            (true, Some(throughput)) => {
+                a_flex_fmt!(
+                    "{}, {}",
+                    self.format_time(&self.time),
+                    throughput.to_a_flex_str()
+                )
             }
+            (false, Some(throughput)) => throughput.to_a_flex_str(),
+            (_, None) => self.format_time(&self.time),
+        };
+
+        if self.extra_stats.is_empty() {
+            return measurement;
         }
 
-        // End of doc
-        f.end(&mut buffer);
+        let mut measurement = measurement.to_string();
+        for (label, time) in self.extra_stats.iter() {
+            measurement.push_str(&format!(", {label} {}", self.format_time(time)));
+        }
+        measurement.into_flex()
+    }
 
-        buffer
+    /// Returns the margin of error of the time: the largest distance from the time to either bound
+    /// of the confidence interval, in the same unit as the time (ex: 0.04 for `1.23 us ±0.04`)
+    pub fn margin(&self) -> f64 {
+        let time = self.time.base_value();
+        let margin = f64::max(
+            self.upper_bound.base_value() - time,
+            time - self.lower_bound.base_value(),
+        );
+
+        // Base units (ex: picoseconds) per unit of the time as displayed
+        let scale = time / self.time.scaled().0;
+        if scale.is_finite() {
+            margin / scale
+        } else {
+            0.0
+        }
     }
 }
 
-// *** Formatter ***
-
 /// Implement this "visitor" trait to create a `Formatter` for a new file type
 pub trait Formatter {
-    /// Called first at the start of output. Passed top level `top_comments` and a slice of table
-    /// names (typically used to build a table of contents)
+    /// Called first at the start of output. Passed top level `top_comments` and the title and
+    /// table of contents (with entries of tables, or sections holding tables)
     fn start(
         &mut self,
         buffer: &mut String,
-        top_comments: &IndexMap<FlexStr, FlexStr>,
-        tables: &[&FlexStr],
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
     );
 
     /// Called last after all processing is done
     fn end(&mut self, buffer: &mut String);
 
+    /// Returns the benchmarking `environment` (label -> value) formatted as the comment of a top
+    /// level comment section (ex: as a list). The default writes `label: value` on a line each
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        environment
+            .iter()
+            .map(|(label, value)| format!("{label}: {value}\n"))
+            .collect::<String>()
+            .into_flex()
+    }
+
+    /// Called before `start` with the footer options and the provenance of the results (ex: the git
+    /// commit), for the footer written by `end`. The default ignores it
+    fn metadata(&mut self, _metadata: &Metadata) {}
+
+    /// Called before `start` when only the tables are written, to be placed in a larger document
+    /// (see `EmbedOptions`). `start` and `end` should then write nothing around the tables, and
+    /// headings of tables and sections are only written if `options.headings`. The default
+    /// ignores it (ex: for data formats like CSV, which have nothing around the tables)
+    fn embed(&mut self, _options: &EmbedOptions) {}
+
+    /// Called before the tables of each section, when output is split into sections (ex: one per
+    /// crate in a workspace report), with the `name` of the section
+    fn start_section(&mut self, _buffer: &mut String, _name: &AFlexStr) {}
+
+    /// Called after the tables of each section
+    fn end_section(&mut self, _buffer: &mut String) {}
+
     /// Called before each table is output with the `name` of the table, a table `comment`, if any,
     /// and column maximum display width data
     fn start_table(
         &mut self,
         buffer: &mut String,
-        name: &FlexStr,
-        comment: Option<&FlexStr>,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
         columns: &[ColumnInfo],
     );
 
     /// Called at the end of each table output
     fn end_table(&mut self, buffer: &mut String);
 
+    /// Called before `start_table` when any columns of the table are in a group, with each run of
+    /// adjacent columns in the same group in column order (not including the first column, which
+    /// holds the row names). Returns true if the formatter displays the groups (ex: as a header
+    /// row spanning the columns of each group), in which case the column names passed to
+    /// `start_table` don't include their group. The default displays nothing and returns false
+    fn column_groups(&mut self, _groups: &[ColumnGroup]) -> bool {
+        false
+    }
+
     /// Called at the start of each new row with the row `name` and the `max_width` of the row name
     /// column
-    fn start_row(&mut self, buffer: &mut String, name: &FlexStr, max_width: usize);
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, max_width: usize);
+
+    /// Called instead of `start_row` when the row name links to criterion's HTML report (at
+    /// `link`) of the first benchmark of the row. By default, the link is left out
+    fn start_linked_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        _link: &AFlexStr,
+        max_width: usize,
+    ) {
+        self.start_row(buffer, name, max_width);
+    }
 
     /// Called at the end of each row
     fn end_row(&mut self, buffer: &mut String);
 
-    /// Called for each column that is populated with the `time` measurement, a comparison to baseline,
-    /// and the maximum display width of the column
-    fn used_column(
+    /// Called for each column that is populated with the `cell` data (the time measurement, a
+    /// comparison to baseline, etc.) and the maximum display width of the column. The width
+    /// includes the tags written as ` [tag1, tag2]` (see `tags_width`) and, when highlighting,
+    /// the role marker of the cell (see `CellRole::marker`)
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, max_width: usize);
+
+    /// Called for each column that is blank with the maximum display width of the the column
+    fn unused_column(&mut self, buffer: &mut String, max_width: usize);
+
+    /// Called after the last row of a table when a summary row is enabled (ex: `geomean_row`). It
+    /// is passed the `name` of the row, a summary comparison for each column (blank if the column
+    /// has none), and the same `columns` passed to `start_table`. The default writes nothing
+    fn summary_row(
+        &mut self,
+        _buffer: &mut String,
+        _name: &AFlexStr,
+        _comparisons: &[Option<Comparison>],
+        _columns: &[ColumnInfo],
+    ) {
+    }
+
+    /// Called after the last row (and any summary row) of a table with footnotes, before
+    /// `end_table`. It is passed each footnote in order of its marker, which is already after
+    /// the names of the columns and rows it is attached to. The default writes nothing
+    fn footnotes(&mut self, _buffer: &mut String, _footnotes: &[Footnote]) {}
+
+    /// Returns the link anchor a heading with the given `name` will have in the output. The
+    /// default uses Github style anchors (lowercase with spaces replaced by dashes)
+    fn anchor(&self, name: &str) -> AFlexStr {
+        name.replace(' ', "-").into_flex().to_lower()
+    }
+}
+
+// Allows the formatter to be chosen at runtime (ex: `Box<dyn Formatter>`)
+impl<F: Formatter + ?Sized> Formatter for Box<F> {
+    #[inline]
+    fn start(
+        &mut self,
+        buffer: &mut String,
+        top_comments: &IndexMap<AFlexStr, AFlexStr>,
+        toc: &Toc,
+    ) {
+        (**self).start(buffer, top_comments, toc)
+    }
+
+    #[inline]
+    fn end(&mut self, buffer: &mut String) {
+        (**self).end(buffer)
+    }
+
+    #[inline]
+    fn start_section(&mut self, buffer: &mut String, name: &AFlexStr) {
+        (**self).start_section(buffer, name)
+    }
+
+    #[inline]
+    fn end_section(&mut self, buffer: &mut String) {
+        (**self).end_section(buffer)
+    }
+
+    #[inline]
+    fn start_table(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comment: Option<&AFlexStr>,
+        columns: &[ColumnInfo],
+    ) {
+        (**self).start_table(buffer, name, comment, columns)
+    }
+
+    #[inline]
+    fn end_table(&mut self, buffer: &mut String) {
+        (**self).end_table(buffer)
+    }
+
+    #[inline]
+    fn environment(&self, environment: &IndexMap<AFlexStr, AFlexStr>) -> AFlexStr {
+        (**self).environment(environment)
+    }
+
+    #[inline]
+    fn metadata(&mut self, metadata: &Metadata) {
+        (**self).metadata(metadata)
+    }
+
+    #[inline]
+    fn embed(&mut self, options: &EmbedOptions) {
+        (**self).embed(options)
+    }
+
+    #[inline]
+    fn column_groups(&mut self, groups: &[ColumnGroup]) -> bool {
+        (**self).column_groups(groups)
+    }
+
+    #[inline]
+    fn start_row(&mut self, buffer: &mut String, name: &AFlexStr, max_width: usize) {
+        (**self).start_row(buffer, name, max_width)
+    }
+
+    #[inline]
+    fn start_linked_row(
         &mut self,
         buffer: &mut String,
-        time: TimeUnit,
-        compare: Comparison,
+        name: &AFlexStr,
+        link: &AFlexStr,
         max_width: usize,
-    );
+    ) {
+        (**self).start_linked_row(buffer, name, link, max_width)
+    }
 
-    /// Called for each column that is blank with the maximum display width of the the column
-    fn unused_column(&mut self, buffer: &mut String, max_width: usize);
+    #[inline]
+    fn end_row(&mut self, buffer: &mut String) {
+        (**self).end_row(buffer)
+    }
+
+    #[inline]
+    fn used_column(&mut self, buffer: &mut String, cell: &CellData, max_width: usize) {
+        (**self).used_column(buffer, cell, max_width)
+    }
+
+    #[inline]
+    fn unused_column(&mut self, buffer: &mut String, max_width: usize) {
+        (**self).unused_column(buffer, max_width)
+    }
+
+    #[inline]
+    fn summary_row(
+        &mut self,
+        buffer: &mut String,
+        name: &AFlexStr,
+        comparisons: &[Option<Comparison>],
+        columns: &[ColumnInfo],
+    ) {
+        (**self).summary_row(buffer, name, comparisons, columns)
+    }
+
+    #[inline]
+    fn footnotes(&mut self, buffer: &mut String, footnotes: &[Footnote]) {
+        (**self).footnotes(buffer, footnotes)
+    }
+
+    #[inline]
+    fn anchor(&self, name: &str) -> AFlexStr {
+        (**self).anchor(name)
+    }
 }
 
 // *** Functions ***
 
-fn load_config(cfg_name: impl AsRef<Path>) -> anyhow::Result<TablesConfig> {
-    match File::open(cfg_name) {
-        // If the file exists, but it can't be deserialized then report that error
-        Ok(f) => Ok(TablesConfig::try_load_config(f)?),
-        // If file just isn't there then ignore and return a blank config
-        Err(err) if err.kind() == ErrorKind::NotFound => Ok(TablesConfig::default()),
-        // Report any other I/O errors
-        Err(err) => Err(err.into()),
+/// Returns the display width of `s` in terminal columns, so wide chars (ex: CJK or emoji) count
+/// as two and zero width chars (ex: combining accents) count as none
+#[inline]
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Returns the display width of `tags` written as ` [tag1, tag2]` (zero if there are no tags)
+pub fn tags_width(tags: &[AFlexStr]) -> usize {
+    if tags.is_empty() {
+        0
+    } else {
+        // ' [' + tags + ', ' between each tag + ']'
+        let width: usize = tags.iter().map(|tag| display_width(tag)).sum();
+        width + (tags.len() - 1) * 2 + 3
     }
 }
 
+/// Returns the display width of the markup of `link` written as `[...](link)` (zero if there is no
+/// link)
+pub fn link_width(link: Option<&AFlexStr>) -> usize {
+    // '[' + '](' + link + ')'
+    link.map_or(0, |link| display_width(link) + 4)
+}
+
+// The current time in seconds since the Unix epoch
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// Returns the (UTC) date of the given seconds since the Unix epoch in `YYYY-MM-DD` format
+fn date(secs: u64) -> AFlexStr {
+    // Civil from days algorithm (from: http://howardhinnant.github.io/date_algorithms.html)
+    let z = (secs / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    a_flex_fmt!("{year:04}-{month:02}-{day:02}")
+}
+
+// Returns today's date (UTC) in `YYYY-MM-DD` format
+pub(crate) fn today() -> AFlexStr {
+    date(now_secs())
+}
+
+// Returns the current time (UTC) in `YYYY-MM-DD HH:MM:SS UTC` format
+pub(crate) fn now() -> AFlexStr {
+    let secs = now_secs();
+    let (hour, min, sec) = (secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    a_flex_fmt!("{} {hour:02}:{min:02}:{sec:02} UTC", date(secs))
+}
+
 /// Top level function that can be used to build table data. It takes a reader (raw `cargo-criterion`
-/// JSON data), a `Formatter` (only option is `GFMFormatter` as of this writing), and the name of
-/// a file in `TablesConfig` toml format (the file is optional, simply skipped if it can't be found)
+/// JSON data), a `Formatter` (ex: `GFMFormatter`), and the name of a file in `TablesConfig` toml
+/// format (the file is optional, simply skipped if it can't be found). The tables are returned
+/// along with any config entries that matched no table. See `builder::TableBuilder` for more
+/// options
 pub fn build_tables(
     read: impl Read,
     fmt: impl Formatter,
     cfg_name: impl AsRef<Path>,
-) -> anyhow::Result<String> {
-    let raw_data = RawCriterionData::from_reader(read)?;
-    let data = CriterionTableData::from_raw(&raw_data)?;
+) -> Result<(String, Vec<ConfigWarning>), Error> {
+    TableBuilder::new()
+        .config(load_config(cfg_name)?)
+        .add_reader(read)
+        .formatter(fmt)
+        .build_with_warnings()
+}
+
+/// Like `build_tables`, but the formatter is looked up in `registry` by the name of its format
+/// (ex: `csv`, or one registered by the caller)
+pub fn build_tables_as(
+    read: impl Read,
+    registry: &FormatterRegistry,
+    format: &str,
+    cfg_name: impl AsRef<Path>,
+) -> Result<(String, Vec<ConfigWarning>), Error> {
     let config = load_config(cfg_name)?;
-    Ok(data.make_tables(fmt, &config))
+    let f = registry.make(format, &config)?;
+    TableBuilder::new()
+        .config(config)
+        .add_reader(read)
+        .formatter(f)
+        .build_with_warnings()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names<'a>(names: impl Iterator<Item = &'a AFlexStr>) -> Vec<&'a str> {
+        names.map(AFlexStr::as_str).collect()
+    }
+
+    fn time(data: &CriterionTableData, table: &str, column: &str, row: &str) -> Option<f64> {
+        let column = data.table(table)?.row(row)?.cell(column)?;
+        column
+            .measurement
+            .as_time()
+            .map(|time| time.as_nanoseconds())
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn par_make_tables_split_in_order() {
+        use crate::formatter::GFMFormatter;
+
+        let data = CriterionTableData::from_times(&[
+            ("Fib/std/10", 100.0),
+            ("Sort/std/10", 10.0),
+            ("Parse/std/10", 1.0),
+        ]);
+        let config = TablesConfig::default();
+        let make_formatter = || Ok::<_, Error>(GFMFormatter::default());
+
+        let serial = data
+            .make_tables_split(make_formatter, &config)
+            .expect("split");
+        let parallel = data
+            .par_make_tables_split(make_formatter, &config)
+            .expect("split");
+        assert_eq!(names(parallel.keys()), ["Fib", "Sort", "Parse"]);
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn merge_adds_new_benchmarks() {
+        let first = CriterionTableData::from_times(&[("Fib/std/10", 100.0)]);
+        let second = CriterionTableData::from_times(&[
+            ("Fib/fast/10", 50.0),
+            ("Fib/fast/20", 70.0),
+            ("Sort/std/10", 10.0),
+        ]);
+
+        let data = CriterionTableData::merge(vec![first, second], DuplicatePolicy::Error)
+            .expect("no duplicates");
+        assert_eq!(names(data.tables.keys()), ["Fib", "Sort"]);
+
+        let fib = data.table("Fib").expect("a table");
+        assert_eq!(names(fib.column_names()), ["std", "fast"]);
+        assert_eq!(names(fib.rows.keys()), ["10", "20"]);
+        assert_eq!(time(&data, "Fib", "fast", "20"), Some(70.0));
+    }
+
+    #[test]
+    fn merge_duplicates() {
+        let merge = |policy| {
+            let all_data = vec![
+                CriterionTableData::from_times(&[("Fib/std/10", 100.0)]),
+                CriterionTableData::from_times(&[("Fib/std/10", 50.0)]),
+            ];
+            CriterionTableData::merge(all_data, policy)
+        };
+        let merged_time = |policy| {
+            let data = merge(policy).expect("merged");
+            time(&data, "Fib", "std", "10")
+        };
+
+        assert!(matches!(
+            merge(DuplicatePolicy::Error),
+            Err(Error::DuplicateBenchmark { .. })
+        ));
+        assert_eq!(merged_time(DuplicatePolicy::KeepFirst), Some(100.0));
+        assert_eq!(merged_time(DuplicatePolicy::KeepBest), Some(50.0));
+        assert_eq!(merged_time(DuplicatePolicy::Average), Some(75.0));
+    }
+
+    #[test]
+    fn merge_nothing() {
+        assert!(matches!(
+            CriterionTableData::merge(Vec::new(), DuplicatePolicy::Error),
+            Err(Error::NoData)
+        ));
+    }
+
+    #[test]
+    fn common_affixes_at_separators() {
+        let affixes = |names: &[&str]| {
+            let names: Vec<AFlexStr> = names.iter().map(|name| name.to_flex()).collect();
+            common_affixes(&names.iter().collect::<Vec<_>>())
+        };
+
+        assert_eq!(affixes(&["bench_fib_10", "bench_fib_20"]), Some((10, 0)));
+        assert_eq!(affixes(&["parse_u8_fast", "parse_u16_fast"]), Some((6, 5)));
+        // Words are never split
+        assert_eq!(affixes(&["fib10", "fib20"]), None);
+        // Never a whole name
+        assert_eq!(affixes(&["a_", "a_b"]), None);
+        assert_eq!(affixes(&["bench_fib"]), None);
+        assert_eq!(affixes(&[]), None);
+    }
+
+    #[test]
+    fn natural_order() {
+        let mut names = vec!["n100", "n20", "n3", "a", "n", "n03", "b10"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, ["a", "b10", "n", "n03", "n3", "n20", "n100"]);
+
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+        assert_eq!(natural_cmp("x", "x"), Ordering::Equal);
+    }
+
+    #[test]
+    fn sort_rows() {
+        let sorted = |config: &str| {
+            let config = TablesConfig::try_load_config(config.as_bytes()).expect("valid config");
+            let mut data = CriterionTableData::from_times(&[
+                ("Fib/std/100", 300.0),
+                ("Fib/fast/100", 100.0),
+                ("Fib/std/20", 100.0),
+                ("Fib/fast/20", 90.0),
+                ("Fib/std/3", 200.0),
+                ("Fib/fast/3", 400.0),
+            ]);
+            data.apply_config(&config).expect("applied");
+
+            let fib = data.table("Fib").expect("a table");
+            let rows: Vec<_> = fib.rows.keys().map(AFlexStr::to_string).collect();
+            rows
+        };
+
+        assert_eq!(sorted(""), ["100", "20", "3"]);
+        assert_eq!(sorted(r#"sort_rows = "name""#), ["3", "20", "100"]);
+        assert_eq!(sorted(r#"sort_rows = "time""#), ["20", "3", "100"]);
+        assert_eq!(sorted(r#"sort_rows = "speedup""#), ["3", "20", "100"]);
+        assert_eq!(
+            sorted("sort_rows = \"time\"\nsort_descending = true"),
+            ["100", "3", "20"]
+        );
+        assert_eq!(
+            sorted("sort_rows = \"name\"\n[table.Fib]\nsort_rows = \"time\""),
+            ["20", "3", "100"]
+        );
+    }
+
+    #[test]
+    fn transpose() {
+        let config = TablesConfig::try_load_config("[table.Fib]\ntranspose = true".as_bytes())
+            .expect("valid config");
+        let mut data = CriterionTableData::from_times(&[
+            ("Fib/std/10", 100.0),
+            ("Fib/fast/10", 50.0),
+            ("Fib/std/20", 200.0),
+            ("Fib/fast/20", 400.0),
+            ("Sort/std/10", 10.0),
+            ("Sort/fast/10", 5.0),
+        ]);
+        data.apply_config(&config).expect("applied");
+
+        let fib = data.table("Fib").expect("a table");
+        assert_eq!(names(fib.column_names()), ["10", "20"]);
+        assert_eq!(names(fib.rows.keys()), ["std", "fast"]);
+        assert_eq!(time(&data, "Fib", "20", "fast"), Some(400.0));
+
+        // Cells are still compared to the baseline column before transposing
+        let fast = fib.row("fast").expect("a row");
+        let speedups: Vec<_> = fast
+            .column_data
+            .values()
+            .map(|col| col.pct.ratio())
+            .collect();
+        assert_eq!(speedups, [2.0, 0.5]);
+
+        // Other tables are left as is
+        let sort = data.table("Sort").expect("a table");
+        assert_eq!(names(sort.column_names()), ["std", "fast"]);
+    }
+
+    #[test]
+    fn rename() {
+        let config = TablesConfig::try_load_config(
+            r#"
+            [rename.tables]
+            "Fib" = "Fibonacci"
+            [rename.columns]
+            "std" = "Standard"
+            [rename.rows]
+            "10" = "n = 10"
+            "#
+            .as_bytes(),
+        )
+        .expect("valid config");
+        let mut data =
+            CriterionTableData::from_times(&[("Fib/std/10", 100.0), ("Fib/fast/10", 50.0)]);
+
+        data.rename(&config).expect("no collisions");
+        assert_eq!(names(data.tables.keys()), ["Fibonacci"]);
+
+        let fib = data.table("Fibonacci").expect("a table");
+        assert_eq!(fib.name, "Fibonacci");
+        assert_eq!(names(fib.column_names()), ["Standard", "fast"]);
+        assert_eq!(names(fib.rows.keys()), ["n = 10"]);
+    }
+
+    #[test]
+    fn rename_collision() {
+        let config = TablesConfig::try_load_config(
+            r#"
+            [rename.columns]
+            "fast" = "std"
+            "#
+            .as_bytes(),
+        )
+        .expect("valid config");
+        let mut data =
+            CriterionTableData::from_times(&[("Fib/std/10", 100.0), ("Fib/fast/10", 50.0)]);
+
+        assert!(matches!(
+            data.rename(&config),
+            Err(Error::RenameCollision { .. })
+        ));
+    }
+
+    #[test]
+    fn series_regressions_against_baseline_column() {
+        let mut data = CriterionTableData::from_times(&[
+            ("Fib/std/10", 100.0),
+            ("Fib/fast/10", 150.0),
+            ("Fib/fast/20", 70.0),
+            ("Fib/slow/20", 200.0),
+        ]);
+        data.series = true;
+
+        // Row `20` has no `std` benchmark, so `fast` isn't its baseline
+        let regressions: Vec<_> = data
+            .regressions(0.1)
+            .into_iter()
+            .map(|r| (r.column.to_string(), r.row.to_string(), r.change))
+            .collect();
+        assert_eq!(regressions, [("fast".to_string(), "10".to_string(), 0.5)]);
+    }
+
+    #[test]
+    fn baseline_run_regressions() {
+        let baseline =
+            CriterionTableData::from_times(&[("Fib/std/10", 100.0), ("Fib/std/20", 100.0)]);
+        let latest =
+            CriterionTableData::from_times(&[("Fib/std/10", 130.0), ("Fib/std/20", 105.0)]);
+        let data = CriterionTableData::with_baseline_run(&baseline, &"v1".into(), &latest)
+            .expect("valid benchmarks");
+
+        let regressions: Vec<_> = data
+            .regressions(0.1)
+            .into_iter()
+            .map(|r| (r.column.to_string(), r.row.to_string()))
+            .collect();
+        assert_eq!(regressions, [("std".to_string(), "10".to_string())]);
+    }
+
+    #[test]
+    fn deltas_skip_zero_baseline() {
+        let baseline =
+            CriterionTableData::from_times(&[("Fib/std/10", 0.0), ("Fib/std/20", 100.0)]);
+        let latest =
+            CriterionTableData::from_times(&[("Fib/std/10", 100.0), ("Fib/std/20", 150.0)]);
+
+        let deltas = CriterionTableData::deltas(&baseline, &latest, 0.1);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].row, "20");
+        assert_eq!(deltas[0].change, 0.5);
+    }
+
+    #[test]
+    fn index_times_in_base_unit() {
+        use crate::formatter::GFMFormatter;
+
+        let data = CriterionTableData::from_times(&[("Fib/std/10", 1.0), ("Fib/std/20", 2.0)]);
+        let index = data
+            .make_index(&GFMFormatter::default())
+            .expect("serialized");
+        let index: serde_json::Value = serde_json::from_str(&index).expect("JSON");
+
+        let column = &index["tables"][0]["columns"][0];
+        assert_eq!(column["fastest_base"], 1000.0);
+        assert_eq!(column["slowest_base"], 2000.0);
+    }
 }
@@ -0,0 +1,97 @@
+use std::io::{BufRead, BufReader, Read};
+
+use regex::Regex;
+
+use crate::divan::{make_id, MODULE_SEPARATOR};
+use crate::{BenchmarkComplete, Error, RawCriterionData};
+
+// Ex: `test fib::recursive::n10 ... bench:       1,234 ns/iter (+/- 56)`. Newer toolchains add
+// fractional digits
+const BENCH_LINE: &str =
+    r"^test (?P<name>\S+)\s+\.\.\. bench:\s+(?P<time>[\d,.]+) ns/iter \(\+/- (?P<dev>[\d,.]+)\)";
+
+// *** Functions ***
+
+/// Load raw benchmark data from the text output of `cargo bench` with the nightly libtest
+/// harness (`#[bench]`). All other lines (ex: `running 3 tests` or ignored tests) are skipped.
+///
+/// Test paths map onto table/column/row like divan's hierarchy: the last two parts are the column
+/// and row, and any parts before them are joined by `::` into the table (ex:
+/// `fib::recursive::n10` is column `recursive` and row `n10` of table `fib`). The resulting ids
+/// are split via the id scheme
+pub fn from_reader(r: impl Read) -> Result<Vec<RawCriterionData>, Error> {
+    let regex = Regex::new(BENCH_LINE)?;
+    let mut raw_data = Vec::new();
+
+    for line in BufReader::new(r).lines() {
+        let line = line?;
+
+        if let Some(captures) = regex.captures(line.trim_end()) {
+            let number = |name: &str| {
+                let s = captures[name].replace(',', "");
+                s.parse::<f64>().map_err(|_| Error::InvalidTime { time: s })
+            };
+            let path: Vec<_> = captures["name"].split(MODULE_SEPARATOR).collect();
+            let time = number("time")?;
+
+            // Libtest reports the median of its samples, and the spread between the fastest and
+            // slowest sample as the deviation
+            let benchmark = BenchmarkComplete::from_samples(
+                make_id(&path),
+                time,
+                time,
+                number("dev")? / 2.0,
+                Vec::new(),
+            );
+            raw_data.push(RawCriterionData::Benchmark(Box::new(benchmark)));
+        }
+    }
+
+    Ok(raw_data)
+}
+
+// Whether any line of `input` is a libtest benchmark result, for detecting the input format
+pub(crate) fn is_libtest(input: &[u8]) -> bool {
+    match Regex::new(BENCH_LINE) {
+        Ok(regex) => String::from_utf8_lossy(input)
+            .lines()
+            .any(|line| regex.is_match(line.trim_end())),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "running 3 tests
+test fib::iterative::n10 ... bench:          12 ns/iter (+/- 2)
+test fib::recursive::n10 ... bench:       1,234 ns/iter (+/- 56)
+test parse::ints         ... bench:       7.50 ns/iter (+/- 0.50)
+test other ... ignored
+
+test result: ok. 0 passed; 0 failed; 1 ignored; 3 measured; 0 filtered out
+";
+
+    #[test]
+    fn from_reader_bench_lines() {
+        let raw_data = from_reader(OUTPUT.as_bytes()).expect("valid output");
+        let benchmarks: Vec<_> = raw_data
+            .iter()
+            .filter_map(|data| data.benchmark())
+            .collect();
+        assert_eq!(benchmarks.len(), 3);
+
+        assert_eq!(benchmarks[1].id, "fib/recursive/n10");
+        assert_eq!(benchmarks[1].median.estimate, 1_234.0);
+        assert_eq!(benchmarks[1].median.lower_bound, 1_234.0 - 28.0);
+        assert_eq!(benchmarks[2].id, "parse/ints");
+        assert_eq!(benchmarks[2].median.estimate, 7.5);
+    }
+
+    #[test]
+    fn detect_bench_lines() {
+        assert!(is_libtest(OUTPUT.as_bytes()));
+        assert!(!is_libtest(b"running 0 tests\n"));
+    }
+}
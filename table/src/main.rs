@@ -1,17 +1,1396 @@
-use std::io;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
-use criterion_table::build_tables;
-use criterion_table::formatter::GFMFormatter;
+use clap::{Parser, Subcommand, ValueEnum};
+use criterion_table::badge::Badge;
+use criterion_table::formatter::{
+    BoxStyle, FormatterRegistry, GFMFormatter, LatexFormatter, Profile, TemplateFormatter,
+    TermFormatter, DEFAULT_FORMAT,
+};
+use criterion_table::report::Report;
+use criterion_table::workspace::WorkspaceData;
+use criterion_table::{
+    chart, criterion_dir, history, inject, load_config, mdbook, ComparisonStyle, CriterionMessage,
+    CriterionTableData, Delta, DuplicatePolicy, Formatter, Highlight, IdParser, IdPattern,
+    InputFormat, Metric, RawCriterionData, RowSort, TablesConfig, TimeFormat, UnitPolicy,
+    CRITERION_DIR,
+};
+use flexstr::{ToAFlexStr, ToFlex};
+
+mod gather;
 
 const TABLES_CONFIG: &str = "tables.toml";
+const WORKSPACE_FILE: &str = "benchmarks.json";
+const SPLIT_INDEX_FILE: &str = "index.md";
+const HISTORY_FILE: &str = "benchmark-history.jsonl";
+// The tables are re-rendered at most this often in follow mode
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+// Clears the terminal and moves the cursor home
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+// How often watched files are checked for changes in watch mode
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
 
-fn main() {
-    match build_tables(io::stdin(), GFMFormatter, TABLES_CONFIG) {
-        Ok(data) => {
-            println!("{data}");
+/// Generate comparison tables from cargo-criterion benchmark output (read from stdin by default)
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Read the raw Criterion JSON from this file instead of stdin. This can be given multiple
+    /// times (ex: results from several machines), merging the benchmarks of every file
+    #[arg(short, long, value_name = "FILE")]
+    input: Vec<PathBuf>,
+
+    /// The format of the benchmark data read from stdin, `--input` or `--run` files
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = InputFormat::Criterion)]
+    input_format: InputFormat,
+
+    /// Skip benchmarks that can't be tabulated (ex: a malformed id or a duplicate column)
+    /// with a warning, instead of failing. Applies to stdin, `--input` and `--from-dir` data
+    #[arg(long)]
+    skip_invalid: bool,
+
+    /// Discard a truncated last message of criterion's message stream (ex: when cargo-criterion was
+    /// interrupted) with a warning, instead of failing
+    #[arg(long)]
+    recover: bool,
+
+    /// Report on stderr how many records were read, which tables were created and which benchmarks
+    /// were dropped by filters
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Write the output to this file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Write each table to its own file in this directory (ex: `benches/fibonacci.md`), plus an
+    /// `index.md` linking to each of them, instead of all tables to one output
+    #[arg(long, value_name = "DIR", conflicts_with = "output")]
+    split_tables: Option<PathBuf>,
+
+    /// Replace the section of this file (ex: `README.md`) between the `<!-- criterion-table start
+    /// -->` and `<!-- criterion-table end -->` markers with the tables, keeping the rest as is
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["output", "split_tables"])]
+    inject: Option<PathBuf>,
+
+    /// The output format: `gfm` (the default, or `format` in the config), `html`, `csv`, `tsv`,
+    /// `json`, `rst`, `term`, `org`, `confluence`, `latex` or `mediawiki`. It can be given
+    /// multiple times with a file to write each format to from one run (ex: `--format
+    /// gfm:README.md --format csv:results.csv`). A format without a file is the one written to
+    /// stdout, `--output`, `--inject` or `--split-tables` (and used by subcommands)
+    #[arg(short, long, value_name = "FORMAT[:FILE]", value_parser = parse_format)]
+    format: Vec<(String, Option<PathBuf>)>,
+
+    /// Render the tables through this Handlebars template file instead of an output format (ex:
+    /// `{{#each tables}}## {{table}}{{/each}}`). Each cell has both raw numbers and display values
+    #[arg(long, value_name = "FILE", value_parser = parse_template)]
+    template: Option<String>,
+
+    /// When to color faster and slower cells of `term` output (`auto` colors only when writing to a
+    /// terminal and `NO_COLOR` isn't set)
+    #[arg(long, value_enum, default_value_t = Color::Auto)]
+    color: Color,
+
+    /// Draw the borders of `term` output with plain ASCII instead of box-drawing characters
+    #[arg(long)]
+    ascii: bool,
+
+    /// Write `latex` output as a full standalone document (for previewing) instead of just the
+    /// tables
+    #[arg(long)]
+    standalone: bool,
+
+    /// The tables config file (a missing file is treated as a blank config)
+    #[arg(short, long, value_name = "FILE", default_value = TABLES_CONFIG)]
+    config: PathBuf,
+
+    /// The markdown dialect to tailor the output for (overrides `gfm.profile` in the config)
+    #[arg(long, value_enum)]
+    profile: Option<Profile>,
+
+    /// Compare the latest results against this baseline saved by criterion (`base` for the
+    /// previous run or the name given to `--save-baseline`) instead of reading stdin
+    #[arg(long, value_name = "NAME")]
+    baseline: Option<String>,
+
+    /// Compare the results against a snapshot written by the `save` command, with a `baseline` and
+    /// a `latest` column per benchmark
+    #[arg(long, value_name = "FILE", conflicts_with = "baseline")]
+    snapshot: Option<PathBuf>,
+
+    /// Compare the results against an earlier run (ex: of the previous release) read from this raw
+    /// benchmark data file, added as a leading column of every table that all others are compared
+    /// to
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["snapshot", "baseline", "run"])]
+    baseline_file: Option<PathBuf>,
+
+    /// The name of the column of the `--baseline-file` run (ex: `v1.0`)
+    #[arg(
+        long,
+        value_name = "LABEL",
+        default_value = "previous",
+        requires = "baseline_file"
+    )]
+    baseline_label: String,
+
+    /// Read the results from a snapshot written by the `save` command instead of raw benchmark
+    /// data (ex: to render them again in another format without re-running the benchmarks)
+    #[arg(long, value_name = "FILE",
+          conflicts_with_all = ["input", "run", "baseline", "from_dir", "follow"])]
+    from_snapshot: Option<PathBuf>,
+
+    /// Compare runs (ex: `--run main=main.json --run pr=pr.json`), each read from its own raw
+    /// Criterion JSON file. Each run label becomes a column, compared to the first run
+    #[arg(long, value_name = "LABEL=FILE", value_parser = parse_run,
+          conflicts_with_all = ["input", "baseline", "from_dir"])]
+    run: Vec<(String, PathBuf)>,
+
+    /// Prefix each column name with its run label (ex: `main/std`) instead of replacing it with the
+    /// label (which moves the column name into the row name)
+    #[arg(long, requires = "run")]
+    prefix_columns: bool,
+
+    /// What to do with benchmarks found in more than one input file (overrides `duplicates` in the
+    /// config)
+    #[arg(long, value_enum, value_name = "POLICY")]
+    duplicates: Option<DuplicatePolicy>,
+
+    /// The estimate of each benchmark to tabulate: `typical`, `mean`, `median`, `slope`, or a robust
+    /// estimate recomputed from the samples, `trimmed_mean:FRACTION` or `percentile:PERCENTILE`
+    /// (overrides `metric` in the config)
+    #[arg(short, long)]
+    metric: Option<Metric>,
+
+    /// Read the latest results directly from the criterion output directory instead of stdin
+    /// (ex: after a plain `cargo bench`)
+    #[arg(long, conflicts_with_all = ["input", "baseline"])]
+    from_dir: bool,
+
+    /// The criterion output directory results and baselines are read from
+    #[arg(long, value_name = "DIR", default_value = CRITERION_DIR)]
+    criterion_dir: PathBuf,
+
+    /// Only keep benchmarks slower than their baseline by more than this fraction (ex: 0.05),
+    /// sorted worst first (overrides `regressions_only` in the config)
+    #[arg(long, value_name = "THRESHOLD")]
+    regressions_only: Option<f64>,
+
+    /// Exit with an error (after writing the output) and print a summary of regressions to stderr
+    /// if any benchmark is slower than its baseline by more than this fraction (ex: 0.05). The
+    /// baseline is `--baseline`, `--snapshot` or the previous run of a series, otherwise criterion's
+    /// previous run
+    #[arg(long, value_name = "THRESHOLD")]
+    fail_on_regression: Option<f64>,
+
+    /// Sort the rows of each table (overrides `sort_rows` in the config)
+    #[arg(long, value_enum, value_name = "ORDER")]
+    sort_rows: Option<RowSort>,
+
+    /// Sort rows in descending instead of ascending order
+    #[arg(long)]
+    sort_descending: bool,
+
+    /// Display each time with this many significant digits instead of two decimal places
+    /// (overrides `time_format.significant_digits` in the config)
+    #[arg(long, value_name = "DIGITS")]
+    significant_digits: Option<usize>,
+
+    /// The unit times are displayed in: `auto` per time, `table` (or `table-max`) for the unit of
+    /// the fastest (or slowest) time of each table, or a fixed unit (overrides `time_format.unit`
+    /// in the config)
+    #[arg(long, value_enum, value_name = "UNIT")]
+    time_unit: Option<UnitPolicy>,
+
+    /// How comparisons to the baseline are displayed (overrides `comparison_style` in the config)
+    #[arg(long, value_enum, value_name = "STYLE")]
+    comparison_style: Option<ComparisonStyle>,
+
+    /// Highlight the fastest (and optionally slowest) cell of each row (overrides `highlight` in
+    /// the config)
+    #[arg(long, value_enum)]
+    highlight: Option<Highlight>,
+
+    /// Display times below this many nanoseconds as `< ` this time (overrides
+    /// `time_format.min_time` in the config)
+    #[arg(long, value_name = "NANOS")]
+    min_time: Option<f64>,
+
+    /// Display comparisons within this fraction of the baseline (ex: 0.05 for ±5%) as `~equal`,
+    /// without emphasis (overrides `noise_threshold` in the config)
+    #[arg(long, value_name = "FRACTION")]
+    noise_threshold: Option<f64>,
+
+    /// Display this extra statistic of each benchmark after its time (or as an extra column in CSV
+    /// and TSV output). This can be given multiple times (enabled in `extra_stats` in the config)
+    #[arg(long, value_enum, value_name = "STAT")]
+    extra_stat: Vec<ExtraStat>,
+
+    /// Add an `Environment` section at the top of the output, with the CPU model, core count, OS,
+    /// rustc and criterion versions and the date gathered automatically (sets
+    /// `environment.enabled` in the config)
+    #[arg(long)]
+    environment: bool,
+
+    /// A value of the environment section, used instead of the one gathered (ex: `CPU=Apple M2`),
+    /// or left out if blank. This can be given multiple times and implies `--environment` (added
+    /// to `environment.values` in the config)
+    #[arg(long, value_name = "LABEL=VALUE", value_parser = parse_env_value)]
+    env_value: Vec<(String, String)>,
+
+    /// Compact GFM output for CI, to post as a PR comment or write to `$GITHUB_STEP_SUMMARY`: no
+    /// title, table of contents or footer, each table collapsible, and only significant
+    /// comparisons emphasized (sets `gfm.compact` in the config)
+    #[arg(long)]
+    github: bool,
+
+    /// Write only the tables, without the title, table of contents or footer, so the output can be
+    /// placed in a larger generated document as is (sets `embed` in the config)
+    #[arg(long)]
+    embed: bool,
+
+    /// Write the heading of each table when embedding (implies `--embed`, and sets
+    /// `embed.headings` in the config)
+    #[arg(long)]
+    embed_headings: bool,
+
+    /// Only keep tables with a significant change (overrides `changed_tables_only` in the config)
+    #[arg(long)]
+    changed_only: bool,
+
+    /// Only keep benchmarks whose id matches this glob (ex: `Fibonacci/*`) or `re:` prefixed
+    /// regex. This can be given multiple times (added to `include` in the config)
+    #[arg(long, value_name = "PATTERN")]
+    include: Vec<IdPattern>,
+
+    /// Remove benchmarks whose id matches this glob or `re:` prefixed regex. This can be given
+    /// multiple times (added to `exclude` in the config)
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<IdPattern>,
+
+    /// Keep reading the cargo-criterion JSON stream from stdin as benchmarks run, re-rendering the
+    /// (partial) tables to the terminal or output file as benchmarks complete (at most twice a
+    /// second)
+    #[arg(long, conflicts_with_all = ["input", "run", "baseline", "snapshot", "from_dir"])]
+    follow: bool,
+
+    /// Keep running, regenerating the output whenever the benchmark data changes: the criterion
+    /// directory (with `--from-dir` or `--baseline`), `--input`, `--run` and snapshot files, and
+    /// the config file
+    #[arg(long, conflicts_with = "follow")]
+    watch: bool,
+
+    /// Explore the tables in an interactive terminal viewer (scrolling, sorting by any column,
+    /// filtering rows and switching between times, throughput and ratios) instead of writing them
+    #[cfg(feature = "interactive")]
+    #[arg(long, conflicts_with_all = ["output", "split_tables", "inject", "follow", "watch"])]
+    interactive: bool,
+
+    /// Also write a JSON index of each table's name, anchor and summary stats to this file
+    #[arg(long, value_name = "FILE")]
+    index: Option<PathBuf>,
+
+    /// Also write each table to a worksheet of an Excel workbook (`.xlsx`) at this path
+    #[cfg(feature = "xlsx")]
+    #[arg(long, value_name = "FILE")]
+    xlsx: Option<PathBuf>,
+
+    /// Also write an SVG bar chart of each table to this directory (ex: `charts/fibonacci.svg`),
+    /// which GFM output references as images below each table via `gfm.chart_dir` in the config
+    #[arg(long, value_name = "DIR")]
+    charts: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExtraStat {
+    /// The standard deviation of the samples
+    Stddev,
+    /// Criterion's median absolute deviation
+    Mad,
+    /// The fastest sample
+    Min,
+    /// The slowest sample
+    Max,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build a single report with a section per workspace member from the raw Criterion JSON file
+    /// saved in each member's directory
+    Workspace {
+        /// The name of the raw Criterion JSON file in each member's directory
+        #[arg(long, value_name = "NAME", default_value = WORKSPACE_FILE)]
+        file: PathBuf,
+
+        /// Path to the workspace's Cargo.toml (defaults to the current workspace)
+        #[arg(long, value_name = "PATH")]
+        manifest_path: Option<PathBuf>,
+    },
+    /// Write a summary page (winners and geomeans per table) linking to a detail page per table
+    /// (including all statistics, or a bar chart as HTML) into a directory
+    Report {
+        /// The directory the report pages are written to
+        #[arg(long, value_name = "DIR")]
+        out_dir: PathBuf,
+    },
+    /// Build tables from a directory of dated raw Criterion JSON files (ex: `2024-05-01.json`)
+    /// with a column per date, giving a view of each benchmark over time
+    Series {
+        /// The directory holding the raw Criterion JSON files
+        dir: PathBuf,
+    },
+    /// Save the results (read the same as when generating tables) to a snapshot file that can be
+    /// compared against later via `--snapshot`
+    Save {
+        /// The snapshot file to write
+        file: PathBuf,
+    },
+    /// Write a badge (shields.io endpoint JSON, or SVG) for each cell selected via `[[badges]]` in
+    /// the config into a directory
+    Badge {
+        /// The directory the badges are written to
+        #[arg(long, value_name = "DIR")]
+        out_dir: PathBuf,
+
+        /// Write self-contained SVG badges instead of shields.io endpoint JSON
+        #[arg(long)]
+        svg: bool,
+    },
+    /// Record runs in a local history store (a JSON Lines file) and build trend tables from them
+    History {
+        /// The history store
+        #[arg(long, value_name = "FILE", default_value = HISTORY_FILE)]
+        file: PathBuf,
+
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+    /// Compare two runs (read via `--input-format`, ex: raw Criterion JSON from the base branch and
+    /// a PR) and write a markdown table of the benchmarks whose time changed by more than a
+    /// threshold, with the old time, new time and percent change of each
+    Diff {
+        /// The baseline run
+        old: PathBuf,
+
+        /// The run compared to the baseline
+        new: PathBuf,
+
+        /// Only list benchmarks whose time changed by more than this fraction (ex: 0.05 for ±5%)
+        #[arg(long, value_name = "FRACTION", default_value_t = 0.0)]
+        threshold: f64,
+
+        /// Exit with an error (after writing the table) if any benchmark is slower than the
+        /// baseline by more than this fraction (ex: 0.10)
+        #[arg(long, value_name = "FRACTION")]
+        fail_on: Option<f64>,
+    },
+    /// Run as an mdBook preprocessor, replacing `{{#criterion-table data.json}}` placeholders
+    Mdbook {
+        #[command(subcommand)]
+        command: Option<MdbookCommand>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommand {
+    /// Append the results (read the same as when generating tables) to the history store
+    Record {
+        /// The label of the run (ex: a commit hash)
+        #[arg(long)]
+        label: String,
+    },
+    /// Build tables with a column per recorded run, showing the trend of each benchmark. The latest
+    /// cell of each benchmark that got slower run over run across its last three runs is tagged
+    /// `regressing`
+    Trend {
+        /// The number of most recent runs to include
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        last: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum MdbookCommand {
+    /// Called by mdBook to check if a renderer is supported (all are, as output is markdown)
+    Supports { renderer: String },
+}
+
+fn load_config_with_args(args: &Args) -> anyhow::Result<TablesConfig> {
+    let mut config = load_config(&args.config)?;
+    if let Some(format) = args.main_format() {
+        config.format = Some(format.into());
+    }
+    if let Some(format) = &config.format {
+        check_format(format).map_err(|err| anyhow::anyhow!("{}: {err}", args.config.display()))?;
+    }
+    if let Some(metric) = args.metric {
+        config.metric = metric;
+    }
+    if args.regressions_only.is_some() {
+        config.regressions_only = args.regressions_only;
+    }
+    if let Some(duplicates) = args.duplicates {
+        config.duplicates = duplicates;
+    }
+    if args.sort_rows.is_some() {
+        config.sort_rows = args.sort_rows;
+    }
+    if args.sort_descending {
+        config.sort_descending = true;
+    }
+    if args.significant_digits.is_some() {
+        config.time_format.significant_digits = args.significant_digits;
+    }
+    if let Some(unit) = args.time_unit {
+        config.time_format.unit = unit;
+    }
+    if let Some(style) = args.comparison_style {
+        config.comparison_style = style;
+    }
+    if let Some(highlight) = args.highlight {
+        config.highlight = highlight;
+    }
+    if args.min_time.is_some() {
+        config.time_format.min_time = args.min_time;
+    }
+    if args.noise_threshold.is_some() {
+        config.noise_threshold = args.noise_threshold;
+    }
+    for stat in &args.extra_stat {
+        match stat {
+            ExtraStat::Stddev => config.extra_stats.stddev = true,
+            ExtraStat::Mad => config.extra_stats.mad = true,
+            ExtraStat::Min => config.extra_stats.min = true,
+            ExtraStat::Max => config.extra_stats.max = true,
+        }
+    }
+    if args.environment || !args.env_value.is_empty() {
+        config.environment.enabled = true;
+    }
+    for (label, value) in &args.env_value {
+        config
+            .environment
+            .values
+            .insert(label.to_flex(), value.to_flex());
+    }
+    if args.github {
+        config.gfm.compact = true;
+    }
+    if args.changed_only {
+        config.changed_tables_only = true;
+    }
+    if args.embed || args.embed_headings {
+        let embed = config.embed.get_or_insert_with(Default::default);
+        embed.headings |= args.embed_headings;
+    }
+    config.include.extend(args.include.iter().cloned());
+    config.exclude.extend(args.exclude.iter().cloned());
+    gather_config(&mut config);
+    Ok(config)
+}
+
+// Add what the library leaves to its caller to collect to `config`
+fn gather_config(config: &mut TablesConfig) {
+    config.footer.provenance = gather::provenance(&config.footer);
+    if config.environment.enabled {
+        config.environment.gathered = gather::environment();
+    }
+}
+
+fn make_gfm_formatter(config: &TablesConfig, profile: Option<Profile>) -> GFMFormatter {
+    let mut options = config.gfm.clone();
+    if let Some(profile) = profile {
+        options.profile = profile;
+    }
+    GFMFormatter::new(options)
+}
+
+impl Args {
+    // The format of the main output: the first given without a file, if any
+    fn main_format(&self) -> Option<&str> {
+        self.format
+            .iter()
+            .find(|(_, path)| path.is_none())
+            .map(|(format, _)| format.as_str())
+    }
+
+    // The formats written to their own file, in addition to the main output
+    fn format_files(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.format
+            .iter()
+            .filter_map(|(format, path)| Some((format.as_str(), path.as_deref()?)))
+    }
+
+    // The main output is skipped if every format was given a file and no other output was
+    fn writes_main_output(&self) -> bool {
+        self.format.iter().all(|(_, path)| path.is_none())
+            || self.output.is_some()
+            || self.inject.is_some()
+            || self.split_tables.is_some()
+    }
+
+    // Whether the main output is written to stdout, instead of to one or more files
+    fn writes_to_stdout(&self) -> bool {
+        self.output.is_none() && self.inject.is_none() && self.split_tables.is_none()
+    }
+}
+
+fn make_formatter(args: &Args, config: &TablesConfig) -> anyhow::Result<Box<dyn Formatter>> {
+    match &args.template {
+        Some(template) => Ok(Box::new(TemplateFormatter::new(template)?)),
+        None => make_format_formatter(args, config, format(config), args.writes_to_stdout()),
+    }
+}
+
+fn make_format_formatter(
+    args: &Args,
+    config: &TablesConfig,
+    format: &str,
+    to_stdout: bool,
+) -> anyhow::Result<Box<dyn Formatter>> {
+    Ok(format_registry(args, to_stdout).make(format, config)?)
+}
+
+// The format of the main output
+fn format(config: &TablesConfig) -> &str {
+    config.format.as_deref().unwrap_or(DEFAULT_FORMAT)
+}
+
+// The built-in formats, with the options given on the command line. `to_stdout` is whether the
+// output is written to stdout (and so possibly a terminal) instead of a file
+fn format_registry(args: &Args, to_stdout: bool) -> FormatterRegistry {
+    let mut registry = FormatterRegistry::new();
+
+    let profile = args.profile;
+    registry.register("gfm", "md", move |config| {
+        Box::new(make_gfm_formatter(config, profile))
+    });
+
+    let standalone = args.standalone;
+    registry.register("latex", "tex", move |_| {
+        Box::new(LatexFormatter::new(standalone))
+    });
+
+    let style = if args.ascii {
+        BoxStyle::Ascii
+    } else {
+        BoxStyle::Unicode
+    };
+    let color = match args.color {
+        Color::Auto => {
+            to_stdout && io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+        }
+        Color::Always => true,
+        Color::Never => false,
+    };
+    registry.register("term", "txt", move |_| {
+        Box::new(TermFormatter::new(style, color))
+    });
+
+    registry
+}
+
+fn write_output(args: &Args, output: &str) -> io::Result<()> {
+    match &args.output {
+        Some(path) => fs::write(path, output),
+        None => {
+            println!("{output}");
+            Ok(())
+        }
+    }
+}
+
+// The number of benchmarks of `new` slower than in `old` by more than `fail_on`. These are found
+// from all the deltas over `fail_on`, since the deltas displayed are filtered by `--threshold`
+fn count_regressions(old: &CriterionTableData, new: &CriterionTableData, fail_on: f64) -> usize {
+    CriterionTableData::deltas(old, new, fail_on)
+        .iter()
+        .filter(|delta| delta.change > fail_on)
+        .count()
+}
+
+// Write each table to its own file in `dir`, named after the table's anchor, plus the index page
+// titled like the table of contents
+fn write_split_tables(
+    args: &Args,
+    config: &TablesConfig,
+    data: &CriterionTableData,
+    dir: &Path,
+) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
+    let namer = make_formatter(args, config)?;
+    let mut index = format!("# {}\n\n", config.toc.title);
+
+    // The index is always markdown
+    let top_comments = config
+        .environment
+        .top_comments(&config.top_comments, &GFMFormatter::default());
+    for (header, comment) in &top_comments {
+        index.push_str(&format!("## {header}\n\n{comment}\n\n"));
+    }
+
+    #[cfg(feature = "parallel")]
+    let split = data.par_make_tables_split(|| make_formatter(args, config), config)?;
+    #[cfg(not(feature = "parallel"))]
+    let split = data.make_tables_split(|| make_formatter(args, config), config)?;
+
+    let extension = format_registry(args, false)
+        .extension(format(config))
+        .map_or("txt", |extension| extension.as_str())
+        .to_string();
+    let mut taken = HashSet::from([SPLIT_INDEX_FILE.to_string()]);
+
+    for (name, tables) in split {
+        let file_name = unique_file_name(&mut taken, &namer.anchor(&name), &extension);
+        fs::write(dir.join(&file_name), tables)?;
+        index.push_str(&format!("- [{name}]({file_name})\n"));
+    }
+
+    fs::write(dir.join(SPLIT_INDEX_FILE), index)?;
+    Ok(())
+}
+
+// The file name `stem.extension`, or `stem-1.extension`, `stem-2.extension`, etc. if already
+// `taken`, so tables with the same anchor don't overwrite each other (or the index page)
+fn unique_file_name(taken: &mut HashSet<String>, stem: &str, extension: &str) -> String {
+    let mut file_name = format!("{stem}.{extension}");
+    let mut count = 0;
+
+    while !taken.insert(file_name.clone()) {
+        count += 1;
+        file_name = format!("{stem}-{count}.{extension}");
+    }
+
+    file_name
+}
+
+fn write_charts(config: &TablesConfig, data: &CriterionTableData, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for (name, svg) in data.make_charts(&config.chart) {
+        fs::write(dir.join(chart::file_name(&name).as_str()), svg)?;
+    }
+
+    Ok(())
+}
+
+fn load_baseline_data(
+    args: &Args,
+    config: &TablesConfig,
+    baseline: &str,
+) -> anyhow::Result<CriterionTableData> {
+    let runs = [
+        (
+            baseline.to_flex(),
+            criterion_dir::load_baseline(&args.criterion_dir, baseline)?,
+        ),
+        (
+            criterion_dir::LATEST_BASELINE.to_flex(),
+            criterion_dir::load_baseline(&args.criterion_dir, criterion_dir::LATEST_BASELINE)?,
+        ),
+    ];
+    Ok(CriterionTableData::from_series(
+        &runs,
+        config.metric,
+        &config.id_scheme,
+    )?)
+}
+
+fn parse_run(s: &str) -> Result<(String, PathBuf), String> {
+    match s.split_once('=') {
+        Some((label, path)) if !label.is_empty() && !path.is_empty() => {
+            Ok((label.to_string(), path.into()))
+        }
+        _ => Err(format!("expected LABEL=FILE, found '{s}'")),
+    }
+}
+
+fn parse_format(s: &str) -> Result<(String, Option<PathBuf>), String> {
+    let (name, path) = match s.split_once(':') {
+        Some((name, path)) if !path.is_empty() => (name, Some(PathBuf::from(path))),
+        _ => (s, None),
+    };
+
+    let name = name.to_lowercase();
+    check_format(&name)?;
+    Ok((name, path))
+}
+
+fn check_format(name: &str) -> Result<(), String> {
+    let registry = FormatterRegistry::new();
+    if registry.contains(name) {
+        return Ok(());
+    }
+
+    let names: Vec<_> = registry.names().map(|name| name.as_str()).collect();
+    Err(format!(
+        "unknown format '{name}' (expected one of: {})",
+        names.join(", ")
+    ))
+}
+
+fn parse_env_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((label, value)) if !label.is_empty() => Ok((label.to_string(), value.to_string())),
+        _ => Err(format!("expected LABEL=VALUE, found '{s}'")),
+    }
+}
+
+// The template is loaded and compiled up front, so any error is reported like other invalid args
+fn parse_template(s: &str) -> Result<String, String> {
+    let template = fs::read_to_string(s).map_err(|err| format!("unable to read '{s}': {err}"))?;
+    TemplateFormatter::new(&template).map_err(|err| err.to_string())?;
+    Ok(template)
+}
+
+fn load_runs(args: &Args, config: &TablesConfig) -> anyhow::Result<CriterionTableData> {
+    let runs = args
+        .run
+        .iter()
+        .map(|(label, path)| {
+            let (format, raw_data) = read_raw(args, open(path)?, Some(path))
+                .map_err(|err| anyhow::anyhow!("{}: {err}", path.display()))?;
+            Ok((format, (label.to_flex(), raw_data)))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // Every run is split with the id parser of the first, so each has the same names
+    let id_parser = match runs.first() {
+        Some((format, _)) => format.id_parser(config),
+        None => args.input_format.id_parser(config),
+    };
+    let runs: Vec<_> = runs.into_iter().map(|(_, run)| run).collect();
+    if args.prefix_columns {
+        Ok(CriterionTableData::from_runs(
+            &runs,
+            config.metric,
+            id_parser,
+        )?)
+    } else {
+        Ok(CriterionTableData::from_series(
+            &runs,
+            config.metric,
+            id_parser,
+        )?)
+    }
+}
+
+// Renders the tables of the benchmarks read so far, as each completes. It returns the data of the
+// whole stream, with the config applied, for the final output
+fn follow(args: &Args, config: &TablesConfig) -> anyhow::Result<CriterionTableData> {
+    if args.input_format != InputFormat::Criterion {
+        return Err(anyhow::anyhow!("Only criterion input can be followed"));
+    }
+
+    // Skipped benchmarks are only warned about once, with the final output
+    let make_data = |raw_data: &[RawCriterionData], last: bool| {
+        let mut data = if args.skip_invalid && !last {
+            CriterionTableData::from_raw_lenient(raw_data, config.metric, &config.id_scheme).0
+        } else {
+            build_data(args, raw_data, config.metric, &config.id_scheme, None)?
+        };
+        data.apply_config(config)?;
+        anyhow::Ok(data)
+    };
+    let clear = args.output.is_none() && io::stdout().is_terminal();
+    let stream = serde_json::Deserializer::from_reader(io::stdin().lock()).into_iter();
+    let mut raw_data = Vec::new();
+    let mut last_render: Option<Instant> = None;
+
+    for msg in stream {
+        match msg {
+            Ok(msg) => raw_data.push(CriterionMessage::into_raw_data(msg)),
+            Err(err) if args.recover && err.is_eof() => {
+                eprintln!("Discarded truncated message: {err}");
+                break;
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        if last_render.is_none_or(|at| at.elapsed() >= FOLLOW_INTERVAL) {
+            let data = make_data(&raw_data, false)?;
+            if clear {
+                print!("{CLEAR_SCREEN}");
+            }
+            write_output(
+                args,
+                &data.make_tables(make_formatter(args, config)?, config),
+            )?;
+            last_render = Some(Instant::now());
+        }
+    }
+
+    // The final output replaces the last partial tables
+    if clear && last_render.is_some() {
+        print!("{CLEAR_SCREEN}");
+    }
+    let data = make_data(&raw_data, true)?;
+    report_summary(args, &data);
+    Ok(data)
+}
+
+// Reads the raw data of one input. A truncated last message is discarded with a warning if
+// `--recover` was given, instead of failing
+fn read_raw(
+    args: &Args,
+    r: impl Read,
+    path: Option<&Path>,
+) -> anyhow::Result<(InputFormat, Vec<RawCriterionData>)> {
+    if !args.recover {
+        return Ok(args.input_format.read_raw(r)?);
+    }
+
+    let (format, raw_data, truncated) = args.input_format.read_raw_recovering(r)?;
+    if let Some(err) = truncated {
+        match path {
+            Some(path) => eprintln!("Discarded truncated message in {}: {err}", path.display()),
+            None => eprintln!("Discarded truncated message: {err}"),
+        }
+    }
+    Ok((format, raw_data))
+}
+
+// Builds the data of one input. Invalid benchmarks are skipped with a warning if `--skip-invalid`
+// was given, instead of failing
+fn build_data(
+    args: &Args,
+    raw_data: &[RawCriterionData],
+    metric: Metric,
+    id_parser: &dyn IdParser,
+    path: Option<&Path>,
+) -> anyhow::Result<CriterionTableData> {
+    if !args.skip_invalid {
+        return Ok(CriterionTableData::from_raw(raw_data, metric, id_parser)?);
+    }
+
+    let (data, skipped) = CriterionTableData::from_raw_lenient(raw_data, metric, id_parser);
+    for err in skipped {
+        match path {
+            Some(path) => eprintln!("Skipped invalid benchmark in {}: {err}", path.display()),
+            None => eprintln!("Skipped invalid benchmark: {err}"),
+        }
+    }
+    Ok(data)
+}
+
+// Loads the data without applying the config or comparing against a snapshot
+fn load_raw_data(args: &Args, config: &TablesConfig) -> anyhow::Result<CriterionTableData> {
+    if let Some(path) = &args.from_snapshot {
+        return CriterionTableData::from_reader(open(path)?)
+            .map_err(|err| anyhow::anyhow!("{}: {err}", path.display()));
+    }
+
+    match &args.baseline {
+        Some(baseline) => load_baseline_data(args, config, baseline),
+        None if !args.run.is_empty() => load_runs(args, config),
+        None if args.from_dir || args.input.is_empty() => {
+            if args.from_dir {
+                let raw_data = criterion_dir::load(&args.criterion_dir)?;
+                build_data(args, &raw_data, config.metric, &config.id_scheme, None)
+            } else {
+                let (format, raw_data) = read_raw(args, io::stdin(), None)?;
+                build_data(
+                    args,
+                    &raw_data,
+                    config.metric,
+                    format.id_parser(config),
+                    None,
+                )
+            }
+        }
+        None => {
+            let all_data = args
+                .input
+                .iter()
+                .map(|path| {
+                    let (format, raw_data) = read_raw(args, open(path)?, Some(path))?;
+                    let id_parser = format.id_parser(config);
+                    build_data(args, &raw_data, config.metric, id_parser, Some(path))
+                        .map_err(|err| anyhow::anyhow!("{}: {err}", path.display()))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Ok(CriterionTableData::merge(all_data, config.duplicates)?)
+        }
+    }
+}
+
+fn load_data(args: &Args, config: &TablesConfig) -> anyhow::Result<CriterionTableData> {
+    let mut data = load_raw_data(args, config)?;
+
+    if let Some(path) = &args.snapshot {
+        let baseline = CriterionTableData::from_reader(open(path)?)
+            .map_err(|err| anyhow::anyhow!("{}: {err}", path.display()))?;
+        data = CriterionTableData::diff(&baseline, &data)?;
+    }
+    if let Some(path) = &args.baseline_file {
+        let baseline = load_file(args, config, path)?;
+        data = CriterionTableData::with_baseline_run(
+            &baseline,
+            &args.baseline_label.to_flex(),
+            &data,
+        )?;
+    }
+
+    for warning in data.check_config(config)? {
+        eprintln!("Warning: {}", warning.to_a_flex_str());
+    }
+    data.apply_config(config)?;
+    report_summary(args, &data);
+    Ok(data)
+}
+
+fn report_summary(args: &Args, data: &CriterionTableData) {
+    if args.verbose {
+        eprint!("{}", data.summary().to_a_flex_str());
+    }
+}
+
+fn check_regressions(args: &Args, data: &CriterionTableData) -> anyhow::Result<()> {
+    if let Some(threshold) = args.fail_on_regression {
+        let regressions = data.regressions(threshold);
+
+        if !regressions.is_empty() {
+            eprintln!("| Benchmark | Change |\n|:----------|-------:|");
+            for regression in &regressions {
+                let name = benchmark_name(&regression.table, &regression.column, &regression.row);
+                eprintln!("| `{name}` | +{:.2}% |", regression.change * 100.0);
+            }
+
+            return Err(anyhow::anyhow!(
+                "{} benchmark(s) regressed by more than {:.2}%",
+                regressions.len(),
+                threshold * 100.0
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn benchmark_name(table: &str, column: &str, row: &str) -> String {
+    if row.is_empty() {
+        format!("{table}/{column}")
+    } else {
+        format!("{table}/{column}/{row}")
+    }
+}
+
+// Loads one run of the `diff` subcommand, with the config applied so names match (ex: renamed)
+fn load_diff_run(
+    args: &Args,
+    config: &TablesConfig,
+    path: &Path,
+) -> anyhow::Result<CriterionTableData> {
+    let mut data = load_file(args, config, path)?;
+    data.apply_config(config)?;
+    Ok(data)
+}
+
+// Loads the raw benchmark data of a single file, without applying the config
+fn load_file(
+    args: &Args,
+    config: &TablesConfig,
+    path: &Path,
+) -> anyhow::Result<CriterionTableData> {
+    let (format, raw_data) = read_raw(args, open(path)?, Some(path))
+        .map_err(|err| anyhow::anyhow!("{}: {err}", path.display()))?;
+    build_data(
+        args,
+        &raw_data,
+        config.metric,
+        format.id_parser(config),
+        Some(path),
+    )
+    .map_err(|err| anyhow::anyhow!("{}: {err}", path.display()))
+}
+
+// A markdown table of each delta, with slower benchmarks marked `❌` and faster ones `🚀`
+fn delta_table(deltas: &[Delta], threshold: f64, time_format: &TimeFormat) -> String {
+    if deltas.is_empty() {
+        return format!(
+            "No benchmark changed by more than {:.2}%\n",
+            threshold * 100.0
+        );
+    }
+
+    let mut table = String::from("| Benchmark | Old | New | Change |\n");
+    table.push_str("|:----------|----:|----:|-------:|\n");
+    for delta in deltas {
+        let marker = if delta.change > 0.0 { "❌" } else { "🚀" };
+        table.push_str(&format!(
+            "| `{}` | `{}` | `{}` | {marker} {:+.2}% |\n",
+            benchmark_name(&delta.table, &delta.column, &delta.row),
+            delta.old.format(time_format),
+            delta.new.format(time_format),
+            delta.change * 100.0
+        ));
+    }
+    table
+}
+
+fn open(path: &Path) -> anyhow::Result<File> {
+    File::open(path).map_err(|err| anyhow::anyhow!("Unable to open {}: {err}", path.display()))
+}
+
+fn run(args: Args) -> anyhow::Result<()> {
+    match &args.command {
+        Some(Command::Workspace {
+            file,
+            manifest_path,
+        }) => {
+            let config = load_config_with_args(&args)?;
+            let mut data = WorkspaceData::discover(
+                manifest_path.as_deref(),
+                file,
+                config.metric,
+                &config.id_scheme,
+            )?;
+            data.apply_config(&config)?;
+            let formatter = make_formatter(&args, &config)?;
+            write_output(&args, &data.make_tables(formatter, &config))?;
+        }
+        Some(Command::Report { out_dir }) => {
+            let config = load_config_with_args(&args)?;
+            let data = load_data(&args, &config)?;
+            let report = match format(&config) {
+                "gfm" => Report::new(&data, make_gfm_formatter(&config, args.profile), &config),
+                "html" => Report::new_html(&data, &config),
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Reports can only be generated as GFM or HTML"
+                    ))
+                }
+            };
+            report.write_to(out_dir)?;
+            check_regressions(&args, &data)?;
+        }
+        Some(Command::Series { dir }) => {
+            let config = load_config_with_args(&args)?;
+            let runs = RawCriterionData::from_dir(dir)?;
+            let mut data =
+                CriterionTableData::from_series(&runs, config.metric, &config.id_scheme)?;
+            data.apply_config(&config)?;
+            let formatter = make_formatter(&args, &config)?;
+            write_output(&args, &data.make_tables(formatter, &config))?;
+            check_regressions(&args, &data)?;
+        }
+        Some(Command::Save { file }) => {
+            let config = load_config_with_args(&args)?;
+            let data = load_raw_data(&args, &config)?;
+            data.to_writer(File::create(file)?)?;
+        }
+        Some(Command::Badge { out_dir, svg }) => {
+            let config = load_config_with_args(&args)?;
+            let data = load_data(&args, &config)?;
+            if config.badges.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "No badges are configured (via `[[badges]]`)"
+                ));
+            }
+
+            fs::create_dir_all(out_dir)?;
+            for spec in &config.badges {
+                let badge = Badge::try_new(&data, spec)?;
+                let (contents, extension) = if *svg {
+                    (badge.to_svg(), "svg")
+                } else {
+                    (badge.to_endpoint_json(), "json")
+                };
+                fs::write(
+                    out_dir.join(format!("{}.{extension}", spec.file_name())),
+                    contents,
+                )?;
+            }
+        }
+        Some(Command::History {
+            file,
+            command: HistoryCommand::Record { label },
+        }) => {
+            let config = load_config_with_args(&args)?;
+            let data = load_raw_data(&args, &config)?;
+            history::append(file, label.as_str(), data)?;
+        }
+        Some(Command::History {
+            file,
+            command: HistoryCommand::Trend { last },
+        }) => {
+            let config = load_config_with_args(&args)?;
+            let entries = history::load(file)?;
+            if entries.is_empty() {
+                return Err(anyhow::anyhow!("No runs recorded in {}", file.display()));
+            }
+
+            let mut data = history::trend(&entries, *last)?;
+            data.apply_config(&config)?;
+            let formatter = make_formatter(&args, &config)?;
+            write_output(&args, &data.make_tables(formatter, &config))?;
+            check_regressions(&args, &data)?;
+        }
+        Some(Command::Diff {
+            old,
+            new,
+            threshold,
+            fail_on,
+        }) => {
+            let config = load_config_with_args(&args)?;
+            let old = load_diff_run(&args, &config, old)?;
+            let new = load_diff_run(&args, &config, new)?;
+
+            let deltas = CriterionTableData::deltas(&old, &new, *threshold);
+            write_output(
+                &args,
+                &delta_table(&deltas, *threshold, &config.time_format),
+            )?;
+
+            if let Some(fail_on) = fail_on {
+                let regressions = count_regressions(&old, &new, *fail_on);
+                if regressions > 0 {
+                    return Err(anyhow::anyhow!(
+                        "{regressions} benchmark(s) regressed by more than {:.2}%",
+                        fail_on * 100.0
+                    ));
+                }
+            }
+        }
+        // The config file is relative to the book root and the book is always written to stdout
+        Some(Command::Mdbook { command: None }) => {
+            println!(
+                "{}",
+                mdbook::preprocess(io::stdin(), &args.config, gather_config)?
+            );
+        }
+        Some(Command::Mdbook {
+            command: Some(MdbookCommand::Supports { .. }),
+        }) => {}
+        None if args.watch => watch(&args)?,
+        None => write_tables(&args)?,
+    }
+
+    Ok(())
+}
+
+// Writes the tables (or the other outputs requested) of the input benchmark data
+fn write_tables(args: &Args) -> anyhow::Result<()> {
+    let config = load_config_with_args(args)?;
+    let data = if args.follow {
+        follow(args, &config)?
+    } else {
+        load_data(args, &config)?
+    };
+
+    #[cfg(feature = "interactive")]
+    if args.interactive {
+        return Ok(criterion_table::interactive::run(&data)?);
+    }
+
+    let formatter = make_formatter(args, &config)?;
+
+    // Every format written to a file is formatted from the same data
+    let files: Vec<_> = args.format_files().collect();
+    if !files.is_empty() {
+        let formatters = files
+            .iter()
+            .map(|(format, _)| make_format_formatter(args, &config, format, false))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        for ((_, path), output) in files
+            .iter()
+            .zip(data.make_tables_multi(formatters, &config))
+        {
+            fs::write(path, output).map_err(|err| anyhow::anyhow!("{}: {err}", path.display()))?;
+        }
+    }
+
+    if let Some(index) = &args.index {
+        fs::write(index, data.make_index(&formatter)?)?;
+    }
+
+    #[cfg(feature = "xlsx")]
+    if let Some(path) = &args.xlsx {
+        let file =
+            File::create(path).map_err(|err| anyhow::anyhow!("{}: {err}", path.display()))?;
+        criterion_table::xlsx::write_workbook(&data, file)?;
+    }
+
+    if let Some(dir) = &args.charts {
+        write_charts(&config, &data, dir)?;
+    }
+
+    match (&args.split_tables, &args.inject) {
+        (Some(dir), _) => write_split_tables(args, &config, &data, dir)?,
+        (None, Some(file)) => inject::inject_file(file, &data.make_tables(formatter, &config))?,
+        (None, None) if args.writes_main_output() => {
+            write_output(args, &data.make_tables(formatter, &config))?
         }
-        Err(err) => {
-            eprintln!("An error occurred processing Criterion data: {err}");
+        (None, None) => {}
+    }
+    check_regressions(args, &data)?;
+    Ok(())
+}
+
+// Regenerates the output whenever the watched files change, until killed. Errors (ex: a file read
+// while only partially written) are reported without stopping
+fn watch(args: &Args) -> anyhow::Result<()> {
+    let reads_stdin = !args.from_dir
+        && args.baseline.is_none()
+        && args.input.is_empty()
+        && args.run.is_empty()
+        && args.from_snapshot.is_none();
+    if reads_stdin {
+        return Err(anyhow::anyhow!(
+            "Nothing to watch (stdin can't be watched): give `--from-dir`, `--baseline`, \
+             `--input` or `--run`"
+        ));
+    }
+
+    let mut paths = watched_paths(args);
+    paths.push(args.config.clone());
+
+    let clear = args.output.is_none() && io::stdout().is_terminal();
+    let mut last_stamp = None;
+
+    loop {
+        let stamp = modified_stamp(&paths);
+
+        if last_stamp.as_ref() != Some(&stamp) {
+            // Benchmarks write several files, so wait until they stop changing
+            thread::sleep(WATCH_INTERVAL);
+            if modified_stamp(&paths) != stamp {
+                continue;
+            }
+
+            if clear {
+                print!("{CLEAR_SCREEN}");
+            }
+            if let Err(err) = write_tables(args) {
+                eprintln!("An error occurred processing Criterion data: {err}");
+            }
+            last_stamp = Some(stamp);
         }
+
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+// The files and directories the benchmark data is read from
+fn watched_paths(args: &Args) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if args.from_dir || args.baseline.is_some() {
+        paths.push(args.criterion_dir.clone());
+    }
+    paths.extend(args.input.iter().cloned());
+    paths.extend(args.run.iter().map(|(_, path)| path.clone()));
+    paths.extend(args.snapshot.iter().cloned());
+    paths.extend(args.from_snapshot.iter().cloned());
+    paths
+}
+
+// The modification time and length of each file in `paths` (recursively for directories), which
+// changes whenever any of them are written, added or removed
+fn modified_stamp(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    fn add_path(stamp: &mut Vec<(PathBuf, Option<SystemTime>, u64)>, path: &Path) {
+        match fs::metadata(path) {
+            Ok(meta) if meta.is_dir() => {
+                let mut entries: Vec<_> = fs::read_dir(path)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .collect();
+                entries.sort();
+
+                for entry in entries {
+                    add_path(stamp, &entry);
+                }
+            }
+            Ok(meta) => stamp.push((path.to_path_buf(), meta.modified().ok(), meta.len())),
+            // Missing (ex: not yet written)
+            Err(_) => stamp.push((path.to_path_buf(), None, 0)),
+        }
+    }
+
+    let mut stamp = Vec::new();
+    for path in paths {
+        add_path(&mut stamp, path);
+    }
+    stamp
+}
+
+fn main() {
+    if let Err(err) = run(Args::parse()) {
+        eprintln!("An error occurred processing Criterion data: {err}");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use criterion_table::builder::TableBuilder;
+
+    // Table data of benchmarks with the given ids and times (in nanoseconds)
+    fn data(benchmarks: &[(&str, f64)]) -> CriterionTableData {
+        let messages: String = benchmarks
+            .iter()
+            .map(|(id, time)| {
+                let estimate = format!(
+                    r#"{{"estimate": {time}, "lower_bound": {time}, "upper_bound": {time}, "unit": "ns"}}"#
+                );
+                format!(
+                    r#"{{"reason": "benchmark-complete", "id": "{id}", "report_directory": "", "iteration_count": [1], "measured_values": [{time}], "unit": "ns", "throughput": [], "typical": {estimate}, "mean": {estimate}, "median": {estimate}, "median_abs_dev": {estimate}, "slope": null, "change": null}}
+"#
+                )
+            })
+            .collect();
+
+        TableBuilder::new()
+            .add_reader(messages.as_bytes())
+            .build_data()
+            .expect("valid benchmarks")
+    }
+
+    #[test]
+    fn fail_on_ignores_threshold() {
+        let old = data(&[
+            ("Fib/std/10", 100.0),
+            ("Fib/std/20", 100.0),
+            ("Fib/std/30", 100.0),
+        ]);
+        let new = data(&[
+            ("Fib/std/10", 115.0),
+            ("Fib/std/20", 130.0),
+            ("Fib/std/30", 90.0),
+        ]);
+
+        // Only the +30% regression is displayed with `--threshold 0.2`...
+        let displayed = CriterionTableData::deltas(&old, &new, 0.2);
+        assert_eq!(displayed.len(), 1);
+        // ...but both regressions over 10% fail the run with `--fail-on 0.1`
+        assert_eq!(count_regressions(&old, &new, 0.1), 2);
+        assert_eq!(count_regressions(&old, &new, 0.5), 0);
+    }
+
+    #[test]
+    fn unique_split_file_names() {
+        let mut taken = HashSet::from([SPLIT_INDEX_FILE.to_string()]);
+        let file_names: Vec<_> = ["fib", "fib", "index", "fib"]
+            .into_iter()
+            .map(|stem| unique_file_name(&mut taken, stem, "md"))
+            .collect();
+        assert_eq!(file_names, ["fib.md", "fib-1.md", "index-1.md", "fib-2.md"]);
     }
 }
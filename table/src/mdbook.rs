@@ -0,0 +1,154 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::formatter::GFMFormatter;
+use crate::{load_config, CriterionTableData, EmbedOptions, Error, RawCriterionData, TablesConfig};
+
+const PLACEHOLDER_START: &str = "{{#criterion-table ";
+const PLACEHOLDER_END: &str = "}}";
+
+/// Runs the mdBook preprocessor. `input` is the `[context, book]` JSON pair mdBook sends on stdin
+/// and the processed book JSON is returned (to be written to stdout). The tables config file
+/// `cfg_name` is relative to the book root. Each `{{#criterion-table path/to/data.json}}`
+/// placeholder is replaced by tables rendered from that file (relative to the chapter file). Only
+/// the tables and their headings are rendered (unless the config has its own `embed` options),
+/// since the chapter has its own title. The loaded config is passed to `prepare` first (ex: to add
+/// the provenance of the footer)
+pub fn preprocess(
+    input: impl Read,
+    cfg_name: impl AsRef<Path>,
+    prepare: impl FnOnce(&mut TablesConfig),
+) -> Result<String, Error> {
+    let (ctx, mut book): (Value, Value) = serde_json::from_reader(input)?;
+
+    let root = ctx["root"].as_str().ok_or(Error::MissingBookRoot)?;
+    let src = ctx["config"]["book"]["src"].as_str().unwrap_or("src");
+    let src_dir = Path::new(root).join(src);
+    let mut config = load_config(Path::new(root).join(cfg_name))?;
+    prepare(&mut config);
+    config.embed.get_or_insert(EmbedOptions { headings: true });
+
+    // Older versions of mdBook call these 'sections', newer versions 'items'
+    for key in ["sections", "items"] {
+        if let Some(items) = book.get_mut(key) {
+            process_items(items, &src_dir, &config)?;
+        }
+    }
+
+    Ok(serde_json::to_string(&book)?)
+}
+
+fn process_items(items: &mut Value, src_dir: &Path, config: &TablesConfig) -> Result<(), Error> {
+    if let Some(items) = items.as_array_mut() {
+        for item in items {
+            // Anything other than a chapter (separators, part titles, etc.) has no content
+            if let Some(chapter) = item.get_mut("Chapter") {
+                process_chapter(chapter, src_dir, config)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn process_chapter(
+    chapter: &mut Value,
+    src_dir: &Path,
+    config: &TablesConfig,
+) -> Result<(), Error> {
+    // Paths are relative to the chapter file, just like mdBook's own `{{#include}}`
+    let chapter_dir = chapter["source_path"]
+        .as_str()
+        .and_then(|path| Path::new(path).parent())
+        .map(|path| src_dir.join(path))
+        .unwrap_or_else(|| src_dir.to_path_buf());
+
+    if let Some(content) = chapter["content"].as_str() {
+        if content.contains(PLACEHOLDER_START) {
+            chapter["content"] =
+                Value::String(replace_placeholders(content, &chapter_dir, config)?);
+        }
+    }
+
+    if let Some(sub_items) = chapter.get_mut("sub_items") {
+        process_items(sub_items, src_dir, config)?;
+    }
+
+    Ok(())
+}
+
+fn replace_placeholders(
+    content: &str,
+    chapter_dir: &Path,
+    config: &TablesConfig,
+) -> Result<String, Error> {
+    let mut buffer = String::with_capacity(content.len());
+    let mut remaining = content;
+
+    while let Some(start) = remaining.find(PLACEHOLDER_START) {
+        let after_start = &remaining[start + PLACEHOLDER_START.len()..];
+        let end = after_start
+            .find(PLACEHOLDER_END)
+            .ok_or(Error::UnterminatedPlaceholder)?;
+
+        let path: PathBuf = chapter_dir.join(after_start[..end].trim());
+        buffer.push_str(&remaining[..start]);
+        buffer.push_str(&render_file(&path, config)?);
+        remaining = &after_start[end + PLACEHOLDER_END.len()..];
+    }
+
+    buffer.push_str(remaining);
+    Ok(buffer)
+}
+
+fn render_file(path: &Path, config: &TablesConfig) -> Result<String, Error> {
+    let f = File::open(path).map_err(|err| Error::File {
+        path: path.to_path_buf(),
+        source: Box::new(err.into()),
+    })?;
+    let raw_data = RawCriterionData::from_reader(f)?;
+    let mut data = CriterionTableData::from_raw(&raw_data, config.metric, &config.id_scheme)?;
+    data.apply_config(config)?;
+    Ok(data.make_tables(GFMFormatter::new(config.gfm.clone()), config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn placeholders_render_tables_only() {
+        let root = std::env::temp_dir().join(format!("mdbook-{}", std::process::id()));
+        std::fs::create_dir_all(&root).expect("created");
+        let estimate =
+            r#"{"estimate": 100.0, "lower_bound": 100.0, "upper_bound": 100.0, "unit": "ns"}"#;
+        let message = format!(
+            r#"{{"reason": "benchmark-complete", "id": "Fib/std/10", "report_directory": "", "iteration_count": [1], "measured_values": [100.0], "unit": "ns", "throughput": [], "typical": {estimate}, "mean": {estimate}, "median": {estimate}, "median_abs_dev": {estimate}, "slope": null, "change": null}}"#
+        );
+        std::fs::write(root.join("data.json"), message).expect("written");
+
+        let input = json!([
+            {"root": root, "config": {"book": {"src": "."}}},
+            {"items": [{"Chapter": {
+                "content": "# Results\n\n{{#criterion-table data.json}}\n",
+                "source_path": "results.md",
+                "sub_items": [],
+            }}]},
+        ]);
+        let book = preprocess(input.to_string().as_bytes(), "tables.toml", |_| {});
+        let _ = std::fs::remove_dir_all(&root);
+
+        let book: Value = serde_json::from_str(&book.expect("processed")).expect("JSON");
+        let content = book["items"][0]["Chapter"]["content"]
+            .as_str()
+            .expect("content");
+        assert!(content.starts_with("# Results\n\n### Fib"));
+        assert!(!content.contains("# Benchmarks"));
+        assert!(!content.contains("Table of Contents"));
+    }
+}
@@ -0,0 +1,773 @@
+use std::ops::Div;
+
+use flexstr::{a_flex_fmt, AFlexStr, ToAFlexStr, ToFlex};
+use serde::{Deserialize, Serialize};
+
+use crate::{display_width, Error, Throughput};
+
+// *** Time Format ***
+
+/// Which unit times are displayed in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum UnitPolicy {
+    /// The largest unit each time is at least 1 of (ex: `1.50 us` instead of `1500.00 ns`)
+    #[default]
+    #[serde(rename = "auto")]
+    #[cfg_attr(feature = "cli", value(name = "auto"))]
+    Auto,
+    /// The unit of the fastest time of each table, for every time in the table
+    #[serde(rename = "table")]
+    #[cfg_attr(feature = "cli", value(name = "table"))]
+    Table,
+    /// The unit of the slowest time of each table, for every time in the table
+    #[serde(rename = "table-max")]
+    #[cfg_attr(feature = "cli", value(name = "table-max"))]
+    TableMax,
+    /// Always seconds
+    #[serde(rename = "s")]
+    #[cfg_attr(feature = "cli", value(name = "s"))]
+    Seconds,
+    /// Always milliseconds
+    #[serde(rename = "ms")]
+    #[cfg_attr(feature = "cli", value(name = "ms"))]
+    Milliseconds,
+    /// Always microseconds
+    #[serde(rename = "us")]
+    #[cfg_attr(feature = "cli", value(name = "us"))]
+    Microseconds,
+    /// Always nanoseconds
+    #[serde(rename = "ns")]
+    #[cfg_attr(feature = "cli", value(name = "ns"))]
+    Nanoseconds,
+    /// Always picoseconds
+    #[serde(rename = "ps")]
+    #[cfg_attr(feature = "cli", value(name = "ps"))]
+    Picoseconds,
+}
+
+impl UnitPolicy {
+    // The unit of a fixed policy, as a `TimeUnit` of 1 of that unit
+    fn fixed_unit(self) -> Option<TimeUnit> {
+        match self {
+            UnitPolicy::Auto | UnitPolicy::Table | UnitPolicy::TableMax => None,
+            UnitPolicy::Seconds => Some(TimeUnit::Second(1.0)),
+            UnitPolicy::Milliseconds => Some(TimeUnit::Millisecond(1.0)),
+            UnitPolicy::Microseconds => Some(TimeUnit::Microsecond(1.0)),
+            UnitPolicy::Nanoseconds => Some(TimeUnit::Nanosecond(1.0)),
+            UnitPolicy::Picoseconds => Some(TimeUnit::Picosecond(1.0)),
+        }
+    }
+
+    // The fixed policy of the unit of `time`
+    fn of(time: TimeUnit) -> Self {
+        match time {
+            TimeUnit::Second(_) => UnitPolicy::Seconds,
+            TimeUnit::Millisecond(_) => UnitPolicy::Milliseconds,
+            TimeUnit::Microsecond(_) => UnitPolicy::Microseconds,
+            TimeUnit::Nanosecond(_) => UnitPolicy::Nanoseconds,
+            TimeUnit::Picosecond(_) => UnitPolicy::Picoseconds,
+        }
+    }
+}
+
+/// How times are displayed (ex: `[time_format]` in the config). The default is two decimal places
+/// in the largest unit each time is at least 1 of (ex: `1.23 us`)
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeFormat {
+    /// Display each time (and other measurement) with this many significant digits instead of two
+    /// decimal places (ex: 3 for `1.23 us`, `12.3 us` and `123 us`)
+    pub significant_digits: Option<usize>,
+    /// Which unit times are displayed in
+    pub unit: UnitPolicy,
+    /// Times below this many nanoseconds are displayed as `< ` this time (ex: `< 0.50 ns` for 0.5),
+    /// instead of a precise but meaningless number
+    pub min_time: Option<f64>,
+}
+
+impl TimeFormat {
+    // Resolve the `table` (or `table-max`) unit policy into the unit of the fastest (or slowest) of
+    // `times`
+    pub(crate) fn for_times(mut self, times: impl Iterator<Item = TimeUnit>) -> Self {
+        let times = times.map(|time| time.as_picoseconds());
+        let time = match self.unit {
+            UnitPolicy::Table => times.reduce(f64::min),
+            UnitPolicy::TableMax => times.reduce(f64::max),
+            _ => return self,
+        };
+
+        self.unit = match time.map(|ps| TimeUnit::try_new(ps, "ps")) {
+            Some(Ok(time)) => UnitPolicy::of(time),
+            _ => UnitPolicy::Auto,
+        };
+        self
+    }
+}
+
+// *** Time Unit ***
+
+/// Time unit of a particular measurement
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum TimeUnit {
+    /// Time is in seconds
+    Second(f64),
+    /// Time is in milliseconds
+    Millisecond(f64),
+    /// Time is in microseconds
+    Microsecond(f64),
+    /// Time is in nanoseconds
+    Nanosecond(f64),
+    /// Time is in picoseconds
+    Picosecond(f64),
+}
+
+impl TimeUnit {
+    /// Create a new `TimeUnit` taking the time and initial unit string as input
+    pub fn try_new(time: f64, unit: &str) -> Result<Self, Error> {
+        match unit {
+            "ms" if time > 1000.0 => Self::try_new(time / 1000.0, "s"),
+            "us" if time > 1000.0 => Self::try_new(time / 1000.0, "ms"),
+            "ns" if time > 1000.0 => Self::try_new(time / 1000.0, "us"),
+            "ps" if time > 1000.0 => Self::try_new(time / 1000.0, "ns"),
+            "s" => Ok(TimeUnit::Second(time)),
+            "ms" => Ok(TimeUnit::Millisecond(time)),
+            "us" => Ok(TimeUnit::Microsecond(time)),
+            "ns" => Ok(TimeUnit::Nanosecond(time)),
+            "ps" => Ok(TimeUnit::Picosecond(time)),
+            _ => Err(Error::UnknownUnit {
+                unit: unit.to_string(),
+            }),
+        }
+    }
+
+    /// Returns this time converted to the unit of `unit` (ex: `TimeUnit::Nanosecond(1.0)` for
+    /// nanoseconds)
+    pub fn in_unit_of(&self, unit: TimeUnit) -> Self {
+        let time = self.as_picoseconds() / unit.as_picoseconds() * unit.value();
+
+        match unit {
+            TimeUnit::Second(_) => TimeUnit::Second(time),
+            TimeUnit::Millisecond(_) => TimeUnit::Millisecond(time),
+            TimeUnit::Microsecond(_) => TimeUnit::Microsecond(time),
+            TimeUnit::Nanosecond(_) => TimeUnit::Nanosecond(time),
+            TimeUnit::Picosecond(_) => TimeUnit::Picosecond(time),
+        }
+    }
+
+    /// Returns this time in the unit given by `format` (if fixed), or as is
+    #[inline]
+    pub fn with_unit(&self, format: &TimeFormat) -> Self {
+        match format.unit.fixed_unit() {
+            Some(unit) => self.in_unit_of(unit),
+            None => *self,
+        }
+    }
+
+    /// Returns this time as it should be displayed with `format` (ex: `1.23 us`)
+    pub fn format(&self, format: &TimeFormat) -> AFlexStr {
+        let time = self.with_unit(format);
+
+        if let Some(min_time) = format.min_time {
+            if self.as_nanoseconds() < min_time {
+                // Shown in the same unit the time would have been (if fixed), or its own
+                let min_time = TimeUnit::Nanosecond(min_time);
+                let min_time = match format.unit.fixed_unit() {
+                    Some(unit) => min_time.in_unit_of(unit),
+                    None => TimeUnit::try_new(min_time.as_picoseconds(), "ps").unwrap_or(min_time),
+                };
+                return a_flex_fmt!(
+                    "< {}",
+                    min_time.format(&TimeFormat {
+                        min_time: None,
+                        ..*format
+                    })
+                );
+            }
+        }
+
+        let suffix = match time {
+            TimeUnit::Second(_) => "s",
+            TimeUnit::Millisecond(_) => "ms",
+            TimeUnit::Microsecond(_) => "us",
+            TimeUnit::Nanosecond(_) => "ns",
+            TimeUnit::Picosecond(_) => "ps",
+        };
+        a_flex_fmt!("{} {suffix}", format_number(time.value(), format))
+    }
+
+    /// Returns the display width in columns for this `TimeUnit`
+    #[inline]
+    pub fn width(&self) -> usize {
+        display_width(&self.to_a_flex_str())
+    }
+
+    /// Returns the rate (operations per second) derived from this time, as in: 1 / time
+    #[inline]
+    pub fn rate(&self) -> Rate {
+        Rate(1_000_000_000_000.0 / self.as_picoseconds())
+    }
+
+    /// Returns this time in picoseconds
+    pub fn as_picoseconds(&self) -> f64 {
+        match *self {
+            TimeUnit::Second(s) => s * 1_000_000_000_000.0,
+            TimeUnit::Millisecond(ms) => ms * 1_000_000_000.0,
+            TimeUnit::Microsecond(us) => us * 1_000_000.0,
+            TimeUnit::Nanosecond(ns) => ns * 1_000.0,
+            TimeUnit::Picosecond(ps) => ps,
+        }
+    }
+
+    /// Returns this time in nanoseconds
+    pub fn as_nanoseconds(&self) -> f64 {
+        match *self {
+            TimeUnit::Second(s) => s * 1_000_000_000.0,
+            TimeUnit::Millisecond(ms) => ms * 1_000_000.0,
+            TimeUnit::Microsecond(us) => us * 1_000.0,
+            TimeUnit::Nanosecond(ns) => ns,
+            TimeUnit::Picosecond(ps) => ps / 1_000.0,
+        }
+    }
+
+    // Returns the time in the unit of this time (ex: 1.5 for 1.5 seconds)
+    #[inline]
+    fn value(&self) -> f64 {
+        match *self {
+            TimeUnit::Second(t)
+            | TimeUnit::Millisecond(t)
+            | TimeUnit::Microsecond(t)
+            | TimeUnit::Nanosecond(t)
+            | TimeUnit::Picosecond(t) => t,
+        }
+    }
+}
+
+impl Div for TimeUnit {
+    type Output = f64;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let unit1 = self.as_picoseconds();
+        let unit2 = rhs.as_picoseconds();
+        unit1 / unit2
+    }
+}
+
+impl ToAFlexStr for TimeUnit {
+    #[inline]
+    fn to_a_flex_str(&self) -> AFlexStr {
+        self.format(&Default::default())
+    }
+}
+
+// Formats a value (without its unit) with the significant digits of `format`, or two decimal places
+fn format_number(value: f64, format: &TimeFormat) -> String {
+    match format.significant_digits {
+        Some(digits) => {
+            let digits = digits.max(1) as i32;
+            // The power of ten of the most significant digit (ex: 2 for 123.4)
+            let magnitude = if value == 0.0 {
+                0
+            } else {
+                value.abs().log10().floor() as i32
+            };
+            let decimals = digits - 1 - magnitude;
+
+            if decimals >= 0 {
+                format!("{value:.*}", decimals as usize)
+            } else {
+                // Round away the digits before the decimal point that aren't significant
+                let scale = 10f64.powi(-decimals);
+                format!("{:.0}", (value / scale).round() * scale)
+            }
+        }
+        None => format!("{value:.2}"),
+    }
+}
+
+// Scales a value to the largest of `prefixes` (each a step of `step` larger than the last) it is
+// at least 1 of, returning the scaled value and its prefix
+fn scale_value(mut value: f64, step: f64, prefixes: &[&'static str]) -> (f64, &'static str) {
+    let mut prefix = prefixes[0];
+    for next in &prefixes[1..] {
+        if value.abs() < step {
+            break;
+        }
+        value /= step;
+        prefix = next;
+    }
+    (value, prefix)
+}
+
+// *** Measurement ***
+
+// The units bytes are displayed in, each 1024 times the last
+const BYTE_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+// The prefixes counts (and custom units) are displayed with, each 1000 times the last
+const METRIC_PREFIXES: &[&str] = &["", "K", "M", "G", "T"];
+
+/// A value measured by a benchmark. Criterion measures time by default, but custom measurements
+/// (ex: of allocations or instruction counts) can be in any unit. Measurements are compared by
+/// their value in the base unit of their kind (ex: picoseconds for a time)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "MeasurementData", into = "MeasurementData")]
+pub enum Measurement {
+    /// A time
+    Time(TimeUnit),
+    /// A number of bytes (ex: allocated per iteration)
+    Bytes(f64),
+    /// A count without a unit
+    Count(f64),
+    /// A value in any other unit (ex: instructions)
+    Custom(f64, AFlexStr),
+}
+
+impl Measurement {
+    /// Create a new `Measurement` taking the value and unit string as input. Time units (ex: `ns`)
+    /// and byte units (ex: `B` or `KiB`) are recognized, a blank unit is a count, and any other
+    /// unit is kept as a custom unit. Byte units are binary, so `KB` is the same as `KiB` (1024
+    /// bytes), like the units bytes are displayed in
+    pub fn new(value: f64, unit: &str) -> Self {
+        let bytes = match unit {
+            "B" | "bytes" => Some(1.0),
+            "KB" | "KiB" => Some(1024.0),
+            "MB" | "MiB" => Some(1024f64.powi(2)),
+            "GB" | "GiB" => Some(1024f64.powi(3)),
+            "TB" | "TiB" => Some(1024f64.powi(4)),
+            _ => None,
+        };
+
+        match (TimeUnit::try_new(value, unit), bytes) {
+            (Ok(time), _) => Measurement::Time(time),
+            (_, Some(scale)) => Measurement::Bytes(value * scale),
+            _ if unit.is_empty() => Measurement::Count(value),
+            _ => Measurement::Custom(value, unit.to_flex()),
+        }
+    }
+
+    /// Returns the time, if this is one
+    #[inline]
+    pub fn as_time(&self) -> Option<TimeUnit> {
+        match *self {
+            Measurement::Time(time) => Some(time),
+            _ => None,
+        }
+    }
+
+    /// Returns the value in the base unit of its kind: picoseconds for a time, bytes for bytes, or
+    /// as is
+    pub fn base_value(&self) -> f64 {
+        match *self {
+            Measurement::Time(time) => time.as_picoseconds(),
+            Measurement::Bytes(value)
+            | Measurement::Count(value)
+            | Measurement::Custom(value, _) => value,
+        }
+    }
+
+    // Returns a measurement of the same kind (and custom unit) as this one with `value` in the
+    // base unit. Times are in the largest unit they are at least 1 of
+    fn with_base_value(&self, value: f64) -> Self {
+        match self {
+            Measurement::Time(_) => Measurement::Time(
+                TimeUnit::try_new(value, "ps").unwrap_or(TimeUnit::Picosecond(value)),
+            ),
+            Measurement::Bytes(_) => Measurement::Bytes(value),
+            Measurement::Count(_) => Measurement::Count(value),
+            Measurement::Custom(_, unit) => Measurement::Custom(value, unit.clone()),
+        }
+    }
+
+    /// Returns this measurement in the unit given by `format` (if fixed and this is a time), or as
+    /// is
+    #[inline]
+    pub fn with_unit(&self, format: &TimeFormat) -> Self {
+        match *self {
+            Measurement::Time(time) => Measurement::Time(time.with_unit(format)),
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns the name of the base unit of its kind (ex: `ps` for a time or `B` for bytes). It is
+    /// blank for a count
+    pub fn base_unit(&self) -> &str {
+        match self {
+            Measurement::Time(_) => "ps",
+            Measurement::Bytes(_) => "B",
+            Measurement::Count(_) => "",
+            Measurement::Custom(_, unit) => unit,
+        }
+    }
+
+    // The name of the kind of this measurement, for messages (ex: `bytes`, or a custom unit)
+    pub(crate) fn kind(&self) -> &str {
+        match self {
+            Measurement::Time(_) => "time",
+            Measurement::Bytes(_) => "bytes",
+            Measurement::Count(_) => "count",
+            Measurement::Custom(_, unit) => unit,
+        }
+    }
+
+    // True if `other` can be compared to this measurement. Each kind (and custom unit) has its own
+    // base unit, so they are compared instead
+    #[inline]
+    pub(crate) fn same_kind(&self, other: &Self) -> bool {
+        self.base_unit() == other.base_unit()
+    }
+
+    // Returns the value as it is displayed and the unit (or prefix) it is displayed in (ex: 4.5
+    // and `KiB` for 4608 bytes). Times are already in the unit they are displayed in
+    pub(crate) fn scaled(&self) -> (f64, &'static str) {
+        match *self {
+            Measurement::Time(time) => (time.value(), ""),
+            Measurement::Bytes(value) => scale_value(value, 1024.0, BYTE_UNITS),
+            Measurement::Count(value) | Measurement::Custom(value, _) => {
+                scale_value(value, 1000.0, METRIC_PREFIXES)
+            }
+        }
+    }
+
+    /// Returns this measurement as it should be displayed with `format` (ex: `1.23 us`, `4.50 KiB`
+    /// or `1.20M instructions`). Only times use the unit and minimum time of `format`
+    pub fn format(&self, format: &TimeFormat) -> AFlexStr {
+        let (value, prefix) = self.scaled();
+
+        match self {
+            Measurement::Time(time) => time.format(format),
+            Measurement::Bytes(_) => a_flex_fmt!("{} {prefix}", format_number(value, format)),
+            Measurement::Count(_) => a_flex_fmt!("{}{prefix}", format_number(value, format)),
+            Measurement::Custom(_, unit) => {
+                a_flex_fmt!("{}{prefix} {unit}", format_number(value, format))
+            }
+        }
+    }
+
+    // The value written by data exports (ex: CSV): nanoseconds for a time, or the base unit of its
+    // kind
+    pub(crate) fn export_value(&self) -> f64 {
+        match *self {
+            Measurement::Time(time) => time.as_nanoseconds(),
+            _ => self.base_value(),
+        }
+    }
+
+    // Returns the difference from `baseline` to this measurement, without its sign (ex: for a delta
+    // comparison). No difference (ex: the baseline itself) is shown in the unit of this
+    pub(crate) fn abs_diff(&self, baseline: &Self) -> Self {
+        let diff = (self.base_value() - baseline.base_value()).abs();
+
+        match *self {
+            Measurement::Time(time) if diff == 0.0 => {
+                Measurement::Time(TimeUnit::Picosecond(0.0).in_unit_of(time))
+            }
+            _ => self.with_base_value(diff),
+        }
+    }
+
+    // The geometric mean of the given measurements (which must not be empty, and should all be of
+    // the same kind)
+    pub(crate) fn geomean<'a>(values: impl Iterator<Item = &'a Measurement>) -> Self {
+        let (first, count, log_sum) = values.fold((None, 0, 0.0), |(first, count, sum), value| {
+            let log = value.base_value().ln();
+            (first.or(Some(value)), count + 1, sum + log)
+        });
+        let geomean = (log_sum / count as f64).exp();
+        first.map_or(Measurement::Count(geomean), |first| {
+            first.with_base_value(geomean)
+        })
+    }
+
+    // The mean of the given measurements (which must not be empty, and should all be of the same
+    // kind)
+    pub(crate) fn mean(values: &[Measurement]) -> Self {
+        let total: f64 = values.iter().map(Measurement::base_value).sum();
+        let mean = total / values.len() as f64;
+        values.first().map_or(Measurement::Count(mean), |first| {
+            first.with_base_value(mean)
+        })
+    }
+
+    /// Returns the display width in columns for this `Measurement`
+    #[inline]
+    pub fn width(&self) -> usize {
+        display_width(&self.to_a_flex_str())
+    }
+
+    /// Returns the rate (operations per second) derived from this measurement, if it is a time
+    #[inline]
+    pub fn rate(&self) -> Option<Rate> {
+        self.as_time().map(|time| time.rate())
+    }
+}
+
+impl Div for &Measurement {
+    type Output = f64;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.base_value() / rhs.base_value()
+    }
+}
+
+impl ToAFlexStr for Measurement {
+    #[inline]
+    fn to_a_flex_str(&self) -> AFlexStr {
+        self.format(&Default::default())
+    }
+}
+
+// The serialized form of a `Measurement`. Times are written as they were before other measurements
+// were supported (ex: `{"Nanosecond": 1.0}`), so older snapshots and history can still be read
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum MeasurementData {
+    Time(TimeUnit),
+    Other(OtherMeasurement),
+}
+
+#[derive(Serialize, Deserialize)]
+enum OtherMeasurement {
+    Bytes(f64),
+    Count(f64),
+    Custom(f64, AFlexStr),
+}
+
+impl From<MeasurementData> for Measurement {
+    fn from(data: MeasurementData) -> Self {
+        match data {
+            MeasurementData::Time(time) => Measurement::Time(time),
+            MeasurementData::Other(OtherMeasurement::Bytes(value)) => Measurement::Bytes(value),
+            MeasurementData::Other(OtherMeasurement::Count(value)) => Measurement::Count(value),
+            MeasurementData::Other(OtherMeasurement::Custom(value, unit)) => {
+                Measurement::Custom(value, unit)
+            }
+        }
+    }
+}
+
+impl From<Measurement> for MeasurementData {
+    fn from(measurement: Measurement) -> Self {
+        match measurement {
+            Measurement::Time(time) => MeasurementData::Time(time),
+            Measurement::Bytes(value) => MeasurementData::Other(OtherMeasurement::Bytes(value)),
+            Measurement::Count(value) => MeasurementData::Other(OtherMeasurement::Count(value)),
+            Measurement::Custom(value, unit) => {
+                MeasurementData::Other(OtherMeasurement::Custom(value, unit))
+            }
+        }
+    }
+}
+
+// *** Rate ***
+
+/// A rate in operations per second
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Rate(pub f64);
+
+impl Rate {
+    /// The display width in columns of this rate
+    #[inline]
+    pub fn width(self) -> usize {
+        display_width(&self.to_a_flex_str())
+    }
+}
+
+impl ToAFlexStr for Rate {
+    fn to_a_flex_str(&self) -> AFlexStr {
+        match self.0 {
+            rate if rate >= 1_000_000_000_000.0 => a_flex_fmt!("{:.2} Tops/s", rate / 1e12),
+            rate if rate >= 1_000_000_000.0 => a_flex_fmt!("{:.2} Gops/s", rate / 1e9),
+            rate if rate >= 1_000_000.0 => a_flex_fmt!("{:.2} Mops/s", rate / 1e6),
+            rate if rate >= 1_000.0 => a_flex_fmt!("{:.2} Kops/s", rate / 1e3),
+            rate => a_flex_fmt!("{rate:.2} ops/s"),
+        }
+    }
+}
+
+// *** Throughput Rate ***
+
+/// The unit of work a benchmark reports throughput in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThroughputUnit {
+    /// Bytes per second
+    Bytes,
+    /// Elements per second
+    Elements,
+}
+
+/// The throughput of a benchmark: units of work processed per second
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputRate {
+    /// The units of work processed per second
+    pub per_second: f64,
+    /// The unit of work
+    pub unit: ThroughputUnit,
+}
+
+impl ThroughputRate {
+    // The first throughput of the benchmark with a known unit, if any (and only if its
+    // measurement is a time)
+    pub(crate) fn new(throughput: &[Throughput], time: Measurement) -> Option<Self> {
+        let time = time.as_time()?;
+
+        throughput.iter().find_map(|throughput| {
+            let unit = match throughput.unit.as_str() {
+                "bytes" => ThroughputUnit::Bytes,
+                "elements" => ThroughputUnit::Elements,
+                _ => return None,
+            };
+
+            Some(Self {
+                per_second: throughput.per_iteration as f64 * 1_000_000_000_000.0
+                    / time.as_picoseconds(),
+                unit,
+            })
+        })
+    }
+
+    /// The display width in columns of this throughput
+    #[inline]
+    pub fn width(self) -> usize {
+        display_width(&self.to_a_flex_str())
+    }
+}
+
+impl ToAFlexStr for ThroughputRate {
+    fn to_a_flex_str(&self) -> AFlexStr {
+        let rate = self.per_second;
+
+        match self.unit {
+            // Binary prefixes, just like criterion
+            ThroughputUnit::Bytes => match rate {
+                rate if rate >= 1024.0 * 1024.0 * 1024.0 * 1024.0 => {
+                    a_flex_fmt!("{:.2} TiB/s", rate / (1024.0 * 1024.0 * 1024.0 * 1024.0))
+                }
+                rate if rate >= 1024.0 * 1024.0 * 1024.0 => {
+                    a_flex_fmt!("{:.2} GiB/s", rate / (1024.0 * 1024.0 * 1024.0))
+                }
+                rate if rate >= 1024.0 * 1024.0 => {
+                    a_flex_fmt!("{:.2} MiB/s", rate / (1024.0 * 1024.0))
+                }
+                rate if rate >= 1024.0 => a_flex_fmt!("{:.2} KiB/s", rate / 1024.0),
+                rate => a_flex_fmt!("{rate:.2} B/s"),
+            },
+            ThroughputUnit::Elements => match rate {
+                rate if rate >= 1_000_000_000.0 => a_flex_fmt!("{:.2} Gelem/s", rate / 1e9),
+                rate if rate >= 1_000_000.0 => a_flex_fmt!("{:.2} Melem/s", rate / 1e6),
+                rate if rate >= 1_000.0 => a_flex_fmt!("{:.2} Kelem/s", rate / 1e3),
+                rate => a_flex_fmt!("{rate:.2} elem/s"),
+            },
+        }
+    }
+}
+
+/// How the throughput of each benchmark is displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThroughputDisplay {
+    /// Throughput is not displayed
+    #[default]
+    Off,
+    /// Throughput is displayed next to the time (ex: `5.00 us, 190.73 MiB/s`)
+    Alongside,
+    /// Throughput is displayed instead of the time
+    Instead,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(measurement: &Measurement) -> AFlexStr {
+        measurement.format(&TimeFormat::default())
+    }
+
+    #[test]
+    fn times_scale_to_largest_unit() {
+        assert!(matches!(
+            TimeUnit::try_new(1_500.0, "ns"),
+            Ok(TimeUnit::Microsecond(t)) if t == 1.5
+        ));
+        assert!(matches!(
+            TimeUnit::try_new(2_500_000.0, "us"),
+            Ok(TimeUnit::Second(t)) if t == 2.5
+        ));
+        assert!(matches!(
+            TimeUnit::try_new(999.0, "ps"),
+            Ok(TimeUnit::Picosecond(t)) if t == 999.0
+        ));
+        assert!(matches!(
+            TimeUnit::try_new(1.0, "min"),
+            Err(Error::UnknownUnit { unit }) if unit == "min"
+        ));
+
+        let time = TimeUnit::try_new(1_500.0, "ns").expect("a time");
+        assert_eq!(time.as_picoseconds(), 1_500_000.0);
+        assert_eq!(time.as_nanoseconds(), 1_500.0);
+    }
+
+    #[test]
+    fn time_formats() {
+        let time = TimeUnit::try_new(1_234.5, "ns").expect("a time");
+        let significant = TimeFormat {
+            significant_digits: Some(3),
+            ..TimeFormat::default()
+        };
+        let fixed = TimeFormat {
+            unit: UnitPolicy::Nanoseconds,
+            ..TimeFormat::default()
+        };
+        let min_time = TimeFormat {
+            min_time: Some(2_000.0),
+            ..TimeFormat::default()
+        };
+
+        assert_eq!(time.format(&TimeFormat::default()), "1.23 us");
+        assert_eq!(time.format(&significant), "1.23 us");
+        assert_eq!(time.format(&fixed), "1234.50 ns");
+        assert_eq!(time.format(&min_time), "< 2.00 us");
+        assert_eq!(format_number(12_345.0, &significant), "12300");
+    }
+
+    #[test]
+    fn new_recognizes_units() {
+        assert!(matches!(
+            Measurement::new(1.5, "ns"),
+            Measurement::Time(TimeUnit::Nanosecond(t)) if t == 1.5
+        ));
+        assert!(matches!(Measurement::new(2.0, "B"), Measurement::Bytes(b) if b == 2.0));
+        // Byte units are binary, whether written as `KB` or `KiB`
+        assert!(matches!(Measurement::new(2.0, "KB"), Measurement::Bytes(b) if b == 2048.0));
+        assert!(matches!(Measurement::new(2.0, "KiB"), Measurement::Bytes(b) if b == 2048.0));
+        assert!(matches!(Measurement::new(3.0, ""), Measurement::Count(c) if c == 3.0));
+        assert!(matches!(
+            Measurement::new(4.0, "instructions"),
+            Measurement::Custom(v, unit) if v == 4.0 && unit == "instructions"
+        ));
+    }
+
+    #[test]
+    fn scaled_formats() {
+        assert_eq!(format(&Measurement::new(1_500.0, "ns")), "1.50 us");
+        assert_eq!(format(&Measurement::new(4_608.0, "B")), "4.50 KiB");
+        assert_eq!(format(&Measurement::new(1.5, "MB")), "1.50 MiB");
+        assert_eq!(format(&Measurement::new(1_200.0, "")), "1.20K");
+        assert_eq!(
+            format(&Measurement::new(1_200_000.0, "instructions")),
+            "1.20M instructions"
+        );
+    }
+
+    #[test]
+    fn kinds_and_base_values() {
+        let time = Measurement::new(1.0, "ns");
+        let bytes = Measurement::new(1.0, "KiB");
+
+        assert_eq!(time.base_value(), 1_000.0);
+        assert_eq!(bytes.base_value(), 1_024.0);
+        assert!(time.same_kind(&Measurement::new(5.0, "ms")));
+        assert!(!time.same_kind(&bytes));
+        assert!(!Measurement::new(1.0, "x").same_kind(&Measurement::new(1.0, "y")));
+    }
+}
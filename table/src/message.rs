@@ -0,0 +1,612 @@
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use flexstr::{AFlexStr, ToFlex};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::{divan, google_benchmark, hyperfine, libtest, Error, IdParser, Metric, TablesConfig};
+
+// *** Raw JSON Data Structs ***
+
+// NOTE: These were shamelessly copied (with translation) from:
+// https://github.com/bheisler/cargo-criterion/blob/main/src/message_formats/json.rs
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct ConfidenceInterval {
+    pub(crate) estimate: f64,
+    pub(crate) lower_bound: f64,
+    pub(crate) upper_bound: f64,
+    pub(crate) unit: AFlexStr,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub(crate) struct Throughput {
+    pub(crate) per_iteration: u64,
+    pub(crate) unit: AFlexStr,
+}
+
+/// Criterion's verdict on the change of a benchmark since its previous run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeType {
+    /// The change is within the noise threshold (or not statistically significant)
+    NoChange,
+    /// The benchmark is faster
+    Improved,
+    /// The benchmark is slower
+    Regressed,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChangeDetails {
+    pub(crate) mean: ConfidenceInterval,
+    pub(crate) median: ConfidenceInterval,
+
+    pub(crate) change: ChangeType,
+}
+
+/// Raw deserialized JSON Criterion benchmark data
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkComplete {
+    pub(crate) id: AFlexStr,
+    pub(crate) report_directory: AFlexStr,
+    pub(crate) iteration_count: Vec<u64>,
+    pub(crate) measured_values: Vec<f64>,
+    pub(crate) unit: AFlexStr,
+
+    pub(crate) throughput: Vec<Throughput>,
+
+    pub(crate) typical: ConfidenceInterval,
+    pub(crate) mean: ConfidenceInterval,
+    pub(crate) median: ConfidenceInterval,
+    pub(crate) median_abs_dev: ConfidenceInterval,
+    pub(crate) slope: Option<ConfidenceInterval>,
+
+    pub(crate) change: Option<ChangeDetails>,
+}
+
+impl BenchmarkComplete {
+    pub(crate) fn estimate(&self, metric: Metric) -> ConfidenceInterval {
+        match metric {
+            Metric::Typical => self.typical.clone(),
+            Metric::Mean => self.mean.clone(),
+            Metric::Median => self.median.clone(),
+            // Criterion only has a slope when it used linear sampling - typical is the next best
+            Metric::Slope => self.slope.as_ref().unwrap_or(&self.typical).clone(),
+            // Without samples (ex: an old cargo-criterion), typical is the next best
+            Metric::TrimmedMean(fraction) => {
+                Self::trimmed_mean(self.sorted_samples(), fraction, &self.unit)
+                    .unwrap_or_else(|| self.typical.clone())
+            }
+            Metric::Percentile(percentile) => {
+                Self::percentile(self.sorted_samples(), percentile, &self.unit)
+                    .unwrap_or_else(|| self.typical.clone())
+            }
+        }
+    }
+
+    // The time per iteration of each sample, in `unit`
+    pub(crate) fn samples(&self) -> Vec<f64> {
+        self.measured_values
+            .iter()
+            .zip(&self.iteration_count)
+            .filter(|(_, count)| **count > 0)
+            .map(|(value, count)| value / *count as f64)
+            .collect()
+    }
+
+    fn sorted_samples(&self) -> Vec<f64> {
+        let mut samples = self.samples();
+        samples.sort_by(f64::total_cmp);
+        samples
+    }
+
+    // The mean of the samples left after trimming `fraction` of them from each end. The bounds are
+    // a 95% confidence interval derived from the winsorized variance (Tukey-McLaughlin)
+    fn trimmed_mean(
+        samples: Vec<f64>,
+        fraction: f64,
+        unit: &AFlexStr,
+    ) -> Option<ConfidenceInterval> {
+        let len = samples.len();
+        let trim =
+            ((len as f64 * fraction.clamp(0.0, 0.49)) as usize).min(len.saturating_sub(1) / 2);
+        let kept = samples
+            .get(trim..len - trim)
+            .filter(|kept| !kept.is_empty())?;
+        let estimate = kept.iter().sum::<f64>() / kept.len() as f64;
+
+        // The trimmed samples are replaced by the nearest kept sample
+        let (low, high) = (kept[0], kept[kept.len() - 1]);
+        let winsorized = samples.iter().map(|sample| sample.clamp(low, high));
+        let win_mean = winsorized.clone().sum::<f64>() / len as f64;
+        let margin = if len > 1 {
+            let variance = winsorized
+                .map(|sample| (sample - win_mean).powi(2))
+                .sum::<f64>()
+                / (len - 1) as f64;
+            1.96 * variance.sqrt() / (kept.len() as f64 / len as f64 * (len as f64).sqrt())
+        } else {
+            0.0
+        };
+
+        Some(ConfidenceInterval {
+            estimate,
+            lower_bound: estimate - margin,
+            upper_bound: estimate + margin,
+            unit: unit.clone(),
+        })
+    }
+
+    // The `percentile` (0 to 100) of the samples, interpolated between the two nearest. The bounds
+    // are the samples at the ranks of a 95% distribution-free confidence interval
+    fn percentile(
+        samples: Vec<f64>,
+        percentile: f64,
+        unit: &AFlexStr,
+    ) -> Option<ConfidenceInterval> {
+        let last = samples.len().checked_sub(1)?;
+        let fraction = percentile.clamp(0.0, 100.0) / 100.0;
+        let at = |rank: f64| {
+            let rank = rank.clamp(0.0, last as f64);
+            let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+            samples[below] + (samples[above] - samples[below]) * (rank - below as f64)
+        };
+
+        let len = samples.len() as f64;
+        let spread = 1.96 * (len * fraction * (1.0 - fraction)).sqrt();
+        let rank = fraction * last as f64;
+        Some(ConfidenceInterval {
+            estimate: at(rank),
+            lower_bound: at((rank - spread).floor()),
+            upper_bound: at((rank + spread).ceil()),
+            unit: unit.clone(),
+        })
+    }
+
+    // Builds a benchmark from the stats of another harness (all in nanoseconds). Other harnesses
+    // have no confidence intervals, so one standard deviation is used as the bounds
+    pub(crate) fn from_samples(
+        id: AFlexStr,
+        mean: f64,
+        median: f64,
+        stddev: f64,
+        times: Vec<f64>,
+    ) -> Self {
+        let ci = |estimate: f64, spread: f64| ConfidenceInterval {
+            estimate,
+            lower_bound: estimate - spread,
+            upper_bound: estimate + spread,
+            unit: "ns".into(),
+        };
+
+        let mut deviations: Vec<_> = times.iter().map(|time| (time - median).abs()).collect();
+        deviations.sort_by(f64::total_cmp);
+        let median_abs_dev = match deviations.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (deviations[len / 2 - 1] + deviations[len / 2]) / 2.0,
+            len => deviations[len / 2],
+        };
+
+        Self {
+            id,
+            report_directory: Default::default(),
+            iteration_count: vec![1; times.len()],
+            measured_values: times,
+            unit: "ns".into(),
+            throughput: Vec::new(),
+            // Without a slope, criterion also uses the mean as the typical time
+            typical: ci(mean, stddev),
+            mean: ci(mean, stddev),
+            median: ci(median, stddev),
+            median_abs_dev: ci(median_abs_dev, 0.0),
+            slope: None,
+            change: None,
+        }
+    }
+}
+
+/// Raw deserialized JSON Criterion benchmark group data
+#[derive(Debug, Deserialize)]
+pub struct BenchmarkGroupComplete {
+    pub(crate) group_name: AFlexStr,
+    pub(crate) benchmarks: Vec<AFlexStr>,
+    #[allow(dead_code)]
+    report_directory: AFlexStr,
+}
+
+/// Enum that can hold either Raw deserialized JSON benchmark or benchmark group data
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum RawCriterionData {
+    /// Raw benchmark data
+    Benchmark(Box<BenchmarkComplete>),
+    /// Raw benchmark group data
+    BenchmarkGroup(Box<BenchmarkGroupComplete>),
+    /// A message of a kind that isn't tabulated (ex: added by a newer cargo-criterion), which is
+    /// only counted
+    #[serde(skip_deserializing)]
+    Other,
+}
+
+/// A message of criterion's message stream, tagged by its `reason` (ex: `benchmark-complete`).
+/// Kinds of messages that aren't tabulated (ex: added by newer versions of cargo-criterion) are
+/// `RawCriterionData::Other`, and unknown fields are ignored. Messages without a `reason` are
+/// still recognized by their fields
+#[derive(Debug)]
+pub struct CriterionMessage(RawCriterionData);
+
+impl CriterionMessage {
+    /// The data of the message
+    #[inline]
+    pub fn into_raw_data(self) -> RawCriterionData {
+        self.0
+    }
+}
+
+// The `reason` of each kind of message that is tabulated
+const BENCHMARK_COMPLETE: &str = "benchmark-complete";
+const GROUP_COMPLETE: &str = "group-complete";
+
+impl<'de> Deserialize<'de> for CriterionMessage {
+    // The kind of message is found first (by its `reason`, or else its fields), so errors are
+    // those of the fields of that kind instead of a generic mismatch of every kind
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let kind = match value.get("reason") {
+            Some(reason) => match reason.as_str() {
+                Some(BENCHMARK_COMPLETE) => BENCHMARK_COMPLETE,
+                Some(GROUP_COMPLETE) => GROUP_COMPLETE,
+                Some(_) => return Ok(Self(RawCriterionData::Other)),
+                None => return Err(de::Error::custom("`reason` must be a string")),
+            },
+            None if value.get("group_name").is_some() => GROUP_COMPLETE,
+            None => BENCHMARK_COMPLETE,
+        };
+
+        let invalid = |err: serde_json::Error| de::Error::custom(format!("Invalid {kind}: {err}"));
+        let data = match kind {
+            BENCHMARK_COMPLETE => RawCriterionData::Benchmark(
+                Box::<BenchmarkComplete>::deserialize(value).map_err(invalid)?,
+            ),
+            _ => RawCriterionData::BenchmarkGroup(
+                Box::<BenchmarkGroupComplete>::deserialize(value).map_err(invalid)?,
+            ),
+        };
+        Ok(Self(data))
+    }
+}
+
+impl RawCriterionData {
+    /// Load raw Criterion JSON data from the given reader. It returns a `Vec` of enum wrapped raw
+    /// benchmark or group data
+    pub fn from_reader(r: impl Read) -> serde_json::error::Result<Vec<Self>> {
+        match Self::from_reader_recovering(r)? {
+            (data_vec, None) => Ok(data_vec),
+            (_, Some(err)) => Err(err),
+        }
+    }
+
+    /// Load raw Criterion JSON data from the given reader like `from_reader`, except a truncated
+    /// last message (ex: when cargo-criterion was interrupted) is discarded instead of failing.
+    /// It returns everything before it, and the error the truncated message caused (if any)
+    pub fn from_reader_recovering(
+        r: impl Read,
+    ) -> serde_json::error::Result<(Vec<Self>, Option<serde_json::Error>)> {
+        let reader = BufReader::new(r);
+        let mut data_vec = Vec::new();
+
+        for msg in serde_json::Deserializer::from_reader(reader).into_iter() {
+            match msg {
+                Ok(CriterionMessage(data)) => data_vec.push(data),
+                // The input ended within a message, so only the last one can be truncated
+                Err(err) if err.is_eof() => return Ok((data_vec, Some(err))),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((data_vec, None))
+    }
+
+    /// Load raw Criterion data encoded as CBOR from the given reader, as a sequence of CBOR
+    /// encoded messages (RFC 8742). Each message is self-delimiting, so no separators or length
+    /// prefixes are needed. This is much faster to parse than JSON for large suites
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor_reader(r: impl Read) -> Result<Vec<Self>, serde_cbor::Error> {
+        let reader = BufReader::new(r);
+        serde_cbor::Deserializer::from_reader(reader)
+            .into_iter()
+            .map(|msg| msg.map(CriterionMessage::into_raw_data))
+            .collect()
+    }
+
+    /// Load raw Criterion JSON data from each of the given readers (ex: from multiple machines or
+    /// CI shards). It returns a `Vec` of the data of each reader, in order
+    pub fn from_readers(
+        readers: impl IntoIterator<Item = impl Read>,
+    ) -> serde_json::error::Result<Vec<Vec<Self>>> {
+        readers.into_iter().map(Self::from_reader).collect()
+    }
+
+    /// Load each raw Criterion JSON file (`*.json`) in the directory `dir` as a labeled run. The
+    /// label is the file name without extension (ex: `2024-05-01`) and runs are sorted by label,
+    /// so dated file names give runs in chronological order
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Vec<(AFlexStr, Vec<Self>)>, Error> {
+        let mut runs = Vec::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let label = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_flex())
+                    .unwrap_or_default();
+                let data = Self::from_reader(File::open(&path)?).map_err(|err| Error::File {
+                    path: path.clone(),
+                    source: Box::new(err.into()),
+                })?;
+                runs.push((label, data));
+            }
+        }
+
+        runs.sort_by(|(label1, _), (label2, _)| label1.cmp(label2));
+        Ok(runs)
+    }
+}
+
+// *** Input Format ***
+
+/// The format of the raw benchmark data being read
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum InputFormat {
+    /// cargo-criterion's JSON message stream
+    #[default]
+    Criterion,
+    /// cargo-criterion's message stream, with each message encoded as CBOR instead of JSON
+    #[cfg(feature = "cbor")]
+    CriterionCbor,
+    /// hyperfine's JSON output (via `--export-json`), with one column per command
+    Hyperfine,
+    /// Google Benchmark's JSON output (via `--benchmark_format=json`)
+    GoogleBenchmark,
+    /// divan's JSON output, with its module/function/arg hierarchy as table/column/row
+    Divan,
+    /// The text output of `cargo bench` with the nightly libtest harness (`#[bench]`)
+    Libtest,
+    /// Detect the format of each input from its content, so inputs of different formats can be
+    /// merged (ex: Rust and C++ implementations side by side)
+    Auto,
+}
+
+impl InputFormat {
+    /// Read raw benchmark data in this format from a reader. It returns the format actually read,
+    /// which is only different for `Auto`
+    pub fn read_raw(self, r: impl Read) -> Result<(Self, Vec<RawCriterionData>), Error> {
+        match self.read_raw_recovering(r)? {
+            (format, raw_data, None) => Ok((format, raw_data)),
+            (_, _, Some(err)) => Err(err.into()),
+        }
+    }
+
+    /// Like `read_raw`, except a truncated last message of criterion's JSON message stream is
+    /// discarded instead of failing (see `RawCriterionData::from_reader_recovering`). The error it
+    /// would have failed with is returned with the data, so it can be warned about
+    pub fn read_raw_recovering(
+        self,
+        mut r: impl Read,
+    ) -> Result<(Self, Vec<RawCriterionData>, Option<serde_json::Error>), Error> {
+        let format = match self {
+            InputFormat::Auto => {
+                let mut input = Vec::new();
+                r.read_to_end(&mut input)?;
+                return Self::detect(&input)?.read_raw_recovering(input.as_slice());
+            }
+            format => format,
+        };
+
+        let raw_data = match format {
+            InputFormat::Criterion | InputFormat::Auto => {
+                let (raw_data, truncated) = RawCriterionData::from_reader_recovering(r)?;
+                return Ok((format, raw_data, truncated));
+            }
+            #[cfg(feature = "cbor")]
+            InputFormat::CriterionCbor => RawCriterionData::from_cbor_reader(r)?,
+            InputFormat::Hyperfine => hyperfine::from_reader(r)?,
+            InputFormat::GoogleBenchmark => google_benchmark::from_reader(r)?,
+            InputFormat::Divan => divan::from_reader(r)?,
+            InputFormat::Libtest => libtest::from_reader(r)?,
+        };
+        Ok((format, raw_data, None))
+    }
+
+    // Detects the format from the keys of the first JSON object of the input, or else the lines
+    // of libtest's text output (or CBOR, when it starts with a CBOR map). Empty input is
+    // criterion's (empty) message stream
+    fn detect(input: &[u8]) -> Result<Self, Error> {
+        #[cfg(feature = "cbor")]
+        if input
+            .first()
+            .is_some_and(|byte| (0xa0..=0xbf).contains(byte))
+        {
+            return Ok(InputFormat::CriterionCbor);
+        }
+
+        let first = serde_json::Deserializer::from_slice(input)
+            .into_iter::<serde_json::Value>()
+            .next();
+
+        match first {
+            None => Ok(InputFormat::Criterion),
+            Some(Ok(serde_json::Value::Object(object))) => {
+                let is_criterion = ["reason", "group_name", "typical"]
+                    .iter()
+                    .any(|key| object.contains_key(*key));
+
+                if is_criterion {
+                    Ok(InputFormat::Criterion)
+                } else if object.contains_key("results") {
+                    Ok(InputFormat::Hyperfine)
+                } else if object.contains_key("benchmarks") {
+                    Ok(InputFormat::GoogleBenchmark)
+                } else if divan::is_divan(&serde_json::Value::Object(object)) {
+                    Ok(InputFormat::Divan)
+                } else {
+                    Err(Error::UnknownInputFormat)
+                }
+            }
+            Some(_) if libtest::is_libtest(input) => Ok(InputFormat::Libtest),
+            Some(_) => Err(Error::UnknownInputFormat),
+        }
+    }
+
+    /// Returns the parser used to split the ids of benchmarks read in this format
+    pub fn id_parser(self, config: &TablesConfig) -> &dyn IdParser {
+        match self {
+            #[cfg(feature = "cbor")]
+            InputFormat::CriterionCbor => &config.id_scheme,
+            InputFormat::Criterion
+            | InputFormat::GoogleBenchmark
+            | InputFormat::Divan
+            | InputFormat::Libtest
+            | InputFormat::Auto => &config.id_scheme,
+            InputFormat::Hyperfine => &config.hyperfine,
+        }
+    }
+}
+
+#[cfg(test)]
+impl RawCriterionData {
+    // The benchmark of this message, if it is one
+    pub(crate) fn benchmark(&self) -> Option<&BenchmarkComplete> {
+        match self {
+            RawCriterionData::Benchmark(benchmark) => Some(benchmark),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BENCHMARK: &str = r#"{"reason": "benchmark-complete", "id": "Fib/std/10",
+        "report_directory": "", "iteration_count": [1], "measured_values": [110.0], "unit": "ns",
+        "throughput": [], "typical": {"estimate": 111.0, "lower_bound": 108.0, "upper_bound": 115.0,
+        "unit": "ns"}, "mean": {"estimate": 112.0, "lower_bound": 109.0, "upper_bound": 116.0,
+        "unit": "ns"}, "median": {"estimate": 110.0, "lower_bound": 108.0, "upper_bound": 113.0,
+        "unit": "ns"}, "median_abs_dev": {"estimate": 2.0, "lower_bound": 1.0, "upper_bound": 3.0,
+        "unit": "ns"}, "slope": null, "change": null}"#;
+
+    fn read(input: &str) -> Vec<RawCriterionData> {
+        RawCriterionData::from_reader(input.as_bytes()).expect("valid messages")
+    }
+
+    #[test]
+    fn criterion_benchmark() {
+        let raw_data = read(BENCHMARK);
+        assert_eq!(raw_data.len(), 1);
+
+        let benchmark = raw_data[0].benchmark().expect("a benchmark");
+        assert_eq!(benchmark.id, "Fib/std/10");
+        assert_eq!(benchmark.estimate(Metric::Typical).estimate, 111.0);
+        assert_eq!(benchmark.estimate(Metric::Median).estimate, 110.0);
+        // Without a slope, the typical time is used instead
+        assert_eq!(benchmark.estimate(Metric::Slope).estimate, 111.0);
+    }
+
+    #[test]
+    fn criterion_group_and_other_messages() {
+        let input = r#"{"reason": "group-complete", "group_name": "Fib", "benchmarks": ["Fib/std/10"],
+            "report_directory": ""}
+            {"reason": "some-future-message", "anything": 1}"#;
+
+        match read(input).as_slice() {
+            [RawCriterionData::BenchmarkGroup(group), RawCriterionData::Other] => {
+                assert_eq!(group.group_name, "Fib");
+                assert_eq!(group.benchmarks, ["Fib/std/10"]);
+            }
+            raw_data => panic!("unexpected messages: {raw_data:?}"),
+        }
+    }
+
+    #[test]
+    fn criterion_without_reason() {
+        // Older cargo-criterion versions have no `reason`, so the kind is found by its fields
+        let input = BENCHMARK.replace(r#""reason": "benchmark-complete","#, "");
+        assert!(read(&input)[0].benchmark().is_some());
+    }
+
+    #[test]
+    fn criterion_invalid_field() {
+        let input = BENCHMARK.replace(r#""id": "Fib/std/10""#, r#""id": 10"#);
+        let err = RawCriterionData::from_reader(input.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("Invalid benchmark-complete"));
+    }
+
+    #[test]
+    fn detect() {
+        let detect = |input: &str| InputFormat::detect(input.as_bytes()).ok();
+
+        assert_eq!(detect(""), Some(InputFormat::Criterion));
+        assert_eq!(detect(BENCHMARK), Some(InputFormat::Criterion));
+        assert_eq!(
+            detect(r#"{"group_name": "Fib", "benchmarks": []}"#),
+            Some(InputFormat::Criterion)
+        );
+        assert_eq!(
+            detect(r#"{"results": [{"command": "sleep 1", "mean": 1.0, "median": 1.0}]}"#),
+            Some(InputFormat::Hyperfine)
+        );
+        assert_eq!(
+            detect(r#"{"context": {}, "benchmarks": []}"#),
+            Some(InputFormat::GoogleBenchmark)
+        );
+        assert_eq!(
+            detect(r#"{"fib": {"recursive": {"10": {"median": 101.0}}}}"#),
+            Some(InputFormat::Divan)
+        );
+        assert_eq!(
+            detect("running 1 test\ntest fib::n10 ... bench:       1,234 ns/iter (+/- 56)\n"),
+            Some(InputFormat::Libtest)
+        );
+    }
+
+    #[test]
+    fn detect_unknown() {
+        let detect = |input: &str| InputFormat::detect(input.as_bytes());
+
+        // Objects without divan's `median` stat aren't divan's tree
+        assert!(matches!(
+            detect(r#"{"name": "x", "value": 1}"#),
+            Err(Error::UnknownInputFormat)
+        ));
+        assert!(matches!(
+            detect(r#"{"fib": {}}"#),
+            Err(Error::UnknownInputFormat)
+        ));
+        assert!(matches!(
+            detect("not a benchmark\n"),
+            Err(Error::UnknownInputFormat)
+        ));
+    }
+
+    #[test]
+    fn criterion_truncated() {
+        let input = format!("{BENCHMARK}\n{}", &BENCHMARK[..40]);
+
+        let (raw_data, err) =
+            RawCriterionData::from_reader_recovering(input.as_bytes()).expect("recovered");
+        assert_eq!(raw_data.len(), 1);
+        assert!(err.is_some());
+        assert!(RawCriterionData::from_reader(input.as_bytes()).is_err());
+    }
+}
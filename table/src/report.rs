@@ -0,0 +1,263 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use flexstr::{a_flex_fmt, AFlexStr, ToAFlexStr};
+use indexmap::IndexMap;
+
+use crate::chart;
+use crate::formatter::{GFMFormatter, HtmlFormatter};
+use crate::{
+    Comparison, CriterionTableData, Formatter, Measurement, Metadata, Table, TablesConfig,
+    TimeFormat, TocEntry, BUFFER_CAPACITY,
+};
+
+const SUMMARY_PAGE: &str = "README.md";
+const HTML_SUMMARY_PAGE: &str = "index.html";
+
+// *** Report ***
+
+/// A multi-file markdown report: a concise summary page (winners and geometric mean comparisons
+/// per table) linking to a detail page per table (the full table plus all statistics)
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// The file name and contents of each page. The summary page is always first
+    pub pages: IndexMap<AFlexStr, String>,
+}
+
+impl Report {
+    /// Build a report from processed table data, using the `GFMFormatter` `f` to render each
+    /// table
+    pub fn new(data: &CriterionTableData, mut f: GFMFormatter, config: &TablesConfig) -> Self {
+        let mut pages = IndexMap::with_capacity(data.tables.len() + 1);
+        // Reserve the first spot for the summary page
+        pages.insert(SUMMARY_PAGE.into(), String::new());
+        let mut summary = String::with_capacity(BUFFER_CAPACITY);
+
+        f.metadata(&Metadata::new(&config.footer));
+        let top_comments = config.environment.top_comments(&config.top_comments, &f);
+        f.start(
+            &mut summary,
+            &top_comments,
+            &config.toc.toc(data.toc_entries()),
+        );
+
+        for table in data.tables.values() {
+            let page_name = a_flex_fmt!("{}.md", f.anchor(&table.name));
+            Self::write_summary(&mut summary, table, &page_name);
+            pages.insert(page_name, Self::make_detail_page(table, &mut f, config));
+        }
+
+        f.end(&mut summary);
+        pages[0] = summary;
+
+        Self { pages }
+    }
+
+    /// Build a report as a small static HTML site (ex: for Github Pages): an `index.html` summary
+    /// page linking to a page per table, holding the table and an SVG bar chart of its timings
+    pub fn new_html(data: &CriterionTableData, config: &TablesConfig) -> Self {
+        let mut pages = IndexMap::with_capacity(data.tables.len() + 1);
+        // Reserve the first spot for the summary page
+        pages.insert(HTML_SUMMARY_PAGE.into(), String::new());
+        let mut summary = String::with_capacity(BUFFER_CAPACITY);
+        // Collected once, so every page has the same provenance
+        let metadata = Metadata::new(&config.footer);
+
+        let mut f = HtmlFormatter::new(config.html.clone());
+        f.metadata(&metadata);
+        let top_comments = config.environment.top_comments(&config.top_comments, &f);
+        f.start(&mut summary, &top_comments, &config.toc.toc(Vec::new()));
+
+        for table in data.tables.values() {
+            let page_name = a_flex_fmt!("{}.html", f.anchor(&table.name));
+            Self::write_html_summary(&mut summary, table, &page_name);
+            pages.insert(
+                page_name,
+                Self::make_html_detail_page(table, config, &metadata),
+            );
+        }
+
+        f.end(&mut summary);
+        pages[0] = summary;
+
+        Self { pages }
+    }
+
+    /// Write each page of the report into the directory `dir` (created if needed)
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        for (name, page) in &self.pages {
+            fs::write(dir.join(name.as_str()), page)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_summary(buffer: &mut String, table: &Table, page_name: &str) {
+        // The link text becomes the anchor, so table of contents links still work
+        buffer.push_str("### [");
+        buffer.push_str(&table.name);
+        buffer.push_str("](");
+        buffer.push_str(page_name);
+        buffer.push_str(")\n\n");
+
+        let geomeans = Self::geomeans(table);
+
+        if let Some((name, geomean)) = Self::winner(&geomeans) {
+            buffer.push_str("Winner: **`");
+            buffer.push_str(name);
+            buffer.push_str("`** (");
+            buffer.push_str(&geomean.to_a_flex_str());
+            buffer.push_str(" geomean)\n\n");
+        }
+
+        buffer.push_str("| Column | Geomean |\n");
+        buffer.push_str("|:-------|:--------|\n");
+
+        for (name, geomean) in geomeans {
+            buffer.push_str("| `");
+            buffer.push_str(name);
+            buffer.push_str("` | ");
+            buffer.push_str(&geomean.to_a_flex_str());
+            buffer.push_str(" |\n");
+        }
+
+        buffer.push('\n');
+    }
+
+    // The geometric mean of the comparisons of each column that has any
+    fn geomeans(table: &Table) -> Vec<(&AFlexStr, Comparison)> {
+        // Skip the first column - it only holds the row names
+        table.columns.0[1..]
+            .iter()
+            .filter_map(|col| table.geomean(&col.name).map(|geomean| (&col.name, geomean)))
+            .collect()
+    }
+
+    // The column with the best geomean. A winner only makes sense if there is something to
+    // compare to
+    fn winner<'a, 'b>(
+        geomeans: &'b [(&'a AFlexStr, Comparison)],
+    ) -> Option<&'b (&'a AFlexStr, Comparison)> {
+        if geomeans.len() > 1 {
+            geomeans
+                .iter()
+                .max_by(|(_, c1), (_, c2)| c1.ratio().total_cmp(&c2.ratio()))
+        } else {
+            None
+        }
+    }
+
+    fn make_detail_page(table: &Table, f: &mut GFMFormatter, config: &TablesConfig) -> String {
+        let mut buffer = String::with_capacity(BUFFER_CAPACITY);
+
+        buffer.push_str("[Back to summary](");
+        buffer.push_str(SUMMARY_PAGE);
+        buffer.push_str(")\n\n");
+
+        CriterionTableData::write_table(table, f, config, &mut buffer);
+
+        buffer.push_str("#### Statistics\n\n");
+        buffer.push_str("| Row | Column | Typical | Lower Bound | Upper Bound | Mean | Median ");
+        buffer.push_str("| Median Abs Dev | Slope |\n");
+        buffer.push_str("|:----|:-------|:--------|:------------|:------------|:-----|:-------");
+        buffer.push_str("|:---------------|:------|\n");
+
+        for row in table.rows.values() {
+            for (name, col) in &row.column_data {
+                let stats = &col.stats;
+
+                Self::write_name_cell(&mut buffer, &row.name);
+                Self::write_name_cell(&mut buffer, name);
+                for time in [
+                    Some(&col.measurement),
+                    Some(&stats.lower_bound),
+                    Some(&stats.upper_bound),
+                    Some(&stats.mean),
+                    Some(&stats.median),
+                    Some(&stats.median_abs_dev),
+                    stats.slope.as_ref(),
+                ] {
+                    Self::write_time_cell(&mut buffer, time, &table.time_format);
+                }
+                buffer.push_str("|\n");
+            }
+        }
+
+        buffer.push('\n');
+        f.end(&mut buffer);
+        buffer
+    }
+
+    fn write_html_summary(buffer: &mut String, table: &Table, page_name: &str) {
+        buffer.push_str("<h3><a href=\"");
+        buffer.push_str(&HtmlFormatter::escape(page_name));
+        buffer.push_str("\">");
+        buffer.push_str(&HtmlFormatter::escape(&table.name));
+        buffer.push_str("</a></h3>\n");
+
+        let geomeans = Self::geomeans(table);
+
+        if let Some((name, geomean)) = Self::winner(&geomeans) {
+            buffer.push_str("<p>Winner: <b><code>");
+            buffer.push_str(&HtmlFormatter::escape(name));
+            buffer.push_str("</code></b> (");
+            buffer.push_str(&geomean.to_a_flex_str());
+            buffer.push_str(" geomean)</p>\n");
+        }
+
+        buffer.push_str("<table>\n<tr><th>Column</th><th>Geomean</th></tr>\n");
+
+        for (name, geomean) in geomeans {
+            buffer.push_str("<tr><td><code>");
+            buffer.push_str(&HtmlFormatter::escape(name));
+            buffer.push_str("</code></td><td>");
+            buffer.push_str(&geomean.to_a_flex_str());
+            buffer.push_str("</td></tr>\n");
+        }
+
+        buffer.push_str("</table>\n");
+    }
+
+    fn make_html_detail_page(table: &Table, config: &TablesConfig, metadata: &Metadata) -> String {
+        let mut buffer = String::with_capacity(BUFFER_CAPACITY);
+        let mut f = HtmlFormatter::new(config.html.clone());
+        f.metadata(metadata);
+
+        let toc = config.toc.toc(vec![TocEntry::new(&table.name)]);
+        f.start(&mut buffer, &IndexMap::new(), &toc);
+        buffer.push_str("<p><a href=\"");
+        buffer.push_str(HTML_SUMMARY_PAGE);
+        buffer.push_str("\">Back to summary</a></p>\n");
+
+        CriterionTableData::write_table(table, &mut f, config, &mut buffer);
+        buffer.push_str(&chart::bar_chart(table, &config.chart));
+
+        f.end(&mut buffer);
+        buffer
+    }
+
+    fn write_name_cell(buffer: &mut String, name: &str) {
+        if name.is_empty() {
+            buffer.push_str("| ");
+        } else {
+            buffer.push_str("| `");
+            buffer.push_str(name);
+            buffer.push_str("` ");
+        }
+    }
+
+    fn write_time_cell(buffer: &mut String, time: Option<&Measurement>, format: &TimeFormat) {
+        match time {
+            Some(time) => {
+                buffer.push_str("| `");
+                buffer.push_str(&time.format(format));
+                buffer.push_str("` ");
+            }
+            None => buffer.push_str("| `N/A` "),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::env;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use flexstr::AFlexStr;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{
+    CriterionTableData, Error, Formatter, IdParser, Metric, RawCriterionData, TablesConfig,
+    TocEntry, BUFFER_CAPACITY,
+};
+
+// *** Cargo Metadata ***
+
+// NOTE: Only the few fields we need from `cargo metadata --format-version 1`
+
+#[derive(Deserialize)]
+struct Package {
+    name: AFlexStr,
+    id: AFlexStr,
+    manifest_path: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<AFlexStr>,
+}
+
+impl Metadata {
+    fn load(manifest_path: Option<&Path>) -> Result<Self, Error> {
+        let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let mut cmd = Command::new(cargo);
+        cmd.args(["metadata", "--no-deps", "--format-version", "1"]);
+        if let Some(manifest_path) = manifest_path {
+            cmd.arg("--manifest-path").arg(manifest_path);
+        }
+
+        let output = cmd.output()?;
+        if !output.status.success() {
+            return Err(Error::CargoMetadata {
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+}
+
+// *** Workspace Data ***
+
+/// Processed benchmark data for each member of a cargo workspace that has any
+#[derive(Clone, Debug)]
+pub struct WorkspaceData {
+    members: IndexMap<AFlexStr, CriterionTableData>,
+}
+
+impl WorkspaceData {
+    /// Discover the members of the workspace (using `cargo metadata` and the optional
+    /// `manifest_path`) and load the raw Criterion JSON file named `file_name` from the
+    /// directory of each member, tabulating the `metric` estimate of each benchmark (with ids split
+    /// via `id_parser`). Members
+    /// without this file are skipped
+    pub fn discover(
+        manifest_path: Option<&Path>,
+        file_name: impl AsRef<Path>,
+        metric: Metric,
+        id_parser: &dyn IdParser,
+    ) -> Result<Self, Error> {
+        let metadata = Metadata::load(manifest_path)?;
+        let mut members = IndexMap::new();
+
+        for package in &metadata.packages {
+            if !metadata.workspace_members.contains(&package.id) {
+                continue;
+            }
+
+            let path = package.manifest_path.with_file_name(file_name.as_ref());
+            match File::open(&path) {
+                Ok(f) => {
+                    let raw_data = RawCriterionData::from_reader(f)?;
+                    let data = CriterionTableData::from_raw(&raw_data, metric, id_parser).map_err(
+                        |err| Error::File {
+                            path: path.clone(),
+                            source: Box::new(err),
+                        },
+                    )?;
+                    members.insert(package.name.clone(), data);
+                }
+                // Not every member has benchmarks
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        if members.is_empty() {
+            Err(Error::NoWorkspaceData {
+                file_name: file_name.as_ref().to_path_buf(),
+            })
+        } else {
+            Ok(Self { members })
+        }
+    }
+
+    /// Apply the options in `config` that change the processed data to each member's data. This
+    /// should be called once, before any output is generated
+    pub fn apply_config(&mut self, config: &TablesConfig) -> Result<(), Error> {
+        for data in self.members.values_mut() {
+            data.apply_config(config)?;
+        }
+
+        Ok(())
+    }
+
+    /// Given a `Formatter` and `TablesConfig`, generate one document with a section per workspace
+    /// member (and a combined table of contents) as a `String`
+    pub fn make_tables(&self, mut f: impl Formatter, config: &TablesConfig) -> String {
+        let mut buffer = String::with_capacity(BUFFER_CAPACITY);
+
+        // Start of doc
+        let toc: Vec<_> = self
+            .members
+            .iter()
+            .map(|(name, data)| TocEntry {
+                name,
+                children: data.toc_entries(),
+            })
+            .collect();
+        f.metadata(&crate::Metadata::new(&config.footer));
+        if let Some(embed) = &config.embed {
+            f.embed(embed);
+        }
+        let top_comments = config.environment.top_comments(&config.top_comments, &f);
+        f.start(&mut buffer, &top_comments, &config.toc.toc(toc));
+
+        for (name, data) in &self.members {
+            f.start_section(&mut buffer, name);
+            data.write_tables(&mut f, config, &mut buffer);
+            f.end_section(&mut buffer);
+        }
+
+        // End of doc
+        f.end(&mut buffer);
+
+        buffer
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use flexstr::{a_flex_fmt, AFlexStr, ToFlex};
+use rust_xlsxwriter::{
+    ColNum, Color, ConditionalFormatCell, ConditionalFormatCellRule, Format, RowNum, Workbook,
+    Worksheet, XlsxError,
+};
+
+use crate::{CriterionTableData, Error, Table};
+
+// Excel limits sheet names to 31 characters, none of which can be one of these
+const MAX_SHEET_NAME: usize = 31;
+const INVALID_SHEET_CHARS: &[char] = &['[', ']', ':', '*', '?', '/', '\\'];
+
+// The widths (in characters) of the row names and of each data column
+const NAME_WIDTH: f64 = 24.0;
+const DATA_WIDTH: f64 = 18.0;
+
+// *** Formats ***
+
+// A bold header and times and speedups with two decimals. Faster cells are green and slower cells
+// red, via conditional formatting
+struct Formats {
+    header: Format,
+    time: Format,
+    speedup: Format,
+    faster: ConditionalFormatCell,
+    slower: ConditionalFormatCell,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let faster = Format::new()
+            .set_font_color(Color::RGB(0x006100))
+            .set_background_color(Color::RGB(0xC6EFCE));
+        let slower = Format::new()
+            .set_font_color(Color::RGB(0x9C0006))
+            .set_background_color(Color::RGB(0xFFC7CE));
+
+        Self {
+            header: Format::new().set_bold(),
+            time: Format::new().set_num_format("0.00"),
+            speedup: Format::new().set_num_format("0.00\"x\""),
+            faster: ConditionalFormatCell::new()
+                .set_rule(ConditionalFormatCellRule::GreaterThan(1))
+                .set_format(faster),
+            slower: ConditionalFormatCell::new()
+                .set_rule(ConditionalFormatCellRule::LessThan(1))
+                .set_format(slower),
+        }
+    }
+}
+
+// *** Workbook ***
+
+/// Write each table to a worksheet of a single Excel workbook (`.xlsx`). Like CSV output, each
+/// column has the time in nanoseconds and the speedup ratio as numeric cells. Speedups are green
+/// when faster than the baseline and red when slower, and the header row and row names are frozen
+pub fn write_workbook(data: &CriterionTableData, mut w: impl Write) -> Result<(), Error> {
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+    let mut names = HashSet::new();
+
+    for table in data.tables() {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(sheet_name(table.name(), &mut names).as_str())?;
+        write_worksheet(worksheet, table, &formats)?;
+    }
+    // A workbook must have at least one sheet
+    if names.is_empty() {
+        workbook.add_worksheet().set_name("Benchmarks")?;
+    }
+
+    w.write_all(&workbook.save_to_buffer()?)?;
+    Ok(())
+}
+
+// A valid sheet name, unique within the workbook
+fn sheet_name(name: &str, names: &mut HashSet<AFlexStr>) -> AFlexStr {
+    let name: String = name
+        .chars()
+        .map(|ch| {
+            if INVALID_SHEET_CHARS.contains(&ch) {
+                '_'
+            } else {
+                ch
+            }
+        })
+        .take(MAX_SHEET_NAME)
+        .collect();
+    let name = if name.trim().is_empty() {
+        "Sheet".to_string()
+    } else {
+        name
+    };
+
+    let mut unique = name.to_flex();
+    let mut count = 1;
+    // Excel compares sheet names case insensitively
+    while !names.insert(unique.to_lowercase().to_flex()) {
+        count += 1;
+        let suffix = format!(" ({count})");
+        let prefix: String = name.chars().take(MAX_SHEET_NAME - suffix.len()).collect();
+        unique = a_flex_fmt!("{prefix}{suffix}");
+    }
+    unique
+}
+
+// *** Worksheet ***
+
+// The header row (the table name, then the time and speedup of each column) and a row per row of
+// the table
+fn write_worksheet(
+    worksheet: &mut Worksheet,
+    table: &Table,
+    formats: &Formats,
+) -> Result<(), XlsxError> {
+    let columns: Vec<_> = table.column_names().collect();
+
+    worksheet.write_string_with_format(0, 0, table.name().as_str(), &formats.header)?;
+    for (idx, column) in columns.iter().enumerate() {
+        let col = time_column(idx);
+        let time = a_flex_fmt!("{column} (ns)");
+        let speedup = a_flex_fmt!("{column} (speedup)");
+        worksheet.write_string_with_format(0, col, time.as_str(), &formats.header)?;
+        worksheet.write_string_with_format(0, col + 1, speedup.as_str(), &formats.header)?;
+    }
+
+    let mut last_row = 0;
+    for (idx, row) in table.rows().enumerate() {
+        let row_num = idx as RowNum + 1;
+        worksheet.write_string(row_num, 0, row.name().as_str())?;
+
+        for (col_idx, column) in columns.iter().enumerate() {
+            if let Some(cell) = row.cell(column) {
+                let col = time_column(col_idx);
+                let time = cell.time().export_value();
+                worksheet.write_number_with_format(row_num, col, time, &formats.time)?;
+                let ratio = cell.comparison().ratio();
+                worksheet.write_number_with_format(row_num, col + 1, ratio, &formats.speedup)?;
+            }
+        }
+        last_row = row_num;
+    }
+
+    // Speedups above one are faster than the baseline, and below one slower
+    if last_row > 0 {
+        for idx in 0..columns.len() {
+            let col = time_column(idx) + 1;
+            worksheet.add_conditional_format(1, col, last_row, col, &formats.faster)?;
+            worksheet.add_conditional_format(1, col, last_row, col, &formats.slower)?;
+        }
+    }
+
+    worksheet.set_freeze_panes(1, 1)?;
+    worksheet.set_column_width(0, NAME_WIDTH)?;
+    if !columns.is_empty() {
+        worksheet.set_column_range_width(1, time_column(columns.len()) - 1, DATA_WIDTH)?;
+    }
+    Ok(())
+}
+
+// The worksheet column of the time of the table column `idx` (its speedup follows it)
+#[inline]
+fn time_column(idx: usize) -> ColNum {
+    (idx * 2 + 1) as ColNum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::builder::TableBuilder;
+
+    #[test]
+    fn sheet_names() {
+        let mut names = HashSet::new();
+
+        assert_eq!(sheet_name("Fib/Parse [u8]?", &mut names), "Fib_Parse _u8__");
+        assert_eq!(sheet_name("  ", &mut names), "Sheet");
+        // Unique, ignoring case
+        assert_eq!(sheet_name("sheet", &mut names), "sheet (2)");
+        assert_eq!(sheet_name("Sheet", &mut names), "Sheet (3)");
+    }
+
+    #[test]
+    fn long_sheet_names() {
+        let mut names = HashSet::new();
+        let long = "A very long table name of many words";
+
+        let first = sheet_name(long, &mut names);
+        assert_eq!(first.chars().count(), MAX_SHEET_NAME);
+        let second = sheet_name(long, &mut names);
+        assert_eq!(second.chars().count(), MAX_SHEET_NAME);
+        assert!(second.ends_with(" (2)"));
+    }
+
+    #[test]
+    fn workbook() {
+        let json = r#"{"reason":"benchmark-complete","id":"Fib/std/10","report_directory":"",
+            "iteration_count":[1],"measured_values":[1.0],"unit":"ns","throughput":[],
+            "typical":{"estimate":1.0,"lower_bound":1.0,"upper_bound":1.0,"unit":"ns"},
+            "mean":{"estimate":1.0,"lower_bound":1.0,"upper_bound":1.0,"unit":"ns"},
+            "median":{"estimate":1.0,"lower_bound":1.0,"upper_bound":1.0,"unit":"ns"},
+            "median_abs_dev":{"estimate":0.0,"lower_bound":0.0,"upper_bound":0.0,"unit":"ns"},
+            "slope":null,"change":null}"#;
+        let data = TableBuilder::new()
+            .add_reader(json.as_bytes())
+            .build_data()
+            .expect("valid benchmark");
+
+        let mut buffer = Vec::new();
+        write_workbook(&data, &mut buffer).expect("written");
+        // An xlsx file is a zip archive
+        assert!(buffer.starts_with(b"PK\x03\x04"));
+    }
+}